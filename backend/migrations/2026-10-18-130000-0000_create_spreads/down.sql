@@ -0,0 +1,2 @@
+DROP INDEX spreads_user_id_idx;
+DROP TABLE spreads;
@@ -0,0 +1,15 @@
+CREATE TABLE spreads
+(
+    id         uuid PRIMARY KEY,
+    user_id    uuid REFERENCES users (id) ON DELETE CASCADE NOT NULL,
+    created_at timestamp                                    NOT NULL DEFAULT now(),
+    updated_at timestamp                                    NOT NULL DEFAULT now(),
+    name       text                                         NOT NULL,
+    positions  jsonb                                        NOT NULL DEFAULT '[]'::jsonb,
+    shared     boolean                                      NOT NULL DEFAULT false,
+    deleted_at timestamp
+);
+
+CREATE INDEX spreads_user_id_idx ON spreads (user_id);
+
+SELECT diesel_manage_updated_at('spreads');
@@ -0,0 +1 @@
+ALTER TABLE readings ADD COLUMN seed text;
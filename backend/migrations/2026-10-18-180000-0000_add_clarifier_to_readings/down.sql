@@ -0,0 +1,4 @@
+DROP INDEX readings_clarified_reading_id_idx;
+ALTER TABLE readings
+    DROP COLUMN clarified_position,
+    DROP COLUMN clarified_reading_id;
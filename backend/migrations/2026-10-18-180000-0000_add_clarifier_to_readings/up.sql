@@ -0,0 +1,4 @@
+ALTER TABLE readings
+    ADD COLUMN clarified_reading_id uuid REFERENCES readings (id),
+    ADD COLUMN clarified_position int4;
+CREATE INDEX readings_clarified_reading_id_idx ON readings (clarified_reading_id);
@@ -0,0 +1,3 @@
+ALTER TABLE readings
+    DROP COLUMN backend,
+    DROP COLUMN model;
@@ -0,0 +1,3 @@
+ALTER TABLE readings
+    ADD COLUMN backend text,
+    ADD COLUMN model text;
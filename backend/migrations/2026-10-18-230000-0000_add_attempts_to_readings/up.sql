@@ -0,0 +1,2 @@
+ALTER TABLE readings
+    ADD COLUMN interpretation_attempts integer;
@@ -0,0 +1,4 @@
+ALTER TABLE readings
+    DROP COLUMN prompt_tokens,
+    DROP COLUMN completion_tokens,
+    DROP COLUMN cost_usd;
@@ -0,0 +1,4 @@
+ALTER TABLE readings
+    ADD COLUMN prompt_tokens integer,
+    ADD COLUMN completion_tokens integer,
+    ADD COLUMN cost_usd double precision;
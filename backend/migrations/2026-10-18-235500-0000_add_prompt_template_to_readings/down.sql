@@ -0,0 +1,3 @@
+ALTER TABLE readings
+    DROP COLUMN prompt_template,
+    DROP COLUMN prompt_template_version;
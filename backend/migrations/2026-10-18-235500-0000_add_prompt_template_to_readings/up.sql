@@ -0,0 +1,3 @@
+ALTER TABLE readings
+    ADD COLUMN prompt_template text,
+    ADD COLUMN prompt_template_version integer;
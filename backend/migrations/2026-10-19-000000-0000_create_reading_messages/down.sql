@@ -0,0 +1,2 @@
+DROP INDEX reading_messages_reading_id_idx;
+DROP TABLE reading_messages;
@@ -0,0 +1,11 @@
+CREATE TABLE reading_messages
+(
+    id         uuid PRIMARY KEY,
+    reading_id uuid REFERENCES readings (id) ON DELETE CASCADE NOT NULL,
+    created_at timestamp                                       NOT NULL DEFAULT now(),
+    role       text                                            NOT NULL,
+    content    text                                            NOT NULL,
+    error      text
+);
+
+CREATE INDEX reading_messages_reading_id_idx ON reading_messages (reading_id, created_at);
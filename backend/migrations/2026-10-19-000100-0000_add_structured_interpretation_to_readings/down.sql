@@ -0,0 +1 @@
+ALTER TABLE readings DROP COLUMN interpretation_structured;
@@ -0,0 +1 @@
+ALTER TABLE readings ADD COLUMN interpretation_structured jsonb;
@@ -0,0 +1,2 @@
+ALTER TABLE users DROP COLUMN reading_style, DROP COLUMN reading_length;
+ALTER TABLE readings DROP COLUMN style, DROP COLUMN length;
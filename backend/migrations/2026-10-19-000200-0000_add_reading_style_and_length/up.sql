@@ -0,0 +1,6 @@
+ALTER TABLE readings
+    ADD COLUMN style text NOT NULL DEFAULT 'psychological',
+    ADD COLUMN length text NOT NULL DEFAULT 'standard';
+ALTER TABLE users
+    ADD COLUMN reading_style text NOT NULL DEFAULT 'psychological',
+    ADD COLUMN reading_length text NOT NULL DEFAULT 'standard';
@@ -1,6 +1,10 @@
 use crate::handler::{
-    create_interpretation, create_reading, create_user, delete_interpretation, get_interpretation,
-    get_interpretation_history, get_stats, get_user, log_in, notify_websocket_handler, update_user,
+    create_clarifier, create_commitment, create_draw_session, create_interpretation,
+    create_reading, create_reading_message, create_spread, create_user, delete_interpretation,
+    delete_spread, finalize_draw_session, get_fairness, get_interpretation,
+    get_interpretation_history, get_spread, get_stats, get_user, list_backends, list_spreads,
+    log_in, notify_websocket_handler, parse_cards, replay_interpretation, stream_interpretation,
+    update_spread, update_user,
 };
 use crate::middleware;
 use crate::middleware::locale;
@@ -26,6 +30,18 @@ pub fn create_app(state: AppState) -> Router {
     Router::new()
         .route("/metrics", get(move || ready(handle.render())))
         .route("/api/v1/reading", post(create_reading::create_reading))
+        .route(
+            "/api/v1/reading/commitment",
+            post(create_commitment::create_commitment),
+        )
+        .route(
+            "/api/v1/draw-session",
+            post(create_draw_session::create_draw_session),
+        )
+        .route(
+            "/api/v1/draw-session/{id}",
+            post(finalize_draw_session::finalize_draw_session),
+        )
         .route(
             "/api/v1/interpretation/history",
             get(get_interpretation_history::get_interpretation_history),
@@ -38,6 +54,22 @@ pub fn create_app(state: AppState) -> Router {
             "/api/v1/interpretation/{id}",
             get(get_interpretation::get_interpretation),
         )
+        .route(
+            "/api/v1/interpretation/{id}/replay",
+            get(replay_interpretation::replay_interpretation),
+        )
+        .route(
+            "/api/v1/interpretation/{id}/stream",
+            get(stream_interpretation::stream_interpretation),
+        )
+        .route(
+            "/api/v1/interpretation/{id}/clarifier",
+            post(create_clarifier::create_clarifier),
+        )
+        .route(
+            "/api/v1/interpretation/{id}/messages",
+            post(create_reading_message::create_reading_message),
+        )
         .route(
             "/api/v1/interpretation/{id}",
             delete(delete_interpretation::delete_interpretation),
@@ -46,7 +78,15 @@ pub fn create_app(state: AppState) -> Router {
             "/api/v1/interpretation",
             post(create_interpretation::create_interpretation),
         )
+        .route("/api/v1/cards/parse", post(parse_cards::parse_cards))
+        .route("/api/v1/backends", get(list_backends::list_backends))
         .route("/api/v1/stats", get(get_stats::get_stats))
+        .route("/api/v1/stats/fairness", get(get_fairness::get_fairness))
+        .route("/api/v1/spreads", get(list_spreads::list_spreads))
+        .route("/api/v1/spreads", post(create_spread::create_spread))
+        .route("/api/v1/spreads/{id}", get(get_spread::get_spread))
+        .route("/api/v1/spreads/{id}", patch(update_spread::update_spread))
+        .route("/api/v1/spreads/{id}", delete(delete_spread::delete_spread))
         .route("/api/v1/user", post(create_user::create_user))
         .route("/api/v1/user", get(get_user::get_user))
         .route("/api/v1/user", patch(update_user::update_user))
@@ -97,6 +137,29 @@ pub async fn create_test_app() -> (AppState, Router) {
         database_url,
         openai_api_key: "dummy".to_string(),
         google_api_key: "dummy".to_string(),
+        anthropic_api_key: "dummy".to_string(),
+        local_llm: None,
+        interpretation_providers: None,
+        interpretation_models: std::collections::HashMap::from([(
+            webtarot_shared::explain::InterpretationBackend::ChatGPT,
+            webtarot_shared::provider::ModelConfig {
+                allowed_models: vec!["gpt-5-mini".to_string()],
+                ..webtarot_shared::provider::ModelConfig::new("gpt-5.1")
+            },
+        )]),
+        // Retry at once, and fail over to Claude
+        interpretation_resilience: webtarot_shared::resilience::ResilienceConfig {
+            retry: webtarot_shared::resilience::RetryPolicy {
+                base_delay: std::time::Duration::from_millis(1),
+                ..Default::default()
+            },
+            failover: vec![webtarot_shared::explain::InterpretationBackend::Claude],
+            ..Default::default()
+        },
+        interpretation_prices: Default::default(),
+        interpretation_daily_budget: Some(10.0),
+        prompt_templates: Default::default(),
+        structured_interpretations: false,
     })
     .await;
 
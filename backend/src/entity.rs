@@ -1,4 +1,9 @@
+pub mod card;
+pub mod clarifier;
+pub mod draw_session;
 pub mod interpretation;
 pub mod reading;
+pub mod reading_message;
+pub mod spread;
 pub mod stats;
 pub mod user;
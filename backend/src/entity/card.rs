@@ -0,0 +1,57 @@
+use crate::error::AppError;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use webtarot_shared::model::{Card, DeckTradition};
+use webtarot_shared::notation::ParseCardError;
+
+/// Cards typed by hand, e.g. "Ás de Copas", "XVI" or "10S-R", read with the names and
+/// numbering of `tradition`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseCardsRequest {
+    pub cards: Vec<String>,
+    #[serde(default)]
+    pub tradition: DeckTradition,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedCard {
+    pub input: String,
+    pub card: Card,
+    /// Name of the card in the tradition and the request locale.
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseCardsResponse {
+    pub cards: Vec<ParsedCard>,
+}
+
+impl ParseCardsRequest {
+    pub fn parse(&self) -> Result<ParseCardsResponse, AppError> {
+        let cards = self
+            .cards
+            .iter()
+            .map(|input| {
+                let card = Card::parse_in(input, self.tradition).map_err(|e| {
+                    AppError::ValidateError(match e {
+                        ParseCardError::NotReversible(_) => {
+                            t!("errors.card_not_reversible", card = input).to_string()
+                        }
+                        ParseCardError::Empty | ParseCardError::Unknown(_) => {
+                            t!("errors.unknown_card", card = input).to_string()
+                        }
+                    })
+                })?;
+                Ok(ParsedCard {
+                    input: input.clone(),
+                    card,
+                    name: card.in_tradition(self.tradition).to_string(),
+                })
+            })
+            .collect::<Result<_, AppError>>()?;
+        Ok(ParseCardsResponse { cards })
+    }
+}
@@ -0,0 +1,110 @@
+use crate::entity::reading::Reading;
+use crate::entity::user::User;
+use crate::error::AppError;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use webtarot_shared::entropy::Entropy;
+use webtarot_shared::model::{Card, Deck, DeckOptions, DeckSeed};
+
+/// The position of an earlier reading that a clarifier reading's cards were drawn for.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Clarifies {
+    pub reading_id: uuid::Uuid,
+    /// Zero-based index into the clarified reading's cards.
+    pub position: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateClarifierRequest {
+    /// Zero-based index of the card to clarify.
+    pub position: usize,
+    #[serde(default = "default_clarifier_cards")]
+    pub cards: u8,
+}
+
+fn default_clarifier_cards() -> u8 {
+    1
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateClarifierResponse {
+    /// Id of the clarifier reading, whose interpretation is fetched like any other.
+    pub interpretation_id: String,
+    pub clarifies: Clarifies,
+    pub shuffled_times: usize,
+    pub cards: Vec<Card>,
+}
+
+impl From<Reading> for CreateClarifierResponse {
+    fn from(reading: Reading) -> Self {
+        Self {
+            interpretation_id: reading.id.to_string(),
+            clarifies: reading.clarifies.unwrap(),
+            shuffled_times: reading.shuffled_times,
+            cards: reading.cards,
+        }
+    }
+}
+
+impl CreateClarifierRequest {
+    /// Draws the clarifiers for `clarified` from the rest of its deck: `drawn` holds the
+    /// reading's cards and jumpers and any clarifiers already drawn for it. Nothing jumps out
+    /// of the deck this time.
+    pub fn draw(
+        &self,
+        clarified: &Reading,
+        drawn: &[Card],
+        user: &User,
+        (seed, entropy): (DeckSeed, Entropy),
+    ) -> Result<Reading, AppError> {
+        if clarified.clarifies.is_some() {
+            return Err(AppError::ValidateError(
+                t!("errors.clarifier_of_clarifier").to_string(),
+            ));
+        }
+        if self.position >= clarified.cards.len() {
+            return Err(AppError::ValidateError(
+                t!("errors.invalid_clarified_position").to_string(),
+            ));
+        }
+        if self.cards == 0 {
+            return Err(AppError::ValidateError(
+                t!("errors.no_clarifier_cards").to_string(),
+            ));
+        }
+        let options = DeckOptions {
+            jumper_probability: 0.0,
+            ..clarified.deck_options.clone()
+        };
+        let mut deck = Deck::build_with(clarified.tradition, options).without(drawn);
+        deck.validate_draw(self.cards as usize)?;
+        let mut rng = seed.rng();
+        let shuffled_times = deck.shuffle_with(&clarified.question, &mut rng);
+        Ok(Reading {
+            id: uuid::Uuid::new_v4(),
+            created_at: chrono::Utc::now(),
+            shuffled_times,
+            cards: deck.draw_with(self.cards as usize, &mut rng),
+            jumpers: Vec::new(),
+            user_id: Some(user.id()),
+            spread: None,
+            seed: None,
+            fair_draw: None,
+            entropy: Some(entropy),
+            shuffle: deck.shuffle,
+            clarifies: Some(Clarifies {
+                reading_id: clarified.id,
+                position: self.position,
+            }),
+            // Not interpreted yet; what the clarified reading's interpretation took is its own
+            attempts: None,
+            usage: None,
+            cost: None,
+            structured: None,
+            ..clarified.clone()
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use crate::entity::reading::{CreateReadingRequest, Reading};
+use crate::entity::user::User;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use webtarot_shared::entropy::Entropy;
+use webtarot_shared::model::{Card, Deck, DeckSeed, Spread};
+
+/// A shuffled deck waiting for the querent to cut it and pick the cards by hand. The deck
+/// order stays on the server until the reading is finalized.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub request: CreateReadingRequest,
+    pub spread: Option<Spread>,
+    pub shuffled_times: usize,
+    pub cards: Vec<Card>,
+    #[serde(default)]
+    pub jumpers: Vec<Card>,
+    pub entropy: Entropy,
+}
+
+impl DrawSession {
+    /// Shuffles the deck described by `request` with a generator seeded from `seed`.
+    pub fn new(
+        request: CreateReadingRequest,
+        user_id: Uuid,
+        spread: Option<Spread>,
+        seed: &DeckSeed,
+        entropy: Entropy,
+    ) -> Self {
+        let mut deck = request.deck();
+        let shuffled_times = deck.shuffle_with(&request.question, &mut seed.rng());
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            request,
+            spread,
+            shuffled_times,
+            cards: deck.cards,
+            jumpers: deck.jumpers,
+            entropy,
+        }
+    }
+
+    pub fn card_count(&self) -> usize {
+        self.request.card_count(self.spread.as_ref())
+    }
+
+    /// Draws the picked cards from the stored deck order. The reading has no seed: its cards
+    /// depend on the querent's choices and cannot be replayed.
+    pub fn finalize(&self, pick: &PickCardsRequest, user: &User) -> Result<Reading, AppError> {
+        let deck = Deck {
+            cards: self.cards.clone(),
+            ..self.request.deck()
+        };
+        let cards = deck.pick(self.card_count(), &pick.cuts, pick.pile, &pick.picks)?;
+        Ok(Reading {
+            jumpers: self.jumpers.clone(),
+            entropy: Some(self.entropy.clone()),
+            ..Reading::new(
+                &self.request,
+                user,
+                self.spread.clone(),
+                self.shuffled_times,
+                cards,
+            )
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDrawSessionResponse {
+    pub session_id: Uuid,
+    pub deck_size: usize,
+    /// Number of cards to pick.
+    pub card_count: usize,
+    pub shuffled_times: usize,
+    /// Cards that jumped out while shuffling; they are not in the deck to pick from.
+    pub jumpers: Vec<Card>,
+    pub spread: Option<Spread>,
+    /// Seconds the session can be finalized for before it expires.
+    pub expires_in: u64,
+}
+
+/// How the querent drew from the shuffled deck.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PickCardsRequest {
+    /// Positions where each new pile starts, e.g. `[26, 52]` cuts 78 cards into three piles.
+    /// Empty keeps the deck whole.
+    #[serde(default)]
+    pub cuts: Vec<usize>,
+    /// Index of the chosen pile.
+    #[serde(default)]
+    pub pile: usize,
+    /// Positions of the picked cards within the chosen pile, in reading order.
+    pub picks: Vec<usize>,
+}
@@ -1,4 +1,6 @@
+use crate::entity::clarifier::Clarifies;
 use crate::entity::reading::Reading;
+use crate::entity::reading_message::ReadingMessage;
 use crate::entity::user::User;
 use chrono::NaiveDateTime;
 use rust_i18n::t;
@@ -6,11 +8,18 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use webtarot_shared::explain::ExplainError;
 use webtarot_shared::explain::InterpretationBackend;
-use webtarot_shared::model::Card;
+use webtarot_shared::model::{Card, DeckOptions, DeckTradition, ShuffleMethod};
+use webtarot_shared::provider::Usage;
+use webtarot_shared::structured::StructuredInterpretation;
+use webtarot_shared::style::{ReadingLength, ReadingStyle};
+use webtarot_shared::template::TemplateRef;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Interpretation {
     Pending(Reading),
+    /// A piece of the text of a pending interpretation, as the model writes it. Only ever
+    /// broadcast; the reading stays pending in the database until it is done.
+    Streaming(Reading, String),
     Done(Reading, String, NaiveDateTime),
     Failed(Reading, String),
 }
@@ -22,6 +31,7 @@ impl Interpretation {
     pub fn reading(&self) -> &Reading {
         match self {
             Self::Pending(reading) => reading,
+            Self::Streaming(reading, _) => reading,
             Self::Done(reading, _, _) => reading,
             Self::Failed(reading, _) => reading,
         }
@@ -30,6 +40,7 @@ impl Interpretation {
     pub fn into_reading(self) -> Reading {
         match self {
             Self::Pending(reading) => reading,
+            Self::Streaming(reading, _) => reading,
             Self::Done(reading, _, _) => reading,
             Self::Failed(reading, _) => reading,
         }
@@ -38,6 +49,7 @@ impl Interpretation {
     pub fn reading_mut(&mut self) -> &mut Reading {
         match self {
             Self::Pending(reading) => reading,
+            Self::Streaming(reading, _) => reading,
             Self::Done(reading, _, _) => reading,
             Self::Failed(reading, _) => reading,
         }
@@ -55,7 +67,7 @@ impl From<Interpretation> for crate::model::Reading {
             interpretation_error,
             other_interpretation_done_at,
         ) = match value {
-            Interpretation::Pending(r) => (
+            Interpretation::Pending(r) | Interpretation::Streaming(r, _) => (
                 r,
                 InterpretationStatus::Pending,
                 String::new(),
@@ -85,6 +97,32 @@ impl From<Interpretation> for crate::model::Reading {
             interpretation_error,
             deleted_at: None,
             interpretation_done_at: other_interpretation_done_at,
+            spread: reading.spread.map(Into::into),
+            seed: reading.seed.map(|seed| seed.to_string()),
+            fair_draw: reading.fair_draw.map(Into::into),
+            tradition: reading.tradition.id().to_string(),
+            deck_options: Some(reading.deck_options.into()),
+            clarified_reading_id: reading.clarifies.map(|clarifies| clarifies.reading_id),
+            clarified_position: reading.clarifies.map(|clarifies| clarifies.position as i32),
+            jumpers: Some(reading.jumpers.into()),
+            entropy: reading.entropy.map(Into::into),
+            shuffle: reading.shuffle.id().to_string(),
+            backend: reading.backend.map(|backend| backend.id().to_string()),
+            model: reading.model,
+            interpretation_attempts: reading.attempts.map(|attempts| attempts as i32),
+            prompt_tokens: reading.usage.map(|usage| usage.prompt_tokens as i32),
+            completion_tokens: reading.usage.map(|usage| usage.completion_tokens as i32),
+            cost_usd: reading.cost,
+            prompt_template: reading
+                .prompt_template
+                .as_ref()
+                .map(|template| template.name.clone()),
+            prompt_template_version: reading
+                .prompt_template
+                .map(|template| template.version as i32),
+            interpretation_structured: reading.structured.map(Into::into),
+            style: reading.style.id().to_string(),
+            length: reading.length.id().to_string(),
         }
     }
 }
@@ -102,6 +140,7 @@ impl From<crate::model::Reading> for Interpretation {
             question: value.question,
             shuffled_times: value.shuffled_times as usize,
             cards: value.cards.into(),
+            jumpers: value.jumpers.map(Into::into).unwrap_or_default(),
             user_id: if value.user_id.is_nil() {
                 None
             } else {
@@ -110,7 +149,51 @@ impl From<crate::model::Reading> for Interpretation {
             user_name: value.user_name,
             user_self_description: value.user_self_description,
             context: value.context,
-            backend: Some(InterpretationBackend::ChatGPT),
+            // Readings made before the backend was recorded were all interpreted by ChatGPT
+            backend: Some(
+                value
+                    .backend
+                    .and_then(|backend| backend.parse().ok())
+                    .unwrap_or(InterpretationBackend::ChatGPT),
+            ),
+            model: value.model,
+            attempts: value
+                .interpretation_attempts
+                .map(|attempts| attempts as u32),
+            usage: value.prompt_tokens.zip(value.completion_tokens).map(
+                |(prompt_tokens, completion_tokens)| Usage {
+                    prompt_tokens: prompt_tokens as u32,
+                    completion_tokens: completion_tokens as u32,
+                },
+            ),
+            cost: value.cost_usd,
+            prompt_template: value
+                .prompt_template
+                .zip(value.prompt_template_version)
+                .map(|(name, version)| TemplateRef {
+                    name,
+                    version: version as u32,
+                }),
+            structured: value.interpretation_structured.map(Into::into),
+            style: value.style.parse().unwrap_or_default(),
+            length: value.length.parse().unwrap_or_default(),
+            spread: value.spread.map(Into::into),
+            seed: value.seed.and_then(|seed| seed.parse().ok()),
+            fair_draw: value.fair_draw.map(Into::into),
+            tradition: value.tradition.parse().unwrap_or_default(),
+            deck_options: value.deck_options.map(Into::into).unwrap_or_default(),
+            entropy: value.entropy.map(Into::into),
+            shuffle: value
+                .shuffle
+                .parse()
+                .unwrap_or_else(|_| ShuffleMethod::legacy()),
+            clarifies: value
+                .clarified_reading_id
+                .zip(value.clarified_position)
+                .map(|(reading_id, position)| Clarifies {
+                    reading_id,
+                    position: position as usize,
+                }),
         };
 
         match value.interpretation_status {
@@ -132,8 +215,24 @@ impl From<crate::model::Reading> for Interpretation {
 pub struct CreateInterpretationRequest {
     pub question: String,
     pub cards: Vec<Card>,
+    /// Cards that jumped out of a physical deck while shuffling.
+    #[serde(default)]
+    pub jumpers: Vec<Card>,
     pub context: String,
     pub backend: InterpretationBackend,
+    #[serde(default)]
+    pub tradition: DeckTradition,
+    #[serde(default)]
+    pub deck_options: DeckOptions,
+    /// Model to interpret with, from the backend's allow-list; its default model when absent.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// How the reading is interpreted; the user's default style when absent.
+    #[serde(default)]
+    pub style: Option<ReadingStyle>,
+    /// How long the interpretation is; the user's default length when absent.
+    #[serde(default)]
+    pub length: Option<ReadingLength>,
 }
 
 impl From<(CreateInterpretationRequest, &User)> for Reading {
@@ -144,11 +243,28 @@ impl From<(CreateInterpretationRequest, &User)> for Reading {
             question: value.question,
             shuffled_times: 0,
             cards: value.cards,
+            jumpers: value.jumpers,
             user_id: Some(user.id()),
             user_name: user.name().unwrap_or_default().to_string(),
             user_self_description: user.self_description().unwrap_or_default().to_string(),
             context: value.context.clone(),
             backend: Some(value.backend),
+            model: value.model,
+            attempts: None,
+            usage: None,
+            cost: None,
+            prompt_template: None,
+            structured: None,
+            style: value.style.unwrap_or_else(|| user.reading_style()),
+            length: value.length.unwrap_or_else(|| user.reading_length()),
+            spread: None,
+            seed: None,
+            fair_draw: None,
+            tradition: value.tradition,
+            deck_options: value.deck_options,
+            clarifies: None,
+            entropy: None,
+            shuffle: ShuffleMethod::default(),
         }
     }
 }
@@ -175,24 +291,35 @@ pub struct GetInterpretationResult {
     pub interpretation: String,
     pub reading: Option<Reading>,
     pub interpretation_done_at: Option<NaiveDateTime>,
+    /// Sections of the interpretation, for clients that lay each card's analysis next to the
+    /// card; absent when it was written as free text.
+    #[serde(default)]
+    pub structured: Option<StructuredInterpretation>,
+    /// Follow-up questions on the interpretation and their answers, oldest first.
+    #[serde(default)]
+    pub messages: Vec<ReadingMessage>,
 }
 
 impl From<Interpretation> for GetInterpretationResult {
     fn from(value: Interpretation) -> Self {
         match value {
-            Interpretation::Pending(reading) => Self {
+            Interpretation::Pending(reading) | Interpretation::Streaming(reading, _) => Self {
                 done: false,
                 error: "".to_string(),
                 interpretation: reading.question.clone(),
                 reading: Some(reading),
                 interpretation_done_at: None,
+                structured: None,
+                messages: Vec::new(),
             },
-            Interpretation::Done(reading, result, ts) => Self {
+            Interpretation::Done(mut reading, result, ts) => Self {
                 done: true,
                 error: Default::default(),
                 interpretation: result,
+                structured: reading.structured.take(),
                 reading: Some(reading),
                 interpretation_done_at: Some(ts),
+                messages: Vec::new(),
             },
 
             Interpretation::Failed(reading, err) => Self {
@@ -201,6 +328,8 @@ impl From<Interpretation> for GetInterpretationResult {
                 interpretation: Default::default(),
                 reading: Some(reading),
                 interpretation_done_at: None,
+                structured: None,
+                messages: Vec::new(),
             },
         }
     }
@@ -217,6 +346,8 @@ impl From<Option<Interpretation>> for GetInterpretationResult {
             interpretation: "".to_string(),
             reading: None,
             interpretation_done_at: None,
+            structured: None,
+            messages: Vec::new(),
         }
     }
 }
@@ -230,7 +361,7 @@ pub fn localize_explain_error(e: &ExplainError) -> String {
         ExplainError::Request(err) => {
             t!("errors.request_failed", error = err.to_string()).to_string()
         }
-        ExplainError::ApiError { status, body } => t!(
+        ExplainError::ApiError { status, body, .. } => t!(
             "errors.api_error",
             status = status.as_u16().to_string(),
             body = body
@@ -239,6 +370,24 @@ pub fn localize_explain_error(e: &ExplainError) -> String {
         ExplainError::ParseResponse(err) => {
             t!("errors.parse_response", error = err.to_string()).to_string()
         }
+        ExplainError::InvalidEvent(err) => {
+            t!("errors.invalid_event", error = err.to_string()).to_string()
+        }
         ExplainError::EmptyResponse => t!("errors.empty_response").to_string(),
+        ExplainError::ProviderUnavailable(backend) => {
+            t!("errors.provider_unavailable", provider = backend.id()).to_string()
+        }
+        ExplainError::ModelNotAllowed { backend, model } => t!(
+            "errors.model_not_allowed",
+            model = model,
+            provider = backend.id()
+        )
+        .to_string(),
+        ExplainError::CircuitOpen(backend) => {
+            t!("errors.circuit_open", provider = backend.id()).to_string()
+        }
+        ExplainError::UnknownTemplate(template) => {
+            t!("errors.unknown_template", template = template).to_string()
+        }
     }
 }
@@ -1,16 +1,97 @@
+use crate::entity::clarifier::Clarifies;
 use crate::entity::user::User;
+use crate::error::AppError;
+use rust_i18n::t;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
-use webtarot_shared::explain::InterpretationBackend;
-use webtarot_shared::model::{Card, Deck};
+use webtarot_shared::entropy::{Entropy, EntropyChoice};
+use webtarot_shared::explain::{ExplainRequest, InterpretationBackend};
+use webtarot_shared::fair::FairDraw;
+use webtarot_shared::model::{
+    Card, Deck, DeckOptions, DeckSeed, DeckTradition, ShuffleMethod, Spread,
+};
+use webtarot_shared::provider::Usage;
+use webtarot_shared::structured::StructuredInterpretation;
+use webtarot_shared::style::{ReadingLength, ReadingStyle};
+use webtarot_shared::template::TemplateRef;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateReadingRequest {
     pub question: String,
     pub cards: u8,
     pub context: String,
     pub backend: InterpretationBackend,
+    #[serde(default)]
+    pub tradition: DeckTradition,
+    /// Card subset and reversal probability; the full deck with even reversals by default.
+    #[serde(default)]
+    pub deck_options: DeckOptions,
+    /// Built-in layout id (e.g. `celticCross`) or custom spread id. When present it overrides
+    /// `cards`.
+    #[serde(default)]
+    pub spread: Option<String>,
+    /// Commitment obtained from `POST /api/v1/reading/commitment`, for a verifiable draw.
+    #[serde(default)]
+    pub commitment: Option<String>,
+    /// Client contribution to a verifiable draw; only used together with `commitment`.
+    #[serde(default)]
+    pub client_seed: Option<String>,
+    /// Where the shuffle's randomness comes from; the OS generator by default. A verifiable
+    /// draw takes it from the commitment instead.
+    #[serde(default)]
+    pub entropy: EntropyChoice,
+    /// Model to interpret with, from the backend's allow-list; its default model when absent.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// How the reading is interpreted; the user's default style when absent.
+    #[serde(default)]
+    pub style: Option<ReadingStyle>,
+    /// How long the interpretation is; the user's default length when absent.
+    #[serde(default)]
+    pub length: Option<ReadingLength>,
+}
+
+impl CreateReadingRequest {
+    /// Number of cards to draw: the spread's positions, or `cards` without a spread.
+    pub fn card_count(&self, spread: Option<&Spread>) -> usize {
+        spread
+            .map(Spread::card_count)
+            .unwrap_or(self.cards as usize)
+    }
+
+    pub fn deck(&self) -> Deck {
+        Deck::build_with(self.tradition, self.deck_options.clone())
+    }
+
+    pub fn validate(&self, spread: Option<&Spread>) -> Result<(), AppError> {
+        if self.commitment.is_some() && self.entropy != EntropyChoice::Os {
+            return Err(AppError::ValidateError(
+                t!("errors.commitment_entropy").to_string(),
+            ));
+        }
+        Ok(self.deck().validate_draw(self.card_count(spread))?)
+    }
+
+    /// Obtains the seed to shuffle with: derived from the verifiable draw when there is one,
+    /// from the chosen entropy source otherwise.
+    pub async fn seed(
+        &self,
+        fair_draw: Option<&FairDraw>,
+    ) -> Result<(DeckSeed, Entropy), AppError> {
+        match fair_draw {
+            Some(fair_draw) => Ok((fair_draw.deck_seed(&self.question), Entropy::Commitment)),
+            None => Ok(self.entropy.seed().await?),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCommitmentResponse {
+    pub commitment: String,
+    /// Seconds the commitment can be used for before it expires.
+    pub expires_in: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -18,7 +99,15 @@ pub struct CreateReadingRequest {
 pub struct CreateReadingResponse {
     pub shuffled_times: usize,
     pub cards: Vec<Card>,
+    /// Cards that jumped out of the deck while shuffling; empty unless enabled in the deck
+    /// options.
+    pub jumpers: Vec<Card>,
+    pub tradition: DeckTradition,
     pub interpretation_id: String,
+    pub spread: Option<Spread>,
+    pub seed: Option<DeckSeed>,
+    pub fair_draw: Option<FairDraw>,
+    pub entropy: Option<Entropy>,
 }
 
 impl From<Reading> for CreateReadingResponse {
@@ -26,7 +115,13 @@ impl From<Reading> for CreateReadingResponse {
         Self {
             shuffled_times: reading.shuffled_times,
             cards: reading.cards,
+            jumpers: reading.jumpers,
+            tradition: reading.tradition,
             interpretation_id: reading.id.to_string(),
+            spread: reading.spread,
+            seed: reading.seed,
+            fair_draw: reading.fair_draw,
+            entropy: reading.entropy,
         }
     }
 }
@@ -39,29 +134,184 @@ pub struct Reading {
     pub question: String,
     pub shuffled_times: usize,
     pub cards: Vec<Card>,
+    /// Cards that jumped out of the deck while shuffling, read apart from the spread.
+    #[serde(default)]
+    pub jumpers: Vec<Card>,
     pub user_id: Option<uuid::Uuid>,
     pub user_name: String,
     pub user_self_description: String,
     pub context: String,
     #[serde(default)]
     pub backend: Option<InterpretationBackend>,
+    /// Model the reading is interpreted with; absent for readings made before it was recorded.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Calls made to providers for the interpretation, retries and failovers included; absent
+    /// until it is interpreted.
+    #[serde(default)]
+    pub attempts: Option<u32>,
+    /// Tokens the interpretation took; absent until it is interpreted, or when the provider
+    /// does not report them.
+    #[serde(default)]
+    pub usage: Option<Usage>,
+    /// Estimated cost of the interpretation in US dollars; absent when its model has no price.
+    #[serde(default)]
+    pub cost: Option<f64>,
+    /// Prompt template the reading is interpreted with; absent for readings made before it was
+    /// recorded.
+    #[serde(default)]
+    pub prompt_template: Option<TemplateRef>,
+    /// Sections of the interpretation, when it was written as a structured one; returned
+    /// beside the interpretation text rather than with the reading.
+    #[serde(skip)]
+    pub structured: Option<StructuredInterpretation>,
+    #[serde(default)]
+    pub style: ReadingStyle,
+    #[serde(default)]
+    pub length: ReadingLength,
+    #[serde(default)]
+    pub tradition: DeckTradition,
+    #[serde(default)]
+    pub deck_options: DeckOptions,
+    #[serde(default)]
+    pub spread: Option<Spread>,
+    /// Seed the deck was shuffled and drawn with; absent for readings made before seeding
+    /// or with cards supplied by the client.
+    #[serde(default)]
+    pub seed: Option<DeckSeed>,
+    /// Revealed commitment, server seed and client seed of a verifiable draw.
+    #[serde(default)]
+    pub fair_draw: Option<FairDraw>,
+    /// Set on clarifier readings, drawn to clarify one position of an earlier reading.
+    #[serde(default)]
+    pub clarifies: Option<Clarifies>,
+    /// Where the seed came from; absent for readings made before sources were recorded or
+    /// with cards supplied by the client.
+    #[serde(default)]
+    pub entropy: Option<Entropy>,
+    /// How the deck was shuffled, which a seeded replay must repeat.
+    #[serde(default = "ShuffleMethod::legacy")]
+    pub shuffle: ShuffleMethod,
+}
+
+impl Reading {
+    /// A new reading of `cards` drawn for `request`; the caller records how they were drawn.
+    pub fn new(
+        request: &CreateReadingRequest,
+        user: &User,
+        spread: Option<Spread>,
+        shuffled_times: usize,
+        cards: Vec<Card>,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            created_at: chrono::Utc::now(),
+            question: request.question.clone(),
+            shuffled_times,
+            cards,
+            jumpers: Vec::new(),
+            user_id: Some(user.id()),
+            user_name: user.name().unwrap_or_default().to_string(),
+            user_self_description: user.self_description().unwrap_or_default().to_string(),
+            context: request.context.clone(),
+            backend: Some(request.backend.clone()),
+            model: request.model.clone(),
+            attempts: None,
+            usage: None,
+            cost: None,
+            prompt_template: None,
+            structured: None,
+            style: request.style.unwrap_or_else(|| user.reading_style()),
+            length: request.length.unwrap_or_else(|| user.reading_length()),
+            tradition: request.tradition,
+            deck_options: request.deck_options.clone(),
+            spread,
+            seed: None,
+            fair_draw: None,
+            clarifies: None,
+            entropy: None,
+            shuffle: ShuffleMethod::default(),
+        }
+    }
+}
+
+/// A seeded reading regenerated from its stored seed, question and card count.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReadingResponse {
+    pub seed: DeckSeed,
+    pub shuffled_times: usize,
+    /// Full deck order after shuffling, before the draw, without the jumpers.
+    pub deck: Vec<Card>,
+    pub cards: Vec<Card>,
+    pub jumpers: Vec<Card>,
+    /// Whether the regenerated draw is identical to the stored one.
+    pub matches: bool,
+}
+
+impl ReplayReadingResponse {
+    /// Returns `None` when the reading was not drawn from a seed.
+    pub fn replay(reading: &Reading) -> Option<Self> {
+        let seed = reading.seed?;
+        let replayed = Deck::build_with(reading.tradition, reading.deck_options.clone())
+            .with_shuffle(reading.shuffle)
+            .seeded_reading(&reading.question, &seed, reading.cards.len());
+        Some(Self {
+            seed,
+            matches: replayed.shuffled_times == reading.shuffled_times
+                && replayed.cards == reading.cards
+                && replayed.deck.jumpers == reading.jumpers,
+            shuffled_times: replayed.shuffled_times,
+            deck: replayed.deck.cards,
+            cards: replayed.cards,
+            jumpers: replayed.deck.jumpers,
+        })
+    }
+}
+
+impl From<&Reading> for ExplainRequest {
+    fn from(reading: &Reading) -> Self {
+        let non_blank = |s: &String| Some(s.clone()).filter(|i| !i.trim().is_empty());
+        Self {
+            question: reading.question.clone(),
+            context: non_blank(&reading.context),
+            cards: reading.cards.clone(),
+            jumpers: reading.jumpers.clone(),
+            tradition: reading.tradition,
+            deck_options: reading.deck_options.clone(),
+            spread: reading.spread.clone(),
+            user_name: non_blank(&reading.user_name),
+            user_self_description: non_blank(&reading.user_self_description),
+            clarification: None,
+            model: reading.model.clone(),
+            template: reading.prompt_template.as_ref().map(ToString::to_string),
+            asked_at: Some(reading.created_at),
+            conversation: Vec::new(),
+            structured: false,
+            style: reading.style,
+            length: reading.length,
+        }
+    }
 }
 
 #[instrument]
-pub fn perform_reading(request: &CreateReadingRequest, user: &User) -> Reading {
-    let mut deck = Deck::build();
-    let shuffles = deck.shuffle(&request.question);
-    let cards = deck.draw(request.cards as usize);
+pub fn perform_reading(
+    request: &CreateReadingRequest,
+    user: &User,
+    spread: Option<Spread>,
+    fair_draw: Option<FairDraw>,
+    seed: DeckSeed,
+    entropy: Entropy,
+) -> Reading {
+    let count = request.card_count(spread.as_ref());
+    let drawn = request
+        .deck()
+        .seeded_reading(&request.question, &seed, count);
     Reading {
-        id: uuid::Uuid::new_v4(),
-        created_at: chrono::Utc::now(),
-        question: request.question.clone(),
-        shuffled_times: shuffles,
-        cards,
-        user_id: Some(user.id()),
-        user_name: user.name().unwrap_or_default().to_string(),
-        user_self_description: user.self_description().unwrap_or_default().to_string(),
-        context: request.context.clone(),
-        backend: Some(request.backend.clone()),
+        seed: Some(seed),
+        fair_draw,
+        entropy: Some(entropy),
+        jumpers: drawn.deck.jumpers,
+        ..Reading::new(request, user, spread, drawn.shuffled_times, drawn.cards)
     }
 }
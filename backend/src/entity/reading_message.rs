@@ -0,0 +1,118 @@
+use crate::error::AppError;
+use chrono::NaiveDateTime;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use webtarot_shared::provider::{Role, Turn};
+
+/// Most characters a follow-up question may have.
+pub const MAX_MESSAGE_LENGTH: usize = 2_000;
+
+/// How long a question waits for its answer before another may be asked in its place, should
+/// the answer never come (e.g. after a restart).
+const ANSWER_TIMEOUT_MINUTES: i64 = 5;
+
+/// A follow-up question on an interpreted reading, or the answer to one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingMessage {
+    pub id: Uuid,
+    pub reading_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub role: Role,
+    pub content: String,
+    /// Why the answer could not be written; the question can be asked again.
+    pub error: Option<String>,
+}
+
+impl ReadingMessage {
+    pub fn new(reading_id: Uuid, role: Role, content: String, error: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            reading_id,
+            created_at: chrono::Utc::now().naive_utc(),
+            role,
+            content,
+            error,
+        }
+    }
+}
+
+/// Whether the last question of a thread is still being answered.
+pub fn answer_pending(messages: &[ReadingMessage]) -> bool {
+    messages.last().is_some_and(|last| {
+        last.role == Role::User
+            && chrono::Utc::now().naive_utc() - last.created_at
+                < chrono::TimeDelta::minutes(ANSWER_TIMEOUT_MINUTES)
+    })
+}
+
+impl From<crate::model::ReadingMessage> for ReadingMessage {
+    fn from(value: crate::model::ReadingMessage) -> Self {
+        Self {
+            id: value.id,
+            reading_id: value.reading_id,
+            created_at: value.created_at,
+            role: match value.role.as_str() {
+                "assistant" => Role::Assistant,
+                _ => Role::User,
+            },
+            content: value.content,
+            error: value.error,
+        }
+    }
+}
+
+impl From<ReadingMessage> for crate::model::ReadingMessage {
+    fn from(value: ReadingMessage) -> Self {
+        Self {
+            id: value.id,
+            reading_id: value.reading_id,
+            created_at: value.created_at,
+            role: value.role.id().to_string(),
+            content: value.content,
+            error: value.error,
+        }
+    }
+}
+
+impl From<&ReadingMessage> for Turn {
+    fn from(value: &ReadingMessage) -> Self {
+        Self {
+            role: value.role,
+            text: value.content.clone(),
+        }
+    }
+}
+
+/// A piece of an answer, while the model writes it.
+#[derive(Clone, Debug)]
+pub struct MessageChunk {
+    pub reading_id: Uuid,
+    pub message_id: Uuid,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReadingMessageRequest {
+    pub text: String,
+}
+
+impl CreateReadingMessageRequest {
+    /// The question, trimmed.
+    pub fn validate(&self) -> Result<String, AppError> {
+        let text = self.text.trim();
+        if text.is_empty() {
+            return Err(AppError::ValidateError(
+                t!("errors.empty_message").to_string(),
+            ));
+        }
+        if text.chars().count() > MAX_MESSAGE_LENGTH {
+            return Err(AppError::ValidateError(
+                t!("errors.message_too_long", max = MAX_MESSAGE_LENGTH).to_string(),
+            ));
+        }
+        Ok(text.to_string())
+    }
+}
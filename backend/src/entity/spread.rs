@@ -0,0 +1,82 @@
+use crate::error::AppError;
+use chrono::NaiveDateTime;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use webtarot_shared::model::{MAX_DRAWS, Spread, SpreadPosition};
+
+/// A spread layout designed by a user. Its id can be used as `spread` when creating a reading.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomSpread {
+    pub id: Uuid,
+    /// Never sent to clients: shared spreads are returned to anyone who has the link.
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub name: String,
+    pub positions: Vec<SpreadPosition>,
+    /// Shared spreads can be viewed and used by anyone who has the link.
+    pub shared: bool,
+}
+
+impl CustomSpread {
+    pub fn is_visible_to(&self, user_id: Uuid) -> bool {
+        self.shared || self.user_id == user_id
+    }
+}
+
+impl From<crate::model::CustomSpread> for CustomSpread {
+    fn from(value: crate::model::CustomSpread) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            name: value.name,
+            positions: value.positions.into(),
+            shared: value.shared,
+        }
+    }
+}
+
+impl From<CustomSpread> for Spread {
+    fn from(value: CustomSpread) -> Self {
+        Self {
+            id: value.id.to_string(),
+            name: value.name,
+            positions: value.positions,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveSpreadRequest {
+    pub name: String,
+    pub positions: Vec<SpreadPosition>,
+    #[serde(default)]
+    pub shared: bool,
+}
+
+impl SaveSpreadRequest {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::ValidateError(
+                t!("errors.spread_name_required").to_string(),
+            ));
+        }
+        if self.positions.is_empty() || self.positions.len() > MAX_DRAWS {
+            return Err(AppError::ValidateError(
+                t!("errors.spread_position_count", max = MAX_DRAWS).to_string(),
+            ));
+        }
+        if self.positions.iter().any(|p| p.name.trim().is_empty()) {
+            return Err(AppError::ValidateError(
+                t!("errors.spread_position_name_required").to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::entity::reading::Reading;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use webtarot_shared::model::{Arcana, Deck};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +19,8 @@ pub struct ArcanaStats {
     pub drawn_flipped_count: u64,
     pub drawn_count: u64,
     pub total_count: u64,
+    /// Draws from decks that could reverse the card; the base for `percent_flipped`.
+    pub reversible_count: u64,
     pub percent_flipped: f64,
     pub percent_drawn: f64,
     pub percent_total: f64,
@@ -31,6 +33,7 @@ impl ArcanaStats {
             drawn_flipped_count: 0,
             drawn_count: 0,
             total_count: 0,
+            reversible_count: 0,
             percent_flipped: 0.0,
             percent_drawn: 0.0,
             percent_total: 0.0,
@@ -46,12 +49,25 @@ pub fn calculate_stats(readings: &Vec<Reading>) -> Stats {
         never_drawn: Vec::new(),
     };
     let mut arcana_stats = HashMap::<Arcana, ArcanaStats>::new();
+    // Cards that could have come up, given each reading's tradition and deck options
+    let mut possible = Vec::<Arcana>::new();
+    let mut seen = HashSet::<Arcana>::new();
     for reading in readings {
+        let deck = Deck::build_with(reading.tradition, reading.deck_options.clone());
+        for card in &deck.cards {
+            if seen.insert(card.arcana) {
+                possible.push(card.arcana);
+            }
+        }
+        let reversible = reading.deck_options.allows_reversals(reading.tradition);
         for card in &reading.cards {
             let arcana_stats = arcana_stats
                 .entry(card.arcana)
                 .or_insert_with(|| ArcanaStats::new(card.arcana));
             arcana_stats.total_count += 1;
+            if reversible {
+                arcana_stats.reversible_count += 1;
+            }
             if card.flipped {
                 arcana_stats.drawn_flipped_count += 1;
             } else {
@@ -59,16 +75,21 @@ pub fn calculate_stats(readings: &Vec<Reading>) -> Stats {
             }
         }
     }
-    stats.never_drawn = Deck::build()
-        .cards
-        .iter()
-        .filter(|c| !arcana_stats.contains_key(&c.arcana))
-        .map(|c| c.arcana)
+    if readings.is_empty() {
+        possible = Deck::build().cards.iter().map(|c| c.arcana).collect();
+    }
+    stats.never_drawn = possible
+        .into_iter()
+        .filter(|arcana| !arcana_stats.contains_key(arcana))
         .collect();
     stats.arcana_stats = arcana_stats.into_values().collect();
 
     for stat in &mut stats.arcana_stats {
-        stat.percent_flipped = stat.drawn_flipped_count as f64 / stat.total_count as f64;
+        stat.percent_flipped = if stat.reversible_count > 0 {
+            stat.drawn_flipped_count as f64 / stat.reversible_count as f64
+        } else {
+            0.0
+        };
         stat.percent_drawn = stat.drawn_count as f64 / stat.total_count as f64;
         stat.percent_total = stat.total_count as f64 / stats.total_cards_drawn as f64;
     }
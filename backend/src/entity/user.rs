@@ -1,5 +1,6 @@
 use crate::error::AppError;
 use serde::{Deserialize, Serialize};
+use webtarot_shared::style::{ReadingLength, ReadingStyle};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +16,12 @@ pub enum User {
         email: String,
         name: String,
         self_description: String,
+        /// Style of the user's readings when they do not ask for one.
+        #[serde(default)]
+        reading_style: ReadingStyle,
+        /// Length of the user's readings when they do not ask for one.
+        #[serde(default)]
+        reading_length: ReadingLength,
         access_token: AccessToken,
     },
 }
@@ -41,6 +48,20 @@ impl User {
             } => Some(self_description),
         }
     }
+
+    pub fn reading_style(&self) -> ReadingStyle {
+        match self {
+            Self::Anonymous { .. } => ReadingStyle::default(),
+            Self::Authenticated { reading_style, .. } => *reading_style,
+        }
+    }
+
+    pub fn reading_length(&self) -> ReadingLength {
+        match self {
+            Self::Anonymous { .. } => ReadingLength::default(),
+            Self::Authenticated { reading_length, .. } => *reading_length,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -62,6 +83,8 @@ impl From<(crate::model::User, crate::model::AccessToken)> for User {
             email: user.email,
             name: user.name,
             self_description: user.self_description,
+            reading_style: user.reading_style.parse().unwrap_or_default(),
+            reading_length: user.reading_length.parse().unwrap_or_default(),
             access_token: AccessToken {
                 id: access_token.id,
                 created_at: access_token.created_at,
@@ -110,6 +133,12 @@ pub struct UpdateUserRequest {
     pub name: String,
     pub self_description: String,
     pub email: String,
+    /// New default style of the user's readings; kept when absent.
+    #[serde(default)]
+    pub reading_style: Option<ReadingStyle>,
+    /// New default length of the user's readings; kept when absent.
+    #[serde(default)]
+    pub reading_length: Option<ReadingLength>,
 }
 
 impl UpdateUserRequest {
@@ -1,8 +1,11 @@
 use axum::Json;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use rust_i18n::t;
 use serde::{Deserialize, Serialize};
 use tracing::error;
+use webtarot_shared::entropy::EntropyError;
+use webtarot_shared::model::DrawError;
 
 // Diesel is used throughout repositories; bring types when available.
 // Keep these imports optional at compile time where this module is used.
@@ -76,8 +79,48 @@ impl From<RunError> for AppError {
     }
 }
 
+impl From<redis::RedisError> for AppError {
+    fn from(value: redis::RedisError) -> Self {
+        AppError::internal_with_log("Failed to execute Redis command", value)
+    }
+}
+
+impl From<DrawError> for AppError {
+    fn from(value: DrawError) -> Self {
+        AppError::ValidateError(
+            match value {
+                DrawError::InvalidReversalProbability => t!("errors.invalid_reversal_probability"),
+                DrawError::InvalidJumperProbability => t!("errors.invalid_jumper_probability"),
+                DrawError::NotEnoughCards => t!("errors.deck_too_small"),
+                DrawError::TooManyCards { max } => t!("errors.too_many_cards", max = max),
+                DrawError::InvalidCut => t!("errors.invalid_cut"),
+                DrawError::InvalidPile => t!("errors.invalid_pile"),
+                DrawError::InvalidPick => t!("errors.invalid_pick"),
+                DrawError::WrongPickCount { expected } => {
+                    t!("errors.wrong_pick_count", expected = expected)
+                }
+            }
+            .to_string(),
+        )
+    }
+}
+
 impl From<DieselError> for AppError {
     fn from(value: DieselError) -> Self {
         AppError::from_diesel_with_log("Failed to execute DB query", value)
     }
 }
+
+impl From<EntropyError> for AppError {
+    fn from(value: EntropyError) -> Self {
+        match value {
+            EntropyError::NotEnoughSamples { min } => {
+                AppError::ValidateError(t!("errors.not_enough_entropy", min = min).to_string())
+            }
+            EntropyError::Os(e) => AppError::internal_with_log("Failed to read the OS RNG", e),
+            e => AppError::ValidateError(
+                t!("errors.beacon_unavailable", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+}
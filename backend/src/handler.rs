@@ -1,11 +1,26 @@
+pub mod create_clarifier;
+pub mod create_commitment;
+pub mod create_draw_session;
 pub mod create_interpretation;
 pub mod create_reading;
+pub mod create_reading_message;
+pub mod create_spread;
 pub mod create_user;
 pub mod delete_interpretation;
+pub mod delete_spread;
+pub mod finalize_draw_session;
+pub mod get_fairness;
 pub mod get_interpretation;
 pub mod get_interpretation_history;
+pub mod get_spread;
 pub mod get_stats;
 pub mod get_user;
+pub mod list_backends;
+pub mod list_spreads;
 pub mod log_in;
 pub mod notify_websocket_handler;
+pub mod parse_cards;
+pub mod replay_interpretation;
+pub mod stream_interpretation;
+pub mod update_spread;
 pub mod update_user;
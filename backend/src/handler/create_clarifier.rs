@@ -0,0 +1,189 @@
+use crate::entity::clarifier::{CreateClarifierRequest, CreateClarifierResponse};
+use crate::entity::interpretation::Interpretation;
+use crate::entity::user::User;
+use crate::error::{AppError, ResponseResult};
+use crate::middleware::locale::Locale;
+use crate::repository::interpretation_repository::InterpretationRepository;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use rust_i18n::t;
+use webtarot_shared::entropy::{EntropySource, OsEntropy};
+
+/// Draws clarifier cards for one position of an interpreted reading, from the cards its deck
+/// has left, and requests a follow-up interpretation that builds on the original one.
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn create_clarifier(
+    interpretation_repository: InterpretationRepository,
+    user: User,
+    locale: Locale,
+    Path(interpretation_id): Path<String>,
+    Json(create_clarifier_request): Json<CreateClarifierRequest>,
+) -> (StatusCode, ResponseResult<Json<CreateClarifierResponse>>) {
+    let Ok(uuid) = interpretation_id.parse() else {
+        return AppError::ValidateError("invalid uuid".into()).into_response();
+    };
+    let clarified = match interpretation_repository.get_interpretation(uuid).await {
+        Some(interpretation) if interpretation.reading().user_id == Some(user.id()) => {
+            interpretation
+        }
+        _ => return AppError::NotFound.into_response(),
+    };
+    let Interpretation::Done(clarified, ..) = clarified else {
+        return AppError::ValidateError(t!("errors.reading_not_interpreted").to_string())
+            .into_response();
+    };
+    let mut drawn = [clarified.cards.clone(), clarified.jumpers.clone()].concat();
+    for clarifier in interpretation_repository.get_clarifiers(uuid).await {
+        drawn.extend(clarifier.into_reading().cards);
+    }
+    let seed = match OsEntropy.seed().await {
+        Ok(seed) => seed,
+        Err(e) => return AppError::from(e).into_response(),
+    };
+    let reading = match create_clarifier_request.draw(&clarified, &drawn, &user, seed) {
+        Ok(reading) => reading,
+        Err(e) => return e.into_response(),
+    };
+    interpretation_repository
+        .request_interpretation(reading.clone(), locale, user)
+        .await;
+    (
+        StatusCode::OK,
+        Ok(Json(CreateClarifierResponse::from(reading))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::model;
+    use crate::test_helpers::{insert_reading_row, reading_row};
+    use axum::body::Body;
+    use axum::extract::Request;
+    use diesel::{QueryDsl, SelectableHelper};
+    use diesel_async::RunQueryDsl;
+    use serde_json::json;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use webtarot_shared::model::{Card, Deck};
+
+    fn post(uri: &str, user: Uuid, body: String) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", user.to_string())
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_clarifier_draws_from_the_rest_of_the_deck() {
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let user = Uuid::new_v4();
+
+        // Every card but five is already on the table, so the clarifiers come from those five
+        let deck = Deck::build().cards;
+        let (left, cards) = deck.split_at(5);
+        let id = insert_reading_row(
+            &state,
+            model::Reading {
+                interpretation_text: "original text".to_string(),
+                interpretation_attempts: Some(1),
+                prompt_tokens: Some(1200),
+                completion_tokens: Some(800),
+                cost_usd: Some(0.01),
+                ..reading_row(user, cards.to_vec())
+            },
+        )
+        .await;
+        let uri = format!("/api/v1/interpretation/{}/clarifier", id);
+
+        // Only the owner may draw clarifiers, for a position the reading has
+        let response = app
+            .clone()
+            .oneshot(post(
+                &uri,
+                Uuid::new_v4(),
+                json!({ "position": 0 }).to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app
+            .clone()
+            .oneshot(post(&uri, user, json!({ "position": 73 }).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let mut clarifiers: Vec<Card> = Vec::new();
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(post(
+                    &uri,
+                    user,
+                    json!({ "position": 2, "cards": 2 }).to_string(),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let response: CreateClarifierResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(id, response.clarifies.reading_id);
+            assert_eq!(2, response.clarifies.position);
+            assert_eq!(2, response.cards.len());
+
+            let stored = crate::schema::readings::dsl::readings
+                .find(response.interpretation_id.parse::<Uuid>().unwrap())
+                .select(model::Reading::as_select())
+                .first(&mut conn)
+                .await
+                .unwrap();
+            assert_eq!(Some(id), stored.clarified_reading_id);
+            assert_eq!(Some(2), stored.clarified_position);
+            // The clarified reading's spend is not counted again
+            assert_eq!(None, stored.prompt_tokens);
+            assert_eq!(None, stored.completion_tokens);
+            assert_eq!(None, stored.cost_usd);
+            clarifiers.extend(response.cards);
+        }
+        // Later clarifiers skip the ones already drawn
+        let drawn = clarifiers
+            .iter()
+            .map(|c| c.arcana)
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(4, drawn.len());
+        assert!(
+            drawn
+                .iter()
+                .all(|arcana| left.iter().any(|c| c.arcana == *arcana))
+        );
+
+        // Clarifier readings stay out of the history
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/interpretation/history")
+                    .header("x-user-uuid", user.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let history: Vec<Interpretation> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(1, history.len());
+        assert_eq!(id, history[0].reading().id);
+    }
+}
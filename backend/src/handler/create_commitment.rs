@@ -0,0 +1,21 @@
+use crate::entity::reading::CreateCommitmentResponse;
+use crate::error::ResponseResult;
+use crate::repository::commitment_repository::{COMMITMENT_TTL_SECONDS, CommitmentRepository};
+use axum::Json;
+use axum::http::StatusCode;
+
+#[tracing::instrument]
+pub async fn create_commitment(
+    commitment_repository: CommitmentRepository,
+) -> (StatusCode, ResponseResult<Json<CreateCommitmentResponse>>) {
+    match commitment_repository.create().await {
+        Ok(commitment) => (
+            StatusCode::OK,
+            Ok(Json(CreateCommitmentResponse {
+                commitment,
+                expires_in: COMMITMENT_TTL_SECONDS,
+            })),
+        ),
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,62 @@
+use crate::entity::draw_session::{CreateDrawSessionResponse, DrawSession};
+use crate::entity::reading::CreateReadingRequest;
+use crate::entity::user::User;
+use crate::error::{AppError, ResponseResult};
+use crate::repository::draw_session_repository::{DRAW_SESSION_TTL_SECONDS, DrawSessionRepository};
+use crate::repository::interpretation_repository::InterpretationRepository;
+use crate::repository::spread_repository::SpreadRepository;
+use axum::Json;
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+/// First half of an interactive draw: shuffles the deck and keeps its order on the server
+/// until the querent cuts it and picks the cards.
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn create_draw_session(
+    draw_session_repository: DrawSessionRepository,
+    spread_repository: SpreadRepository,
+    interpretation_repository: InterpretationRepository,
+    user: User,
+    Json(create_reading_request): Json<CreateReadingRequest>,
+) -> (StatusCode, ResponseResult<Json<CreateDrawSessionResponse>>) {
+    if create_reading_request.commitment.is_some() {
+        return AppError::ValidateError(t!("errors.commitment_not_interactive").to_string())
+            .into_response();
+    }
+    let spread = match spread_repository
+        .resolve(create_reading_request.spread.as_deref(), user.id())
+        .await
+    {
+        Ok(spread) => spread,
+        Err(e) => return e.into_response(),
+    };
+    if let Err(e) = create_reading_request.validate(spread.as_ref()) {
+        return e.into_response();
+    }
+    if let Err(e) = interpretation_repository.check_model(
+        &create_reading_request.backend,
+        create_reading_request.model.as_deref(),
+    ) {
+        return e.into_response();
+    }
+    let (seed, entropy) = match create_reading_request.seed(None).await {
+        Ok(seed) => seed,
+        Err(e) => return e.into_response(),
+    };
+    let session = DrawSession::new(create_reading_request, user.id(), spread, &seed, entropy);
+    if let Err(e) = draw_session_repository.create(&session).await {
+        return e.into_response();
+    }
+    (
+        StatusCode::OK,
+        Ok(Json(CreateDrawSessionResponse {
+            session_id: session.id,
+            deck_size: session.cards.len(),
+            card_count: session.card_count(),
+            shuffled_times: session.shuffled_times,
+            jumpers: session.jumpers,
+            spread: session.spread,
+            expires_in: DRAW_SESSION_TTL_SECONDS,
+        })),
+    )
+}
@@ -17,6 +17,12 @@ pub async fn create_interpretation(
     StatusCode,
     ResponseResult<Json<CreateInterpretationResponse>>,
 ) {
+    if let Err(e) = interpretation_repository.check_model(
+        &create_interpretation_request.backend,
+        create_interpretation_request.model.as_deref(),
+    ) {
+        return e.into_response();
+    }
     let reading: Reading = (create_interpretation_request, &user).into();
     interpretation_repository
         .request_interpretation(reading.clone(), locale, user)
@@ -66,8 +72,14 @@ mod tests {
                     flipped: false,
                 },
             ],
+            jumpers: vec![],
             context: "".to_string(),
             backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            model: None,
+            style: None,
+            length: None,
         };
 
         let uuid = Uuid::new_v4();
@@ -135,8 +147,14 @@ mod tests {
                     flipped: true,
                 },
             ],
+            jumpers: vec![],
             context: "".to_string(),
             backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            model: None,
+            style: None,
+            length: None,
         };
 
         let uuid = Uuid::new_v4();
@@ -168,4 +186,170 @@ mod tests {
         assert_eq!(reading.question, "mock broadcast question");
         assert_eq!(reading.cards.len(), 2);
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_interpretation_stores_the_structured_interpretation() {
+        let (mut state, _) = create_test_app().await;
+        state.env.structured_interpretations = true;
+        let app = crate::app::create_app(state.clone());
+        let mut rx = subscribe_to_repo(&state);
+
+        let mut server = mockito::Server::new_async().await;
+        // Safety: tests run single-threaded via `serial_test`
+        unsafe {
+            std::env::set_var("OPENAI_BASE_URL", server.url());
+        }
+        let answer = serde_json::json!({
+            "cards": [
+                { "number": 1, "analysis": "A leap." },
+                { "number": 2, "analysis": "Two paths." }
+            ],
+            "synthesis": "Begin.",
+            "emotionalImplications": "Excitement.",
+            "advice": "Choose."
+        });
+        let _m = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "response_format": { "type": "json_schema" }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({ "choices": [{ "message": { "content": answer.to_string() } }] })
+                    .to_string(),
+            )
+            .create();
+
+        let request = CreateInterpretationRequest {
+            question: "structured question".to_string(),
+            cards: vec![
+                Card {
+                    arcana: Arcana::Major { name: Fool },
+                    flipped: false,
+                },
+                Card {
+                    arcana: Arcana::Minor {
+                        rank: webtarot_shared::model::Rank::Two,
+                        suit: webtarot_shared::model::Suit::Wands,
+                    },
+                    flipped: false,
+                },
+            ],
+            jumpers: vec![],
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let user = Uuid::new_v4();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/interpretation")
+                    .header("Content-Type", "application/json")
+                    .header("x-user-uuid", user.to_string())
+                    .body(Body::from(serde_json::to_string(&request).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let id = serde_json::from_slice::<CreateInterpretationResponse>(&body)
+            .unwrap()
+            .interpretation_id;
+        let (_, text) = wait_for_done(&mut rx, id, 5).await.unwrap();
+        // The plain text is the sections in Markdown
+        assert!(text.contains("## 2. "), "{}", text);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/v1/interpretation/{}", id))
+                    .header("x-user-uuid", user.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: crate::entity::interpretation::GetInterpretationResult =
+            serde_json::from_slice(&body).unwrap();
+        let structured = result.structured.unwrap();
+        assert_eq!("Two paths.", structured.cards[1].analysis);
+        assert_eq!("Choose.", structured.advice);
+        assert_eq!(text, result.interpretation);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_interpretation_with_a_template_without_styles_stores_the_defaults() {
+        use webtarot_shared::style::{ReadingLength, ReadingStyle};
+        use webtarot_shared::template::PromptTemplates;
+
+        let (mut state, _) = create_test_app().await;
+        let mut templates = PromptTemplates::default();
+        templates.set_default("classic@1").unwrap();
+        state.interpretation_service = state
+            .interpretation_service
+            .clone()
+            .with_templates(templates);
+        let app = crate::app::create_app(state.clone());
+        let (_server, _) = setup_mock_openai("The Fool says yes.").await;
+        let mut rx = subscribe_to_repo(&state);
+
+        let request = CreateInterpretationRequest {
+            question: "test style question".to_string(),
+            cards: vec![Card {
+                arcana: Arcana::Major { name: Fool },
+                flipped: false,
+            }],
+            jumpers: vec![],
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            model: None,
+            style: Some(ReadingStyle::YesNo),
+            length: Some(ReadingLength::Brief),
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/interpretation")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", Uuid::new_v4().to_string())
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateInterpretationResponse = serde_json::from_slice(&body).unwrap();
+        wait_for_done(&mut rx, response.interpretation_id, 5)
+            .await
+            .expect("Should receive Done event for our interpretation");
+
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let stored = crate::schema::readings::dsl::readings
+            .find(response.interpretation_id)
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(Some(1), stored.prompt_template_version);
+        assert_eq!(ReadingStyle::default().id(), stored.style);
+        assert_eq!(ReadingLength::default().id(), stored.length);
+    }
 }
@@ -1,20 +1,70 @@
 use crate::entity;
 use crate::entity::reading::{CreateReadingRequest, CreateReadingResponse};
 use crate::entity::user::User;
-use crate::error::ResponseResult;
+use crate::error::{AppError, ResponseResult};
 use crate::middleware::locale::Locale;
+use crate::repository::commitment_repository::CommitmentRepository;
 use crate::repository::interpretation_repository::InterpretationRepository;
+use crate::repository::spread_repository::SpreadRepository;
 use axum::Json;
 use axum::http::StatusCode;
+use rust_i18n::t;
+use webtarot_shared::fair::FairDraw;
 
 #[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
 pub async fn create_reading(
     interpretation_repository: InterpretationRepository,
+    spread_repository: SpreadRepository,
+    commitment_repository: CommitmentRepository,
     user: User,
     locale: Locale,
     Json(create_reading_request): Json<CreateReadingRequest>,
 ) -> (StatusCode, ResponseResult<Json<CreateReadingResponse>>) {
-    let reading = entity::reading::perform_reading(&create_reading_request, &user);
+    let spread = match spread_repository
+        .resolve(create_reading_request.spread.as_deref(), user.id())
+        .await
+    {
+        Ok(spread) => spread,
+        Err(e) => return e.into_response(),
+    };
+    if let Err(e) = create_reading_request.validate(spread.as_ref()) {
+        return e.into_response();
+    }
+    if let Err(e) = interpretation_repository.check_model(
+        &create_reading_request.backend,
+        create_reading_request.model.as_deref(),
+    ) {
+        return e.into_response();
+    }
+    let fair_draw = match create_reading_request.commitment.as_deref() {
+        Some(commitment) => match commitment_repository.take(commitment).await {
+            Ok(Some(server_seed)) => Some(FairDraw::new(
+                server_seed,
+                create_reading_request
+                    .client_seed
+                    .clone()
+                    .unwrap_or_default(),
+            )),
+            Ok(None) => {
+                return AppError::ValidateError(t!("errors.unknown_commitment").to_string())
+                    .into_response();
+            }
+            Err(e) => return e.into_response(),
+        },
+        None => None,
+    };
+    let (seed, entropy) = match create_reading_request.seed(fair_draw.as_ref()).await {
+        Ok(seed) => seed,
+        Err(e) => return e.into_response(),
+    };
+    let reading = entity::reading::perform_reading(
+        &create_reading_request,
+        &user,
+        spread,
+        fair_draw,
+        seed,
+        entropy,
+    );
     interpretation_repository
         .request_interpretation(reading.clone(), locale, user)
         .await;
@@ -53,6 +103,15 @@ mod tests {
             cards: 3,
             context: "".to_string(),
             backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
         };
 
         let uuid = Uuid::new_v4();
@@ -102,6 +161,10 @@ mod tests {
 
         // Assert we actually shuffled at least once
         assert!(reading.shuffled_times > 0, "shuffled_times should be > 0");
+
+        // The seed is persisted so the reading can be replayed
+        assert_eq!(reading.seed, response.seed.map(|seed| seed.to_string()));
+        assert!(reading.seed.is_some());
     }
 
     #[tokio::test]
@@ -142,6 +205,15 @@ mod tests {
             cards: 3,
             context: "".to_string(),
             backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
         };
 
         let uuid = Uuid::new_v4();
@@ -193,4 +265,1025 @@ mod tests {
         assert_eq!(reading.cards.len() as u8, 3);
         assert!(reading.shuffled_times > 0, "shuffled_times should be > 0");
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_records_the_model() {
+        use crate::test_helpers::wait_for_done;
+
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let mut server = Server::new_async().await;
+        unsafe {
+            std::env::set_var("OPENAI_BASE_URL", server.url());
+        }
+        let _m = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(json!({"model": "gpt-5-mini"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "choices": [{"message": {"content": "From mini"}}],
+                    "usage": {"prompt_tokens": 1000, "completion_tokens": 200}
+                })
+                .to_string(),
+            )
+            .create();
+        let repo = InterpretationRepository::from(state.clone());
+        let mut rx = repo.subscribe();
+
+        let post = |model: Option<&str>| {
+            let request = CreateReadingRequest {
+                question: "test model question".to_string(),
+                cards: 1,
+                context: "".to_string(),
+                backend: ChatGPT,
+                tradition: Default::default(),
+                deck_options: Default::default(),
+                spread: None,
+                commitment: None,
+                client_seed: None,
+                entropy: Default::default(),
+                model: model.map(str::to_string),
+                style: None,
+                length: None,
+            };
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/reading")
+                .header("Content-Type", "application/json")
+                .header("x-user-uuid", Uuid::new_v4().to_string())
+                .body(Body::from(serde_json::to_string(&request).unwrap()))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(post(Some("gpt-4o"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.oneshot(post(Some("gpt-5-mini"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        let interpretation_id = Uuid::parse_str(&response.interpretation_id).unwrap();
+        let (reading, text) = wait_for_done(&mut rx, interpretation_id, 5).await.unwrap();
+        assert_eq!("From mini", text);
+        assert_eq!(Some("gpt-5-mini"), reading.model.as_deref());
+
+        let stored = crate::schema::readings::dsl::readings
+            .find(interpretation_id)
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(Some("chatGPT"), stored.backend.as_deref());
+        assert_eq!(Some("gpt-5-mini"), stored.model.as_deref());
+        assert_eq!(Some("classic"), stored.prompt_template.as_deref());
+        assert_eq!(Some(2), stored.prompt_template_version);
+        // 1000 tokens in at $0.25 and 200 out at $2 per million
+        assert_eq!(Some(1000), stored.prompt_tokens);
+        assert_eq!(Some(200), stored.completion_tokens);
+        let cost = stored.cost_usd.unwrap();
+        assert!((cost - 0.00065).abs() < 1e-12, "{}", cost);
+        assert_eq!(Some(cost), reading.cost);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_refused_once_the_daily_budget_is_spent() {
+        use crate::test_helpers::wait_for_done;
+        use redis::AsyncCommands;
+
+        let (state, app) = create_test_app().await;
+        let mut server = Server::new_async().await;
+        unsafe {
+            std::env::set_var("OPENAI_BASE_URL", server.url());
+        }
+        let never = server
+            .mock("POST", "/v1/chat/completions")
+            .expect(0)
+            .create();
+        let key = format!(
+            "interpretation_spend:{}",
+            chrono::Utc::now().format("%Y-%m-%d")
+        );
+        let mut redis = state.redis_connection_manager.clone();
+        // The test app allows $10 a day
+        let _: () = redis.set(&key, 10.0).await.unwrap();
+        let repo = InterpretationRepository::from(state.clone());
+        let mut rx = repo.subscribe();
+
+        let request = CreateReadingRequest {
+            question: "test budget question".to_string(),
+            cards: 1,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("accept-language", "en")
+            .header("x-user-uuid", Uuid::new_v4().to_string())
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        let interpretation_id = Uuid::parse_str(&response.interpretation_id).unwrap();
+        let result = wait_for_done(&mut rx, interpretation_id, 5).await;
+        let _: () = redis.del(&key).await.unwrap();
+
+        let err = result.unwrap_err();
+        assert!(err.contains("budget has been spent"), "{}", err);
+        never.assert();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_streams_the_interpretation() {
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let mut server = Server::new_async().await;
+        unsafe {
+            std::env::set_var("OPENAI_BASE_URL", server.url());
+        }
+        let pieces = ["The Sun ", "brings ", "clarity."];
+        let _m = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(json!({"stream": true})))
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(
+                pieces
+                    .iter()
+                    .map(|piece| {
+                        let chunk = json!({"choices": [{"delta": {"content": piece}}]});
+                        format!("data: {}\n\n", chunk)
+                    })
+                    .chain(["data: [DONE]\n\n".to_string()])
+                    .collect::<String>(),
+            )
+            .create();
+        let repo = InterpretationRepository::from(state.clone());
+        let mut rx = repo.subscribe();
+
+        let request = CreateReadingRequest {
+            question: "test streaming question".to_string(),
+            cards: 1,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", Uuid::new_v4().to_string())
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        let interpretation_id = Uuid::parse_str(&response.interpretation_id).unwrap();
+
+        let mut chunks = Vec::new();
+        let text = loop {
+            match tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+                .await
+                .expect("Should receive the interpretation")
+                .unwrap()
+            {
+                Interpretation::Streaming(reading, chunk) if reading.id == interpretation_id => {
+                    chunks.push(chunk)
+                }
+                Interpretation::Done(reading, text, _) if reading.id == interpretation_id => {
+                    break text;
+                }
+                Interpretation::Failed(reading, err) if reading.id == interpretation_id => {
+                    panic!("Interpretation failed unexpectedly: {}", err);
+                }
+                _ => {}
+            }
+        };
+        assert_eq!(pieces.to_vec(), chunks);
+        assert_eq!(pieces.concat(), text);
+
+        let stored = crate::schema::readings::dsl::readings
+            .find(interpretation_id)
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert!(matches!(
+            stored.interpretation_status,
+            model::InterpretationStatus::Done
+        ));
+        assert_eq!(pieces.concat(), stored.interpretation_text);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_interpreted_by_claude() {
+        use crate::test_helpers::wait_for_done;
+        use webtarot_shared::explain::InterpretationBackend::Claude;
+
+        let (state, app) = create_test_app().await;
+        let mut server = Server::new_async().await;
+        unsafe {
+            std::env::set_var("ANTHROPIC_BASE_URL", server.url());
+        }
+        let _m = server
+            .mock("POST", "/v1/messages")
+            .match_header("x-api-key", "dummy")
+            .match_body(Matcher::Regex("test claude question".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"content": [{"type": "text", "text": "Claude reads"}]}).to_string())
+            .create();
+        let repo = InterpretationRepository::from(state.clone());
+        let mut rx = repo.subscribe();
+
+        let request = CreateReadingRequest {
+            question: "test claude question".to_string(),
+            cards: 3,
+            context: "".to_string(),
+            backend: Claude,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", Uuid::new_v4().to_string())
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+
+        let interpretation_id = Uuid::parse_str(&response.interpretation_id).unwrap();
+        let (_, text) = wait_for_done(&mut rx, interpretation_id, 5).await.unwrap();
+        assert_eq!("Claude reads", text);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_fails_over_when_the_backend_is_down() {
+        use crate::test_helpers::wait_for_done;
+
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let mut openai = Server::new_async().await;
+        let mut anthropic = Server::new_async().await;
+        unsafe {
+            std::env::set_var("OPENAI_BASE_URL", openai.url());
+            std::env::set_var("ANTHROPIC_BASE_URL", anthropic.url());
+        }
+        let down = openai
+            .mock("POST", "/v1/chat/completions")
+            .with_status(503)
+            .with_body("upstream unavailable")
+            .expect(3)
+            .create();
+        let _claude = anthropic
+            .mock("POST", "/v1/messages")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"content": [{"type": "text", "text": "Claude stands in"}]}).to_string(),
+            )
+            .create();
+        let repo = InterpretationRepository::from(state.clone());
+        let mut rx = repo.subscribe();
+
+        let request = CreateReadingRequest {
+            question: "test failover question".to_string(),
+            cards: 1,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", Uuid::new_v4().to_string())
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        let interpretation_id = Uuid::parse_str(&response.interpretation_id).unwrap();
+        let (reading, text) = wait_for_done(&mut rx, interpretation_id, 5).await.unwrap();
+        assert_eq!("Claude stands in", text);
+        assert_eq!(
+            Some(webtarot_shared::explain::InterpretationBackend::Claude),
+            reading.backend
+        );
+        assert_eq!(Some(4), reading.attempts);
+        down.assert();
+
+        let stored = crate::schema::readings::dsl::readings
+            .find(interpretation_id)
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(Some("claude"), stored.backend.as_deref());
+        assert_eq!(Some("claude-sonnet-4-5"), stored.model.as_deref());
+        assert_eq!(Some(4), stored.interpretation_attempts);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_with_spread() {
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+
+        let request = CreateReadingRequest {
+            question: "test spread question".to_string(),
+            cards: 3,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: Some("celticCross".to_string()),
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+
+        let uuid = Uuid::new_v4();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", uuid.to_string())
+            .header("x-locale", "en")
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+
+        // The spread decides the card count, not `cards`
+        assert_eq!(response.cards.len(), 10);
+        let spread = response.spread.expect("response should carry the spread");
+        assert_eq!(spread.id, "celticCross");
+        assert_eq!(spread.positions.len(), 10);
+        assert_eq!(spread.positions[0].name, "Present");
+
+        let reading = crate::schema::readings::dsl::readings
+            .filter(crate::schema::readings::dsl::user_id.eq(uuid))
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        let persisted: webtarot_shared::model::Spread = reading.spread.unwrap().into();
+        assert_eq!(persisted, spread);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_with_unknown_spread() {
+        let (_state, app) = create_test_app().await;
+
+        let request = CreateReadingRequest {
+            question: "test spread question".to_string(),
+            cards: 3,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: Some("noSuchSpread".to_string()),
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", Uuid::new_v4().to_string())
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_with_custom_spread() {
+        use crate::entity::interpretation::GetInterpretationResult;
+        use crate::entity::spread::SaveSpreadRequest;
+        use crate::test_helpers::insert_user_with_token;
+        use webtarot_shared::model::SpreadPosition;
+
+        let (state, app) = create_test_app().await;
+        let (user, token) = insert_user_with_token(&state).await;
+        let positions = vec![
+            SpreadPosition {
+                name: "Root".to_string(),
+                description: "Where it started".to_string(),
+                x: Some(0.0),
+                y: Some(1.0),
+            },
+            SpreadPosition {
+                name: "Crown".to_string(),
+                description: "Where it is going".to_string(),
+                x: Some(0.0),
+                y: Some(-1.0),
+            },
+        ];
+        let spread = SpreadRepository::from(state.clone())
+            .create(
+                user.id,
+                SaveSpreadRequest {
+                    name: "Tree".to_string(),
+                    positions: positions.clone(),
+                    shared: false,
+                },
+            )
+            .await
+            .unwrap();
+
+        let request = CreateReadingRequest {
+            question: "test custom spread".to_string(),
+            cards: 5,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: Some(spread.id.to_string()),
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("authorization", format!("Bearer {}", token.token))
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.cards.len(), 2);
+
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!(
+                "/api/v1/interpretation/{}",
+                response.interpretation_id
+            ))
+            .header("authorization", format!("Bearer {}", token.token))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: GetInterpretationResult = serde_json::from_slice(&body).unwrap();
+        let persisted = result.reading.unwrap().spread.unwrap();
+        assert_eq!(persisted.id, spread.id.to_string());
+        assert_eq!(persisted.name, "Tree");
+        assert_eq!(persisted.positions, positions);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_defaults_to_the_user_style_and_length() {
+        use crate::test_helpers::insert_user_with_token;
+        use webtarot_shared::style::{ReadingLength, ReadingStyle};
+
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let (user, token) = insert_user_with_token(&state).await;
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/api/v1/user")
+                    .header("Content-Type", "application/json")
+                    .header("authorization", format!("Bearer {}", token.token))
+                    .body(Body::from(
+                        json!({
+                            "name": user.name,
+                            "selfDescription": user.self_description,
+                            "email": user.email,
+                            "readingStyle": "yesNo",
+                            "readingLength": "brief"
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let post = |length: Option<ReadingLength>| {
+            let request = CreateReadingRequest {
+                question: "test style question".to_string(),
+                cards: 1,
+                context: "".to_string(),
+                backend: ChatGPT,
+                tradition: Default::default(),
+                deck_options: Default::default(),
+                spread: None,
+                commitment: None,
+                client_seed: None,
+                entropy: Default::default(),
+                model: None,
+                style: None,
+                length,
+            };
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/reading")
+                .header("Content-Type", "application/json")
+                .header("authorization", format!("Bearer {}", token.token))
+                .body(Body::from(serde_json::to_string(&request).unwrap()))
+                .unwrap()
+        };
+        for (length, expected) in [
+            (None, ReadingLength::Brief),
+            (Some(ReadingLength::InDepth), ReadingLength::InDepth),
+        ] {
+            let response = app.clone().oneshot(post(length)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+            let stored = crate::schema::readings::dsl::readings
+                .find(Uuid::parse_str(&response.interpretation_id).unwrap())
+                .select(model::Reading::as_select())
+                .first(&mut conn)
+                .await
+                .unwrap();
+            assert_eq!(ReadingStyle::YesNo.id(), stored.style);
+            assert_eq!(expected.id(), stored.length);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_with_commitment() {
+        use crate::entity::reading::CreateCommitmentResponse;
+        use webtarot_shared::fair::{VerifiableReading, verify};
+
+        let (_state, app) = create_test_app().await;
+        let uuid = Uuid::new_v4();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading/commitment")
+            .header("x-user-uuid", uuid.to_string())
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let commitment: CreateCommitmentResponse = serde_json::from_slice(&body).unwrap();
+
+        let create_request = CreateReadingRequest {
+            question: "test fair question".to_string(),
+            cards: 4,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: None,
+            commitment: Some(commitment.commitment.clone()),
+            client_seed: Some("my lucky words".to_string()),
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", uuid.to_string())
+            .body(Body::from(serde_json::to_string(&create_request).unwrap()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+
+        // The server seed is revealed and the draw can be recomputed from it
+        let fair_draw = response.fair_draw.expect("response should reveal the draw");
+        assert_eq!(fair_draw.commitment, commitment.commitment);
+        assert_eq!(fair_draw.client_seed, "my lucky words");
+        verify(&VerifiableReading {
+            question: "test fair question".to_string(),
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            shuffled_times: response.shuffled_times,
+            cards: response.cards,
+            jumpers: response.jumpers,
+            shuffle: Default::default(),
+            fair_draw,
+        })
+        .unwrap();
+
+        // A commitment backs a single reading
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", uuid.to_string())
+            .body(Body::from(serde_json::to_string(&create_request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_with_tradition() {
+        use webtarot_shared::model::{Arcana, ArcanaFilter, DeckTradition};
+
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let uuid = Uuid::new_v4();
+
+        let mut create_request = CreateReadingRequest {
+            question: "test lenormand question".to_string(),
+            cards: 5,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: DeckTradition::Lenormand,
+            deck_options: Default::default(),
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", uuid.to_string())
+            .body(Body::from(serde_json::to_string(&create_request).unwrap()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.tradition, DeckTradition::Lenormand);
+        assert_eq!(response.cards.len(), 5);
+        assert!(
+            response
+                .cards
+                .iter()
+                .all(|card| matches!(card.arcana, Arcana::Lenormand { .. }) && !card.flipped)
+        );
+
+        let reading = crate::schema::readings::dsl::readings
+            .filter(crate::schema::readings::dsl::user_id.eq(uuid))
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(reading.tradition, "lenormand");
+
+        // Arcana subsets do not apply to a Lenormand deck
+        create_request.deck_options.arcana = ArcanaFilter::MajorOnly;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", uuid.to_string())
+            .body(Body::from(serde_json::to_string(&create_request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_with_deck_options() {
+        use webtarot_shared::model::{Arcana, ArcanaFilter, DeckOptions};
+
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let uuid = Uuid::new_v4();
+
+        let mut create_request = CreateReadingRequest {
+            question: "test majors question".to_string(),
+            cards: 10,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: DeckOptions {
+                arcana: ArcanaFilter::MajorOnly,
+                suits: vec![],
+                reversal_probability: 0.0,
+                jumper_probability: 0.0,
+            },
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", uuid.to_string())
+            .body(Body::from(serde_json::to_string(&create_request).unwrap()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.cards.len(), 10);
+        assert!(
+            response
+                .cards
+                .iter()
+                .all(|card| matches!(card.arcana, Arcana::Major { .. }) && !card.flipped)
+        );
+
+        let reading = crate::schema::readings::dsl::readings
+            .filter(crate::schema::readings::dsl::user_id.eq(uuid))
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        let persisted: DeckOptions = reading.deck_options.unwrap().into();
+        assert_eq!(persisted, create_request.deck_options);
+
+        create_request.deck_options.reversal_probability = 2.0;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", uuid.to_string())
+            .body(Body::from(serde_json::to_string(&create_request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_with_jumpers() {
+        use webtarot_shared::model::{DeckOptions, MAX_JUMPERS};
+
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let uuid = Uuid::new_v4();
+
+        let create_request = CreateReadingRequest {
+            question: "test jumpers question".to_string(),
+            cards: 3,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: DeckOptions {
+                jumper_probability: 1.0,
+                ..Default::default()
+            },
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/reading")
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", uuid.to_string())
+            .body(Body::from(serde_json::to_string(&create_request).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(3, response.cards.len());
+        assert_eq!(MAX_JUMPERS, response.jumpers.len());
+        assert!(response.cards.iter().all(|card| {
+            response
+                .jumpers
+                .iter()
+                .all(|jumper| jumper.arcana != card.arcana)
+        }));
+
+        let reading = crate::schema::readings::dsl::readings
+            .filter(crate::schema::readings::dsl::user_id.eq(uuid))
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        let cards: Vec<webtarot_shared::model::Card> = reading.cards.into();
+        let jumpers: Vec<webtarot_shared::model::Card> = reading.jumpers.unwrap().into();
+        assert_eq!(response.cards, cards);
+        assert_eq!(response.jumpers, jumpers);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_reading_with_entropy_sources() {
+        use webtarot_shared::entropy::{Entropy, EntropyChoice, beacon_seed};
+
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let uuid = Uuid::new_v4();
+        let mut beacon = Server::new_async().await;
+        unsafe {
+            std::env::set_var("RANDOMNESS_BEACON_URL", beacon.url());
+        }
+        let pulse_uri = "https://beacon.example/beacon/2.0/chain/1/pulse/7";
+        beacon
+            .mock("GET", "/beacon/2.0/pulse/last")
+            .with_status(200)
+            .with_body(
+                json!({ "pulse": { "uri": pulse_uri, "outputValue": "0f".repeat(64) } })
+                    .to_string(),
+            )
+            .create();
+
+        let post = |entropy: EntropyChoice| {
+            let request = CreateReadingRequest {
+                question: "test entropy question".to_string(),
+                cards: 3,
+                context: "".to_string(),
+                backend: ChatGPT,
+                tradition: Default::default(),
+                deck_options: Default::default(),
+                spread: None,
+                commitment: None,
+                client_seed: None,
+                entropy,
+                model: None,
+                style: None,
+                length: None,
+            };
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/reading")
+                .header("Content-Type", "application/json")
+                .header("x-user-uuid", uuid.to_string())
+                .body(Body::from(serde_json::to_string(&request).unwrap()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(post(EntropyChoice::User {
+                samples: vec![120, 80, 95],
+            }))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let cases = [
+            (EntropyChoice::Os, Entropy::Os),
+            (
+                EntropyChoice::User {
+                    samples: (0..32).map(|i| 60 + i * 7).collect(),
+                },
+                Entropy::User { samples: 32 },
+            ),
+        ];
+        for (choice, expected) in cases {
+            let response = app.clone().oneshot(post(choice)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(Some(expected.clone()), response.entropy);
+
+            let reading = crate::schema::readings::dsl::readings
+                .find(response.interpretation_id.parse::<Uuid>().unwrap())
+                .select(model::Reading::as_select())
+                .first(&mut conn)
+                .await
+                .unwrap();
+            let persisted: Entropy = reading.entropy.unwrap().into();
+            assert_eq!(expected, persisted);
+        }
+
+        // Readings of the same pulse get different seeds, each verifiable from its salt
+        let mut seeds = Vec::new();
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(post(EntropyChoice::Beacon))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+            let Some(Entropy::Beacon {
+                pulse,
+                salt: Some(salt),
+            }) = response.entropy
+            else {
+                panic!("not a salted beacon seed: {:?}", response.entropy);
+            };
+            assert_eq!(pulse_uri, pulse);
+            let seed = response.seed.unwrap();
+            assert_eq!(beacon_seed(&"0f".repeat(64), &salt), seed);
+            seeds.push(seed);
+        }
+        assert_ne!(seeds[0], seeds[1]);
+    }
 }
@@ -0,0 +1,189 @@
+use crate::entity::interpretation::Interpretation;
+use crate::entity::reading_message::{CreateReadingMessageRequest, ReadingMessage, answer_pending};
+use crate::entity::user::User;
+use crate::error::{AppError, ResponseResult};
+use crate::middleware::locale::Locale;
+use crate::repository::interpretation_repository::InterpretationRepository;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+/// Asks a follow-up question on an interpreted reading, without drawing again. The backend that
+/// interpreted the reading answers in the background; subscribers get `replyChunk` messages
+/// while it writes and `done` once the answer is in the thread `GET
+/// /api/v1/interpretation/{id}` returns.
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn create_reading_message(
+    interpretation_repository: InterpretationRepository,
+    user: User,
+    locale: Locale,
+    Path(interpretation_id): Path<String>,
+    Json(create_reading_message_request): Json<CreateReadingMessageRequest>,
+) -> (StatusCode, ResponseResult<Json<ReadingMessage>>) {
+    let Ok(uuid) = interpretation_id.parse() else {
+        return AppError::ValidateError("invalid uuid".into()).into_response();
+    };
+    let interpretation = match interpretation_repository.get_interpretation(uuid).await {
+        Some(interpretation) if interpretation.reading().user_id == Some(user.id()) => {
+            interpretation
+        }
+        _ => return AppError::NotFound.into_response(),
+    };
+    let Interpretation::Done(reading, text, _) = interpretation else {
+        return AppError::ValidateError(t!("errors.reading_not_done").to_string()).into_response();
+    };
+    let question = match create_reading_message_request.validate() {
+        Ok(question) => question,
+        Err(e) => return e.into_response(),
+    };
+    let earlier = interpretation_repository.get_messages(uuid).await;
+    if answer_pending(&earlier) {
+        return AppError::ValidateError(t!("errors.answer_pending").to_string()).into_response();
+    }
+    let question = interpretation_repository
+        .ask(reading, text, earlier, question, locale)
+        .await;
+    (StatusCode::OK, Ok(Json(question)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::entity::interpretation::GetInterpretationResult;
+    use crate::model;
+    use crate::test_helpers::{insert_reading_row, reading_row, subscribe_to_repo, wait_for_done};
+    use axum::body::Body;
+    use axum::extract::Request;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use webtarot_shared::model::Deck;
+    use webtarot_shared::provider::Role;
+
+    fn post(uri: &str, user: Uuid, text: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("accept-language", "en")
+            .header("x-user-uuid", user.to_string())
+            .body(Body::from(json!({ "text": text }).to_string()))
+            .unwrap()
+    }
+
+    fn answer(server: &mut mockito::ServerGuard, question: &str, text: &str) -> mockito::Mock {
+        server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::AllOf(vec![
+                // The interpretation comes before the question
+                Matcher::Regex("original text".into()),
+                Matcher::Regex(question.into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "choices": [{ "message": { "content": text } }] }).to_string())
+            .create()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_follow_up_questions_are_answered_in_the_thread() {
+        let (state, app) = create_test_app().await;
+        let user = Uuid::new_v4();
+        let mut server = Server::new_async().await;
+        // Safety: tests run single-threaded via `serial_test`
+        unsafe {
+            std::env::set_var("OPENAI_BASE_URL", server.url());
+        }
+        let mut rx = subscribe_to_repo(&state);
+
+        let id = insert_reading_row(
+            &state,
+            model::Reading {
+                question: "How is my family?".to_string(),
+                interpretation_text: "original text".to_string(),
+                backend: Some("chatGPT".to_string()),
+                model: Some("gpt-5.1".to_string()),
+                ..reading_row(user, Deck::build().cards[..3].to_vec())
+            },
+        )
+        .await;
+        let uri = format!("/api/v1/interpretation/{}/messages", id);
+
+        // Only the owner may ask, and only something
+        let response = app
+            .clone()
+            .oneshot(post(&uri, Uuid::new_v4(), "What about my sister?"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app.clone().oneshot(post(&uri, user, "  ")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let sister = answer(&mut server, "What about my sister", "She will call soon.");
+        let response = app
+            .clone()
+            .oneshot(post(&uri, user, "What about my sister?"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let question: ReadingMessage = serde_json::from_slice(&body).unwrap();
+        assert_eq!(Role::User, question.role);
+        assert_eq!("What about my sister?", question.content);
+        wait_for_done(&mut rx, id, 5).await.unwrap();
+        sister.assert();
+        drop(sister);
+
+        // Later questions carry the earlier ones and their answers
+        let brother = answer(
+            &mut server,
+            "She will call soon.*What about my brother",
+            "Fine.",
+        );
+        let response = app
+            .clone()
+            .oneshot(post(&uri, user, "What about my brother?"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        wait_for_done(&mut rx, id, 5).await.unwrap();
+        brother.assert();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/v1/interpretation/{}", id))
+                    .header("x-user-uuid", user.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: GetInterpretationResult = serde_json::from_slice(&body).unwrap();
+        assert_eq!("original text", result.interpretation);
+        let thread = result
+            .messages
+            .iter()
+            .map(|message| (message.role, message.content.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Role::User, "What about my sister?"),
+                (Role::Assistant, "She will call soon."),
+                (Role::User, "What about my brother?"),
+                (Role::Assistant, "Fine."),
+            ],
+            thread
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::entity::spread::{CustomSpread, SaveSpreadRequest};
+use crate::entity::user::User;
+use crate::error::{AppError, ResponseResult};
+use crate::repository::spread_repository::SpreadRepository;
+use axum::Json;
+use axum::http::StatusCode;
+
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn create_spread(
+    spread_repository: SpreadRepository,
+    user: User,
+    Json(request): Json<SaveSpreadRequest>,
+) -> (StatusCode, ResponseResult<Json<CustomSpread>>) {
+    let User::Authenticated { id, .. } = user else {
+        return AppError::Forbidden.into_response();
+    };
+    match spread_repository.create(id, request).await {
+        Ok(spread) => (StatusCode::CREATED, Ok(Json(spread))),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::test_helpers::insert_user_with_token;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use webtarot_shared::model::SpreadPosition;
+
+    fn position(name: &str) -> SpreadPosition {
+        SpreadPosition {
+            name: name.to_string(),
+            description: format!("{} description", name),
+            x: Some(1.0),
+            y: None,
+        }
+    }
+
+    fn save_request(body: &SaveSpreadRequest, auth: (&str, String)) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/api/v1/spreads")
+            .header("Content-Type", "application/json")
+            .header(auth.0, auth.1)
+            .body(Body::from(serde_json::to_string(body).unwrap()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_spread_registered_user() {
+        let (state, app) = create_test_app().await;
+        let (user, token) = insert_user_with_token(&state).await;
+        let request = SaveSpreadRequest {
+            name: "Crossroads".to_string(),
+            positions: vec![position("Left path"), position("Right path")],
+            shared: false,
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(save_request(
+                &request,
+                ("authorization", format!("Bearer {}", token.token)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let spread: CustomSpread = serde_json::from_slice(&body).unwrap();
+        let stored = SpreadRepository::from(state.clone())
+            .find(spread.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.user_id, user.id);
+        assert_eq!(spread.name, "Crossroads");
+        assert_eq!(spread.positions, request.positions);
+
+        let req = Request::builder()
+            .method("GET")
+            .uri("/api/v1/spreads")
+            .header("authorization", format!("Bearer {}", token.token))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let spreads: Vec<CustomSpread> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(spreads.len(), 1);
+        assert_eq!(spreads[0].id, spread.id);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_spread_anon_user_forbidden() {
+        let (_state, app) = create_test_app().await;
+        let request = SaveSpreadRequest {
+            name: "Crossroads".to_string(),
+            positions: vec![position("Left path")],
+            shared: false,
+        };
+        let resp = app
+            .oneshot(save_request(
+                &request,
+                ("x-user-uuid", Uuid::new_v4().to_string()),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_spread_validates_positions() {
+        let (state, app) = create_test_app().await;
+        let (_user, token) = insert_user_with_token(&state).await;
+        let request = SaveSpreadRequest {
+            name: "Empty".to_string(),
+            positions: vec![],
+            shared: false,
+        };
+        let resp = app
+            .oneshot(save_request(
+                &request,
+                ("authorization", format!("Bearer {}", token.token)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
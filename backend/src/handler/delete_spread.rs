@@ -0,0 +1,120 @@
+use crate::entity::user::User;
+use crate::error::AppError;
+use crate::repository::spread_repository::SpreadRepository;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn delete_spread(
+    spread_repository: SpreadRepository,
+    user: User,
+    Path(spread_id): Path<String>,
+) -> StatusCode {
+    let User::Authenticated { id, .. } = user else {
+        return AppError::Forbidden.status_code();
+    };
+    let Ok(spread_id) = spread_id.parse::<Uuid>() else {
+        return StatusCode::BAD_REQUEST;
+    };
+    match spread_repository.delete(spread_id, id).await {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(e) => e.status_code(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::entity::spread::{CustomSpread, SaveSpreadRequest};
+    use crate::test_helpers::insert_user_with_token;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use webtarot_shared::model::SpreadPosition;
+
+    async fn create_spread(state: &crate::state::AppState, user_id: Uuid) -> CustomSpread {
+        SpreadRepository::from(state.clone())
+            .create(
+                user_id,
+                SaveSpreadRequest {
+                    name: "Mirror".to_string(),
+                    positions: vec![SpreadPosition {
+                        name: "Reflection".to_string(),
+                        description: String::new(),
+                        x: None,
+                        y: None,
+                    }],
+                    shared: false,
+                },
+            )
+            .await
+            .unwrap()
+    }
+
+    fn delete_request(id: Uuid, auth: (&str, String)) -> Request<Body> {
+        Request::builder()
+            .method("DELETE")
+            .uri(format!("/api/v1/spreads/{}", id))
+            .header(auth.0, auth.1)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_delete_spread_owner_only() {
+        let (state, app) = create_test_app().await;
+        let (owner, owner_token) = insert_user_with_token(&state).await;
+        let (_other, other_token) = insert_user_with_token(&state).await;
+        let spread = create_spread(&state, owner.id).await;
+        let repository = SpreadRepository::from(state.clone());
+
+        let resp = app
+            .clone()
+            .oneshot(delete_request(
+                spread.id,
+                ("authorization", format!("Bearer {}", other_token.token)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert!(repository.find(spread.id).await.unwrap().is_some());
+
+        let resp = app
+            .oneshot(delete_request(
+                spread.id,
+                ("authorization", format!("Bearer {}", owner_token.token)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(repository.find(spread.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_delete_spread_anonymous_forbidden() {
+        let (state, app) = create_test_app().await;
+        let (owner, _token) = insert_user_with_token(&state).await;
+        let spread = create_spread(&state, owner.id).await;
+
+        let resp = app
+            .oneshot(delete_request(
+                spread.id,
+                ("x-user-uuid", Uuid::new_v4().to_string()),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        assert!(
+            SpreadRepository::from(state.clone())
+                .find(spread.id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+}
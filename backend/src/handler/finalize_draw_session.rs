@@ -0,0 +1,175 @@
+use crate::entity::draw_session::PickCardsRequest;
+use crate::entity::reading::CreateReadingResponse;
+use crate::entity::user::User;
+use crate::error::{AppError, ResponseResult};
+use crate::middleware::locale::Locale;
+use crate::repository::draw_session_repository::DrawSessionRepository;
+use crate::repository::interpretation_repository::InterpretationRepository;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+/// Second half of an interactive draw: cuts the stored deck, takes the picked cards and
+/// requests the interpretation. A session can only be finalized once, by its owner.
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn finalize_draw_session(
+    draw_session_repository: DrawSessionRepository,
+    interpretation_repository: InterpretationRepository,
+    user: User,
+    locale: Locale,
+    Path(session_id): Path<String>,
+    Json(pick_cards_request): Json<PickCardsRequest>,
+) -> (StatusCode, ResponseResult<Json<CreateReadingResponse>>) {
+    let Ok(session_id) = session_id.parse::<Uuid>() else {
+        return AppError::ValidateError("invalid uuid".into()).into_response();
+    };
+    let session = match draw_session_repository.find(session_id).await {
+        Ok(Some(session)) if session.user_id == user.id() => session,
+        Ok(_) => return AppError::NotFound.into_response(),
+        Err(e) => return e.into_response(),
+    };
+    let reading = match session.finalize(&pick_cards_request, &user) {
+        Ok(reading) => reading,
+        Err(e) => return e.into_response(),
+    };
+    match draw_session_repository.remove(session_id).await {
+        Ok(true) => {}
+        Ok(false) => return AppError::NotFound.into_response(),
+        Err(e) => return e.into_response(),
+    }
+    interpretation_repository
+        .request_interpretation(reading.clone(), locale, user)
+        .await;
+    (
+        StatusCode::OK,
+        Ok(Json(CreateReadingResponse::from(reading))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::entity::draw_session::CreateDrawSessionResponse;
+    use crate::entity::reading::CreateReadingRequest;
+    use crate::model;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+    use diesel_async::RunQueryDsl;
+    use serde_json::json;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use webtarot_shared::explain::InterpretationBackend::ChatGPT;
+
+    fn post(uri: &str, user: Uuid, body: String) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("x-user-uuid", user.to_string())
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_interactive_draw_session() {
+        let (state, app) = create_test_app().await;
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        let user = Uuid::new_v4();
+
+        let request = CreateReadingRequest {
+            question: "test question".to_string(),
+            cards: 3,
+            context: "".to_string(),
+            backend: ChatGPT,
+            tradition: Default::default(),
+            deck_options: Default::default(),
+            spread: None,
+            commitment: None,
+            client_seed: None,
+            entropy: Default::default(),
+            model: None,
+            style: None,
+            length: None,
+        };
+        let response = app
+            .clone()
+            .oneshot(post(
+                "/api/v1/draw-session",
+                user,
+                serde_json::to_string(&request).unwrap(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let session: CreateDrawSessionResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(78, session.deck_size);
+        assert_eq!(3, session.card_count);
+        let uri = format!("/api/v1/draw-session/{}", session.session_id);
+
+        // A pick outside the chosen pile is rejected and leaves the session usable
+        let response = app
+            .clone()
+            .oneshot(post(
+                &uri,
+                user,
+                json!({ "cuts": [26, 52], "pile": 0, "picks": [0, 1, 26] }).to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Only the owner may finalize it
+        let response = app
+            .clone()
+            .oneshot(post(
+                &uri,
+                Uuid::new_v4(),
+                json!({ "cuts": [26, 52], "pile": 2, "picks": [0, 1, 2] }).to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                &uri,
+                user,
+                json!({ "cuts": [26, 52], "pile": 2, "picks": [25, 0, 7] }).to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(3, response.cards.len());
+        assert_eq!(session.shuffled_times, response.shuffled_times);
+        assert!(response.seed.is_none());
+
+        let reading = crate::schema::readings::dsl::readings
+            .filter(crate::schema::readings::dsl::user_id.eq(user))
+            .select(model::Reading::as_select())
+            .first(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(reading.id.to_string(), response.interpretation_id);
+        let persisted: Vec<webtarot_shared::model::Card> = reading.cards.into();
+        assert_eq!(response.cards, persisted);
+
+        // Sessions are single use
+        let response = app
+            .oneshot(post(&uri, user, json!({ "picks": [0, 1, 2] }).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::repository::interpretation_repository::InterpretationRepository;
+use axum::Json;
+use webtarot_shared::entropy::Entropy;
+use webtarot_shared::fairness::{FairnessAnalysis, FairnessReport};
+use webtarot_shared::model::{Deck, ShuffleMethod};
+
+/// Compares the cards of the readings the server shuffled and drew with what fair draws would
+/// have given. Readings with cards supplied by the client or picked by hand, and clarifiers,
+/// have no seed and are left out. So are readings whose seed the client could choose, which
+/// could skew the report, and readings shuffled the legacy way.
+#[tracing::instrument]
+pub async fn get_fairness(
+    interpretation_repository: InterpretationRepository,
+) -> Json<FairnessReport> {
+    let mut analysis = FairnessAnalysis::new();
+    for interpretation in interpretation_repository.get_all_interpretations().await {
+        let reading = interpretation.into_reading();
+        // Readings made before sources were recorded were all seeded by the server
+        let server_seeded = matches!(
+            reading.entropy,
+            None | Some(Entropy::Os | Entropy::Beacon { .. } | Entropy::Commitment)
+        );
+        if reading.seed.is_none() || !server_seeded || reading.shuffle != ShuffleMethod::default() {
+            continue;
+        }
+        let deck = Deck::build_with(reading.tradition, reading.deck_options);
+        analysis.record(&deck, &[reading.cards, reading.jumpers].concat());
+    }
+    Json(analysis.report())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::StatusCode;
+    use serde_json::json;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    async fn get_report(app: &axum::Router) -> FairnessReport {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/stats/fairness")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fairness_counts_drawn_readings_only() {
+        let (_state, app) = create_test_app().await;
+        let user = Uuid::new_v4().to_string();
+        let before = get_report(&app).await;
+
+        let post = |uri: &str, body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("Content-Type", "application/json")
+                .header("x-user-uuid", &user)
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let reading = json!({
+            "question": "Is it fair?",
+            "cards": 3,
+            "context": "",
+            "backend": "chatGPT"
+        });
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(post("/api/v1/reading", reading.clone()))
+                .await
+                .unwrap();
+            assert_eq!(StatusCode::OK, response.status());
+        }
+        // Cards supplied by the client say nothing about the draw
+        let response = app
+            .clone()
+            .oneshot(post(
+                "/api/v1/interpretation",
+                json!({
+                    "question": "Is it fair?",
+                    "cards": [{ "arcana": { "major": { "name": "tower" } }, "flipped": false }],
+                    "context": "",
+                    "backend": "chatGPT"
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        // Nor do seeds and samples of the client's choosing
+        for entropy in [
+            json!({ "source": "seeded", "seed": "ab".repeat(32) }),
+            json!({ "source": "user", "samples": (0..32).map(|i| 60 + i * 7).collect::<Vec<_>>() }),
+        ] {
+            let mut reading = reading.clone();
+            reading["entropy"] = entropy;
+            let response = app
+                .clone()
+                .oneshot(post("/api/v1/reading", reading))
+                .await
+                .unwrap();
+            assert_eq!(StatusCode::OK, response.status());
+        }
+
+        let after = get_report(&app).await;
+        assert_eq!(before.draws + 2, after.draws);
+        assert_eq!(before.cards_drawn + 6, after.cards_drawn);
+        assert!(after.cards.len() >= 78);
+    }
+}
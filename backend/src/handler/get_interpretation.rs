@@ -29,16 +29,18 @@ pub async fn get_interpretation(
             )),
         );
     };
-    (StatusCode::OK, Ok(Json(interpretation.into())))
+    let mut result = GetInterpretationResult::from(interpretation);
+    result.messages = interpretation_repository.get_messages(uuid).await;
+    (StatusCode::OK, Ok(Json(result)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app::create_test_app;
+    use crate::test_helpers::{insert_reading_row, insert_user_with_token, reading_row};
     use axum::body::Body;
     use axum::extract::Request;
-    use diesel_async::RunQueryDsl;
     use serial_test::serial;
     use tower::ServiceExt;
     use uuid::Uuid;
@@ -48,43 +50,6 @@ mod tests {
     // Backend DB models
     use crate::model;
 
-    // Small fixture helpers using Diesel models
-    async fn insert_user_with_token(
-        state: &crate::state::AppState,
-    ) -> (model::User, model::NewAccessToken) {
-        use chrono::Utc;
-        let mut conn = state.postgresql_pool.get().await.unwrap();
-        let user = model::User {
-            id: Uuid::new_v4(),
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
-            email: format!("test-{}@example.com", Uuid::new_v4()),
-            password_digest: "digest".to_string(),
-            name: "Test User".to_string(),
-            self_description: "desc".to_string(),
-        };
-        diesel::insert_into(crate::schema::users::table)
-            .values(user.clone())
-            .execute(&mut conn)
-            .await
-            .unwrap();
-
-        let token = model::NewAccessToken {
-            user_id: user.id,
-            created_at: Utc::now().naive_utc(),
-            token: format!("token-{}", Uuid::new_v4()),
-            last_user_ip: "127.0.0.1".to_string(),
-            last_user_agent: "test-suite".to_string(),
-            deleted_at: None,
-        };
-        diesel::insert_into(crate::schema::access_tokens::table)
-            .values(token.clone())
-            .execute(&mut conn)
-            .await
-            .unwrap();
-        (user, token)
-    }
-
     async fn insert_reading(
         state: &crate::state::AppState,
         user_id: Option<Uuid>,
@@ -92,35 +57,17 @@ mod tests {
         interpretation_text: &str,
         interpretation_error: &str,
     ) -> Uuid {
-        use chrono::Utc;
-        let mut conn = state.postgresql_pool.get().await.unwrap();
-        let id = Uuid::new_v4();
         let cards = vec![Card {
             arcana: Arcana::Major { name: Fool },
             flipped: false,
         }];
         let reading = model::Reading {
-            id,
-            created_at: Utc::now().naive_utc(),
-            question: "q?".to_string(),
-            context: String::new(),
-            cards: cards.into(),
-            shuffled_times: 0,
-            user_id: user_id.unwrap_or_else(Uuid::nil),
-            user_name: String::new(),
-            user_self_description: String::new(),
             interpretation_status: status,
             interpretation_text: interpretation_text.to_string(),
             interpretation_error: interpretation_error.to_string(),
-            deleted_at: None,
-            interpretation_done_at: Some(Utc::now().naive_utc()),
+            ..reading_row(user_id.unwrap_or_else(Uuid::nil), cards)
         };
-        diesel::insert_into(crate::schema::readings::table)
-            .values(reading)
-            .execute(&mut conn)
-            .await
-            .unwrap();
-        id
+        insert_reading_row(state, reading).await
     }
 
     // Anonymous user tests
@@ -144,7 +91,7 @@ mod tests {
             .await
             .unwrap();
         let result: GetInterpretationResult = serde_json::from_slice(&body).unwrap();
-        assert_eq!(result.done, false);
+        assert!(!result.done);
         assert!(result.error.is_empty());
         assert!(result.reading.is_some());
         // After assignment, reading.user_id should be anon_id
@@ -177,7 +124,7 @@ mod tests {
             .await
             .unwrap();
         let result: GetInterpretationResult = serde_json::from_slice(&body).unwrap();
-        assert_eq!(result.done, true);
+        assert!(result.done);
         assert_eq!(result.interpretation, expected_text);
         assert!(result.reading.is_some());
         assert_eq!(result.reading.unwrap().user_id, Some(anon_id));
@@ -209,7 +156,7 @@ mod tests {
             .await
             .unwrap();
         let result: GetInterpretationResult = serde_json::from_slice(&body).unwrap();
-        assert_eq!(result.done, true);
+        assert!(result.done);
         assert_eq!(result.error, expected_error);
         assert!(result.reading.is_some());
         assert_eq!(result.reading.unwrap().user_id, Some(anon_id));
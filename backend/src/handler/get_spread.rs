@@ -0,0 +1,95 @@
+use crate::entity::spread::CustomSpread;
+use crate::entity::user::User;
+use crate::error::{AppError, ResponseResult};
+use crate::repository::spread_repository::SpreadRepository;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+/// Anyone may fetch a shared spread; private spreads are only visible to their owner.
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn get_spread(
+    spread_repository: SpreadRepository,
+    user: User,
+    Path(spread_id): Path<String>,
+) -> (StatusCode, ResponseResult<Json<CustomSpread>>) {
+    let Ok(spread_id) = spread_id.parse::<Uuid>() else {
+        return AppError::ValidateError("invalid uuid".into()).into_response();
+    };
+    match spread_repository.find(spread_id).await {
+        Ok(Some(spread)) if spread.is_visible_to(user.id()) => (StatusCode::OK, Ok(Json(spread))),
+        Ok(_) => AppError::NotFound.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::entity::spread::SaveSpreadRequest;
+    use crate::test_helpers::insert_user_with_token;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use webtarot_shared::model::SpreadPosition;
+
+    async fn create_spread(state: &crate::state::AppState, shared: bool) -> CustomSpread {
+        let (user, _token) = insert_user_with_token(state).await;
+        SpreadRepository::from(state.clone())
+            .create(
+                user.id,
+                SaveSpreadRequest {
+                    name: "Mirror".to_string(),
+                    positions: vec![SpreadPosition {
+                        name: "Reflection".to_string(),
+                        description: String::new(),
+                        x: None,
+                        y: None,
+                    }],
+                    shared,
+                },
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn get_as_anon(app: axum::Router, id: Uuid) -> axum::response::Response {
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/api/v1/spreads/{}", id))
+            .header("x-user-uuid", Uuid::new_v4().to_string())
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(req).await.unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_shared_spread_by_link() {
+        let (state, app) = create_test_app().await;
+        let spread = create_spread(&state, true).await;
+        let response = get_as_anon(app, spread.id).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["name"], "Mirror");
+        // The owner is not revealed to whoever has the link
+        assert!(body.get("userId").is_none(), "{}", body);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_private_spread_of_other_user() {
+        let (state, app) = create_test_app().await;
+        let spread = create_spread(&state, false).await;
+        assert_eq!(
+            get_as_anon(app, spread.id).await.status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::repository::interpretation_repository::InterpretationRepository;
+use axum::Json;
+use webtarot_shared::provider::ProviderInfo;
+
+/// The interpretation providers enabled on this server, for the frontend's selector.
+#[tracing::instrument]
+pub async fn list_backends(
+    interpretation_repository: InterpretationRepository,
+) -> Json<Vec<ProviderInfo>> {
+    Json(interpretation_repository.providers())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::StatusCode;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use webtarot_shared::explain::InterpretationBackend;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_list_backends() {
+        let (_state, app) = create_test_app().await;
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/backends")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let backends: Vec<ProviderInfo> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            vec![
+                InterpretationBackend::ChatGPT,
+                InterpretationBackend::Gemini,
+                InterpretationBackend::Claude
+            ],
+            backends.iter().map(|b| b.id.clone()).collect::<Vec<_>>()
+        );
+        assert_eq!("ChatGPT", backends[0].name);
+    }
+}
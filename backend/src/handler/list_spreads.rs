@@ -0,0 +1,97 @@
+use crate::entity::spread::CustomSpread;
+use crate::entity::user::User;
+use crate::error::{AppError, ResponseResult};
+use crate::repository::spread_repository::SpreadRepository;
+use axum::Json;
+use axum::http::StatusCode;
+
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn list_spreads(
+    spread_repository: SpreadRepository,
+    user: User,
+) -> (StatusCode, ResponseResult<Json<Vec<CustomSpread>>>) {
+    let User::Authenticated { id, .. } = user else {
+        return AppError::Forbidden.into_response();
+    };
+    match spread_repository.list_for_user(id).await {
+        Ok(spreads) => (StatusCode::OK, Ok(Json(spreads))),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::entity::spread::SaveSpreadRequest;
+    use crate::test_helpers::insert_user_with_token;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use webtarot_shared::model::SpreadPosition;
+
+    fn list_request(auth: (&str, String)) -> Request<Body> {
+        Request::builder()
+            .method("GET")
+            .uri("/api/v1/spreads")
+            .header(auth.0, auth.1)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_list_spreads_only_own() {
+        let (state, app) = create_test_app().await;
+        let (owner, owner_token) = insert_user_with_token(&state).await;
+        let (other, _token) = insert_user_with_token(&state).await;
+        let repository = SpreadRepository::from(state.clone());
+        for (user_id, name) in [(owner.id, "Mirror"), (other.id, "Crossroads")] {
+            repository
+                .create(
+                    user_id,
+                    SaveSpreadRequest {
+                        name: name.to_string(),
+                        positions: vec![SpreadPosition {
+                            name: "Reflection".to_string(),
+                            description: String::new(),
+                            x: None,
+                            y: None,
+                        }],
+                        // Shared spreads are reachable by link, not listed to others
+                        shared: true,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        let resp = app
+            .oneshot(list_request((
+                "authorization",
+                format!("Bearer {}", owner_token.token),
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let spreads: Vec<CustomSpread> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(spreads.len(), 1);
+        assert_eq!(spreads[0].name, "Mirror");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_list_spreads_anonymous_forbidden() {
+        let (_state, app) = create_test_app().await;
+        let resp = app
+            .oneshot(list_request(("x-user-uuid", Uuid::new_v4().to_string())))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -7,13 +7,42 @@ use axum::response::IntoResponse;
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast::error::RecvError;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum InterpretationsWebsocketMessage {
-    Subscribe { uuid: Uuid },
-    Done { uuid: Uuid },
+    Subscribe {
+        uuid: Uuid,
+    },
+    /// The next piece of the interpretation's text, while the model writes it.
+    Chunk {
+        uuid: Uuid,
+        text: String,
+    },
+    /// The next piece of the answer to a follow-up question on the reading.
+    #[serde(rename_all = "camelCase")]
+    ReplyChunk {
+        uuid: Uuid,
+        message_id: Uuid,
+        text: String,
+    },
+    /// The interpretation is done, or a follow-up question on it was answered.
+    Done {
+        uuid: Uuid,
+    },
+}
+
+impl InterpretationsWebsocketMessage {
+    fn uuid(&self) -> Uuid {
+        match self {
+            Self::Subscribe { uuid }
+            | Self::Chunk { uuid, .. }
+            | Self::ReplyChunk { uuid, .. }
+            | Self::Done { uuid } => *uuid,
+        }
+    }
 }
 
 #[tracing::instrument(skip(user, ws), fields(user_id = %user.id().to_string()))]
@@ -33,27 +62,58 @@ async fn notify_websocket(
 ) {
     let (mut sender, mut receiver) = stream.split();
     let mut rx = interpretation_repository.subscribe();
+    let mut message_rx = interpretation_repository.subscribe_messages();
     let uuids_send: Arc<RwLock<Vec<Uuid>>> = Arc::new(RwLock::new(Vec::new()));
     let uuids_recv = uuids_send.clone();
 
+    let repository = interpretation_repository.clone();
     let mut send_task = tokio::spawn(async move {
-        while let Ok(interpretation) = rx.recv().await {
-            let message = InterpretationsWebsocketMessage::Done {
-                uuid: interpretation.reading().id,
+        'receive: loop {
+            let messages = tokio::select! {
+                interpretation = rx.recv() => match interpretation {
+                    Ok(interpretation) => {
+                        let uuid = interpretation.reading().id;
+                        match interpretation {
+                            Interpretation::Pending(_) => continue,
+                            Interpretation::Streaming(_, text) => {
+                                vec![InterpretationsWebsocketMessage::Chunk { uuid, text }]
+                            }
+                            Interpretation::Done(..) | Interpretation::Failed(..) => {
+                                vec![InterpretationsWebsocketMessage::Done { uuid }]
+                            }
+                        }
+                    }
+                    // Missed pieces are in the text the client fetches when it is done, but the
+                    // done event may be among them too: look up what finished in the meantime
+                    Err(RecvError::Lagged(_)) => {
+                        let uuids = uuids_send.read().unwrap().clone();
+                        finished(&repository, uuids).await
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                chunk = message_rx.recv() => match chunk {
+                    Ok(chunk) => vec![InterpretationsWebsocketMessage::ReplyChunk {
+                        uuid: chunk.reading_id,
+                        message_id: chunk.message_id,
+                        text: chunk.text,
+                    }],
+                    // The answer is done once stored, which is announced on the other channel
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                },
             };
-            if !uuids_send
-                .read()
-                .unwrap()
-                .contains(&interpretation.reading().id)
-            {
-                continue;
-            }
-            tracing::debug!(message = ?message, "sending websocket message");
-            let result = sender
-                .send(Message::text(serde_json::to_string(&message).unwrap()))
-                .await;
-            if result.is_err() {
-                break;
+            for message in messages {
+                let uuid = message.uuid();
+                if !uuids_send.read().unwrap().contains(&uuid) {
+                    continue;
+                }
+                tracing::debug!(message = ?message, "sending websocket message");
+                let result = sender
+                    .send(Message::text(serde_json::to_string(&message).unwrap()))
+                    .await;
+                if result.is_err() {
+                    break 'receive;
+                }
             }
         }
     });
@@ -67,9 +127,12 @@ async fn notify_websocket(
                     interpretation_repository.get_interpretation(uuid).await
                 && interpretation.reading().user_id == Some(user.id())
             {
-                if let Interpretation::Pending(_) = interpretation {
+                // Interpreted readings stay subscribed for the answers to follow-up questions
+                if !uuids_recv.read().unwrap().contains(&uuid) {
                     tracing::debug!(uuid = ?uuid, "adding uuid to send queue");
                     uuids_recv.write().unwrap().push(uuid);
+                }
+                if let Interpretation::Pending(_) = interpretation {
                     continue;
                 }
                 tracing::debug!(uuid = ?uuid, "renotifying");
@@ -83,3 +146,19 @@ async fn notify_websocket(
         _ = &mut recv_task => send_task.abort(),
     };
 }
+
+/// A `Done` message for each of the readings that is no longer being interpreted.
+async fn finished(
+    interpretation_repository: &InterpretationRepository,
+    uuids: Vec<Uuid>,
+) -> Vec<InterpretationsWebsocketMessage> {
+    let mut messages = Vec::new();
+    for uuid in uuids {
+        if let Some(Interpretation::Done(..) | Interpretation::Failed(..)) =
+            interpretation_repository.get_interpretation(uuid).await
+        {
+            messages.push(InterpretationsWebsocketMessage::Done { uuid });
+        }
+    }
+    messages
+}
@@ -0,0 +1,114 @@
+use crate::entity::card::{ParseCardsRequest, ParseCardsResponse};
+use crate::error::ResponseResult;
+use axum::Json;
+use axum::http::StatusCode;
+
+#[tracing::instrument]
+pub async fn parse_cards(
+    Json(parse_cards_request): Json<ParseCardsRequest>,
+) -> (StatusCode, ResponseResult<Json<ParseCardsResponse>>) {
+    match parse_cards_request.parse() {
+        Ok(response) => (StatusCode::OK, Ok(Json(response))),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use serde_json::json;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use webtarot_shared::model::{Arcana, Card, MajorArcana, Rank, Suit};
+
+    fn request(body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/api/v1/cards/parse")
+            .header("Content-Type", "application/json")
+            .header("x-locale", "en")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_parse_cards() {
+        let (_state, app) = create_test_app().await;
+
+        let response = app
+            .clone()
+            .oneshot(request(json!({ "cards": ["Ás de Copas", "XVI", "10S-R"] })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: ParseCardsResponse = serde_json::from_slice(&body).unwrap();
+        let cards: Vec<Card> = response.cards.iter().map(|parsed| parsed.card).collect();
+        assert_eq!(
+            vec![
+                Card {
+                    arcana: Arcana::Minor {
+                        rank: Rank::Ace,
+                        suit: Suit::Cups
+                    },
+                    flipped: false
+                },
+                Card {
+                    arcana: Arcana::Major {
+                        name: MajorArcana::Tower
+                    },
+                    flipped: false
+                },
+                Card {
+                    arcana: Arcana::Minor {
+                        rank: Rank::Ten,
+                        suit: Suit::Swords
+                    },
+                    flipped: true
+                },
+            ],
+            cards
+        );
+        assert_eq!("XVI", response.cards[1].input);
+        assert_eq!("The Tower", response.cards[1].name);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                json!({ "cards": ["VIII"], "tradition": "marseille" }),
+            ))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: ParseCardsResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            Arcana::Major {
+                name: MajorArcana::Justice
+            },
+            response.cards[0].card.arcana
+        );
+
+        let response = app
+            .clone()
+            .oneshot(request(json!({ "cards": ["AC", "Eleven of Cups"] })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .oneshot(request(
+                json!({ "cards": ["The Ship reversed"], "tradition": "lenormand" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::entity::reading::ReplayReadingResponse;
+use crate::entity::user::User;
+use crate::error::{AppError, AppResult};
+use crate::repository::interpretation_repository::InterpretationRepository;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn replay_interpretation(
+    interpretation_repository: InterpretationRepository,
+    user: User,
+    Path(interpretation_id): Path<String>,
+) -> (StatusCode, AppResult<Json<ReplayReadingResponse>>) {
+    let Ok(uuid) = interpretation_id.parse() else {
+        return AppError::ValidateError("invalid uuid".into()).into_response();
+    };
+    let Some(interpretation) = interpretation_repository.get_interpretation(uuid).await else {
+        return AppError::NotFound.into_response();
+    };
+    // Readings shuffled every pass take thousands of passes to replay
+    let reading = interpretation.into_reading();
+    let replay =
+        match tokio::task::spawn_blocking(move || ReplayReadingResponse::replay(&reading)).await {
+            Ok(replay) => replay,
+            Err(e) => {
+                tracing::error!("replay failed: {:?}", e);
+                return AppError::InternalError.into_response();
+            }
+        };
+    let Some(replay) = replay else {
+        return AppError::ValidateError(t!("errors.reading_not_seeded").to_string())
+            .into_response();
+    };
+    (StatusCode::OK, Ok(Json(replay)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::create_test_app;
+    use crate::entity::reading::{CreateReadingResponse, ReplayReadingResponse};
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::StatusCode;
+    use serde_json::json;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_replay_reproduces_reading() {
+        let (_state, app) = create_test_app().await;
+        let user_uuid = Uuid::new_v4().to_string();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/reading")
+                    .header("Content-Type", "application/json")
+                    .header("x-user-uuid", &user_uuid)
+                    .body(Body::from(
+                        json!({
+                            "question": "Will it replay?",
+                            "cards": 5,
+                            "context": "",
+                            "backend": "chatGPT"
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let reading: CreateReadingResponse = serde_json::from_slice(&body).unwrap();
+        assert!(reading.seed.is_some());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/api/v1/interpretation/{}/replay",
+                        reading.interpretation_id
+                    ))
+                    .header("x-user-uuid", &user_uuid)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let replay: ReplayReadingResponse = serde_json::from_slice(&body).unwrap();
+        assert!(replay.matches);
+        assert_eq!(reading.seed, Some(replay.seed));
+        assert_eq!(reading.shuffled_times, replay.shuffled_times);
+        assert_eq!(reading.cards, replay.cards);
+        assert_eq!(78, replay.deck.len());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_replay_reproduces_readings_shuffled_every_pass() {
+        use crate::model;
+        use crate::test_helpers::{insert_reading_row, reading_row};
+        use webtarot_shared::model::{Deck, DeckSeed, ShuffleMethod};
+
+        let (state, app) = create_test_app().await;
+        let user = Uuid::new_v4();
+        let seed = DeckSeed([7; 32]);
+        let drawn = Deck::build()
+            .with_shuffle(ShuffleMethod::EveryPass)
+            .seeded_reading("Will it replay?", &seed, 3);
+        let id = insert_reading_row(
+            &state,
+            model::Reading {
+                question: "Will it replay?".to_string(),
+                shuffled_times: drawn.shuffled_times as i32,
+                interpretation_status: model::InterpretationStatus::Pending,
+                interpretation_done_at: None,
+                seed: Some(seed.to_string()),
+                shuffle: "everyPass".to_string(),
+                ..reading_row(user, drawn.cards.clone())
+            },
+        )
+        .await;
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/v1/interpretation/{}/replay", id))
+                    .header("x-user-uuid", user.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let replay: ReplayReadingResponse = serde_json::from_slice(&body).unwrap();
+        assert!(replay.matches);
+        assert_eq!(drawn.cards, replay.cards);
+    }
+}
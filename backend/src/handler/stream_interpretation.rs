@@ -0,0 +1,280 @@
+use crate::entity::interpretation::{GetInterpretationResult, Interpretation};
+use crate::entity::user::User;
+use crate::error::{AppError, AppResult};
+use crate::repository::interpretation_repository::InterpretationRepository;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, KeepAliveStream, Sse};
+use futures_util::StreamExt;
+use futures_util::stream::{self, BoxStream};
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+
+type EventStream = BoxStream<'static, Result<Event, Infallible>>;
+
+/// Server-sent events for clients that cannot hold a websocket: a `chunk` event for each piece
+/// of the interpretation as the model writes it, then a single `done` event carrying the
+/// interpretation like `GET /api/v1/interpretation/{id}` does. Pieces written before the client
+/// connected are only in the `done` event.
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn stream_interpretation(
+    interpretation_repository: InterpretationRepository,
+    user: User,
+    Path(interpretation_id): Path<String>,
+) -> (StatusCode, AppResult<Sse<KeepAliveStream<EventStream>>>) {
+    let Ok(uuid) = interpretation_id.parse() else {
+        return AppError::ValidateError("invalid uuid".into()).into_response();
+    };
+    // Subscribe before looking the reading up, so it cannot finish unnoticed in between
+    let rx = interpretation_repository.subscribe();
+    let Some(interpretation) = interpretation_repository.get_interpretation(uuid).await else {
+        return AppError::NotFound.into_response();
+    };
+    if interpretation.reading().user_id != Some(user.id()) {
+        return AppError::NotFound.into_response();
+    }
+
+    let events: EventStream = match interpretation {
+        Interpretation::Pending(_) | Interpretation::Streaming(..) => {
+            let repository = interpretation_repository.clone();
+            stream::unfold(Some(rx), move |rx| {
+                let repository = repository.clone();
+                async move {
+                    // The receiver is dropped once the interpretation is done
+                    let mut rx = rx?;
+                    loop {
+                        match rx.recv().await {
+                            Ok(Interpretation::Streaming(reading, text)) if reading.id == uuid => {
+                                return Some((
+                                    Event::default().event("chunk").data(text),
+                                    Some(rx),
+                                ));
+                            }
+                            Ok(done @ (Interpretation::Done(..) | Interpretation::Failed(..)))
+                                if done.reading().id == uuid =>
+                            {
+                                return Some((done_event(done), None));
+                            }
+                            // The done event may be among the missed ones
+                            Err(RecvError::Lagged(_)) => {
+                                if let Some(
+                                    done @ (Interpretation::Done(..) | Interpretation::Failed(..)),
+                                ) = repository.get_interpretation(uuid).await
+                                {
+                                    return Some((done_event(done), None));
+                                }
+                            }
+                            Ok(_) => continue,
+                            Err(RecvError::Closed) => return None,
+                        }
+                    }
+                }
+            })
+            .map(Ok)
+            .boxed()
+        }
+        done => stream::once(async move { Ok(done_event(done)) }).boxed(),
+    };
+    (
+        StatusCode::OK,
+        Ok(Sse::new(events).keep_alive(KeepAlive::default())),
+    )
+}
+
+fn done_event(interpretation: Interpretation) -> Event {
+    Event::default()
+        .event("done")
+        .json_data(GetInterpretationResult::from(interpretation))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::model;
+    use crate::test_helpers::{insert_reading_row, reading_row};
+    use axum::body::Body;
+    use axum::extract::Request;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use webtarot_shared::model::MajorArcana::Star;
+    use webtarot_shared::model::{Arcana, Card};
+
+    async fn insert_reading(
+        state: &crate::state::AppState,
+        user_id: Uuid,
+        status: model::InterpretationStatus,
+        interpretation_text: &str,
+    ) -> Uuid {
+        let cards = vec![Card {
+            arcana: Arcana::Major { name: Star },
+            flipped: false,
+        }];
+        let reading = model::Reading {
+            interpretation_status: status,
+            interpretation_text: interpretation_text.to_string(),
+            ..reading_row(user_id, cards)
+        };
+        insert_reading_row(state, reading).await
+    }
+
+    fn stream_request(interpretation_id: Uuid, user_id: Uuid) -> Request<Body> {
+        Request::builder()
+            .method("GET")
+            .uri(format!(
+                "/api/v1/interpretation/{}/stream",
+                interpretation_id
+            ))
+            .header("x-user-uuid", user_id.to_string())
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    async fn body_text(response: axum::response::Response) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_stream_interpretation_done_sends_the_text_at_once() {
+        let (state, app) = create_test_app().await;
+        let user_id = Uuid::new_v4();
+        let interp_id =
+            insert_reading(&state, user_id, model::InterpretationStatus::Done, "Hope.").await;
+
+        let response = app
+            .clone()
+            .oneshot(stream_request(interp_id, user_id))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            "text/event-stream",
+            response.headers()["content-type"].to_str().unwrap()
+        );
+        let text = body_text(response).await;
+        assert!(text.starts_with("event: done\n"), "{}", text);
+        assert!(text.contains(r#""interpretation":"Hope.""#), "{}", text);
+
+        // Only the owner may follow a reading
+        let response = app
+            .oneshot(stream_request(interp_id, Uuid::new_v4()))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_stream_interpretation_forwards_chunks_until_done() {
+        let (state, app) = create_test_app().await;
+        let user_id = Uuid::new_v4();
+        let interp_id =
+            insert_reading(&state, user_id, model::InterpretationStatus::Pending, "").await;
+        let response = app
+            .oneshot(stream_request(interp_id, user_id))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let repository = InterpretationRepository::from(state.clone());
+        let Some(Interpretation::Pending(reading)) = repository.get_interpretation(interp_id).await
+        else {
+            panic!("the reading should be pending");
+        };
+        let mut other = reading.clone();
+        other.id = Uuid::new_v4();
+        let broadcast = &state.interpretation_broadcast;
+        broadcast
+            .send(Interpretation::Streaming(
+                reading.clone(),
+                "The Star\n".into(),
+            ))
+            .unwrap();
+        broadcast
+            .send(Interpretation::Streaming(other, "not ours".into()))
+            .unwrap();
+        broadcast
+            .send(Interpretation::Streaming(reading.clone(), "heals.".into()))
+            .unwrap();
+        broadcast
+            .send(Interpretation::Done(
+                reading,
+                "The Star\nheals.".into(),
+                chrono::Utc::now().naive_utc(),
+            ))
+            .unwrap();
+
+        let text = body_text(response).await;
+        let events = text
+            .split("\n\n")
+            .filter(|event| !event.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(3, events.len(), "{}", text);
+        assert_eq!("event: chunk\ndata: The Star\ndata: ", events[0]);
+        assert_eq!("event: chunk\ndata: heals.", events[1]);
+        assert!(events[2].starts_with("event: done\n"), "{}", text);
+        assert!(events[2].contains(r#""done":true"#), "{}", text);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_stream_interpretation_recovers_a_done_event_lost_to_lag() {
+        use diesel::{ExpressionMethods, QueryDsl};
+        use diesel_async::RunQueryDsl;
+
+        let (state, app) = create_test_app().await;
+        let user_id = Uuid::new_v4();
+        let interp_id =
+            insert_reading(&state, user_id, model::InterpretationStatus::Pending, "").await;
+        let response = app
+            .oneshot(stream_request(interp_id, user_id))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let repository = InterpretationRepository::from(state.clone());
+        let Some(Interpretation::Pending(reading)) = repository.get_interpretation(interp_id).await
+        else {
+            panic!("the reading should be pending");
+        };
+        let mut conn = state.postgresql_pool.get().await.unwrap();
+        diesel::update(crate::schema::readings::table.find(interp_id))
+            .set((
+                crate::schema::readings::interpretation_status
+                    .eq(model::InterpretationStatus::Done),
+                crate::schema::readings::interpretation_text.eq("Hope."),
+            ))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        // The done event is pushed out of the channel before the stream reads it
+        let broadcast = &state.interpretation_broadcast;
+        broadcast
+            .send(Interpretation::Done(
+                reading.clone(),
+                "Hope.".into(),
+                chrono::Utc::now().naive_utc(),
+            ))
+            .unwrap();
+        let mut other = reading;
+        other.id = Uuid::new_v4();
+        for _ in 0..1100 {
+            broadcast
+                .send(Interpretation::Streaming(other.clone(), "not ours".into()))
+                .unwrap();
+        }
+
+        let text = tokio::time::timeout(std::time::Duration::from_secs(10), body_text(response))
+            .await
+            .expect("the stream should end");
+        assert!(text.starts_with("event: done\n"), "{}", text);
+        assert!(text.contains(r#""interpretation":"Hope.""#), "{}", text);
+    }
+}
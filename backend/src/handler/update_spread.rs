@@ -0,0 +1,119 @@
+use crate::entity::spread::{CustomSpread, SaveSpreadRequest};
+use crate::entity::user::User;
+use crate::error::{AppError, ResponseResult};
+use crate::repository::spread_repository::SpreadRepository;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+#[tracing::instrument(skip(user), fields(user_id = %user.id().to_string()))]
+pub async fn update_spread(
+    spread_repository: SpreadRepository,
+    user: User,
+    Path(spread_id): Path<String>,
+    Json(request): Json<SaveSpreadRequest>,
+) -> (StatusCode, ResponseResult<Json<CustomSpread>>) {
+    let User::Authenticated { id, .. } = user else {
+        return AppError::Forbidden.into_response();
+    };
+    let Ok(spread_id) = spread_id.parse::<Uuid>() else {
+        return AppError::ValidateError("invalid uuid".into()).into_response();
+    };
+    match spread_repository.update(spread_id, id, request).await {
+        Ok(spread) => (StatusCode::OK, Ok(Json(spread))),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::create_test_app;
+    use crate::test_helpers::insert_user_with_token;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use webtarot_shared::model::SpreadPosition;
+
+    fn save_request(name: &str) -> SaveSpreadRequest {
+        SaveSpreadRequest {
+            name: name.to_string(),
+            positions: vec![SpreadPosition {
+                name: "Reflection".to_string(),
+                description: String::new(),
+                x: None,
+                y: None,
+            }],
+            shared: false,
+        }
+    }
+
+    fn update_request(id: Uuid, body: &SaveSpreadRequest, auth: (&str, String)) -> Request<Body> {
+        Request::builder()
+            .method("PATCH")
+            .uri(format!("/api/v1/spreads/{}", id))
+            .header("Content-Type", "application/json")
+            .header(auth.0, auth.1)
+            .body(Body::from(serde_json::to_string(body).unwrap()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_update_spread_owner_only() {
+        let (state, app) = create_test_app().await;
+        let (owner, owner_token) = insert_user_with_token(&state).await;
+        let (_other, other_token) = insert_user_with_token(&state).await;
+        let repository = SpreadRepository::from(state.clone());
+        let spread = repository
+            .create(owner.id, save_request("Mirror"))
+            .await
+            .unwrap();
+
+        let resp = app
+            .clone()
+            .oneshot(update_request(
+                spread.id,
+                &save_request("Stolen"),
+                ("authorization", format!("Bearer {}", other_token.token)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = app
+            .oneshot(update_request(
+                spread.id,
+                &save_request("Looking glass"),
+                ("authorization", format!("Bearer {}", owner_token.token)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let stored = repository.find(spread.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, "Looking glass");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_update_spread_anonymous_forbidden() {
+        let (state, app) = create_test_app().await;
+        let (owner, _token) = insert_user_with_token(&state).await;
+        let spread = SpreadRepository::from(state.clone())
+            .create(owner.id, save_request("Mirror"))
+            .await
+            .unwrap();
+
+        let resp = app
+            .oneshot(update_request(
+                spread.id,
+                &save_request("Stolen"),
+                ("x-user-uuid", Uuid::new_v4().to_string()),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
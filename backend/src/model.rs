@@ -7,7 +7,10 @@ use diesel::{AsChangeset, AsExpression, FromSqlRow, Insertable, Queryable, Selec
 use serde::{Deserialize, Serialize};
 use std::io::Write;
 use uuid::Uuid;
-use webtarot_shared::model::Card;
+use webtarot_shared::entropy::Entropy;
+use webtarot_shared::fair::FairDraw;
+use webtarot_shared::model::{Card, DeckOptions, Spread, SpreadPosition};
+use webtarot_shared::structured::StructuredInterpretation;
 
 #[derive(Debug, Clone, Insertable, Queryable, Selectable, AsChangeset)]
 #[diesel(table_name = crate::schema::readings)]
@@ -27,42 +30,106 @@ pub struct Reading {
     pub interpretation_error: String,
     pub deleted_at: Option<NaiveDateTime>,
     pub interpretation_done_at: Option<NaiveDateTime>,
+    pub spread: Option<ReadingSpread>,
+    /// Hex-encoded [`webtarot_shared::model::DeckSeed`] the reading was drawn with.
+    pub seed: Option<String>,
+    pub fair_draw: Option<ReadingFairDraw>,
+    /// [`webtarot_shared::model::DeckTradition`] id.
+    pub tradition: String,
+    pub deck_options: Option<ReadingDeckOptions>,
+    /// Reading whose position `clarified_position` this reading's cards clarify.
+    pub clarified_reading_id: Option<Uuid>,
+    pub clarified_position: Option<i32>,
+    /// Cards that jumped out of the deck while shuffling, apart from the drawn `cards`.
+    pub jumpers: Option<Cards>,
+    /// [`webtarot_shared::entropy::Entropy`] the seed was obtained from.
+    pub entropy: Option<ReadingEntropy>,
+    /// [`webtarot_shared::model::ShuffleMethod`] id.
+    pub shuffle: String,
+    /// [`webtarot_shared::explain::InterpretationBackend`] id; ChatGPT when absent.
+    pub backend: Option<String>,
+    /// Model the reading was interpreted with, as the provider names it.
+    pub model: Option<String>,
+    /// Calls made to providers for the interpretation, retries and failovers included.
+    pub interpretation_attempts: Option<i32>,
+    /// Tokens of the prompt and of the answer, as the provider that answered counted them.
+    pub prompt_tokens: Option<i32>,
+    pub completion_tokens: Option<i32>,
+    /// Estimated cost of the answer in US dollars, from the configured model prices.
+    pub cost_usd: Option<f64>,
+    /// Name and version of the [`webtarot_shared::template::PromptTemplate`] the reading was
+    /// interpreted with.
+    pub prompt_template: Option<String>,
+    pub prompt_template_version: Option<i32>,
+    /// Sections of a structured interpretation, beside its Markdown in `interpretation_text`.
+    pub interpretation_structured: Option<ReadingStructuredInterpretation>,
+    /// [`webtarot_shared::style::ReadingStyle`] id.
+    pub style: String,
+    /// [`webtarot_shared::style::ReadingLength`] id.
+    pub length: String,
 }
 
-#[derive(Debug, Clone, FromSqlRow, Serialize, Deserialize, AsExpression)]
-#[diesel(sql_type = Jsonb)]
-pub struct Cards(Vec<Card>);
-
-impl From<Vec<Card>> for Cards {
-    fn from(cards: Vec<Card>) -> Self {
-        Self(cards)
-    }
+#[derive(Debug, Clone, Insertable, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::reading_messages)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ReadingMessage {
+    pub id: Uuid,
+    pub reading_id: Uuid,
+    pub created_at: NaiveDateTime,
+    /// `user` for a question, `assistant` for an answer.
+    pub role: String,
+    pub content: String,
+    /// Why the answer could not be written; its content is then empty.
+    pub error: Option<String>,
 }
 
-impl From<Cards> for Vec<Card> {
-    fn from(cards: Cards) -> Self {
-        cards.0
-    }
-}
+/// Declares a newtype around a serde type that is stored in a JSONB column.
+macro_rules! jsonb_newtype {
+    ($name:ident($inner:ty)) => {
+        #[derive(Debug, Clone, FromSqlRow, Serialize, Deserialize, AsExpression)]
+        #[diesel(sql_type = Jsonb)]
+        pub struct $name($inner);
 
-impl FromSql<Jsonb, Pg> for Cards {
-    fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
-        let value = <serde_json::Value as FromSql<Jsonb, Pg>>::from_sql(bytes)?;
-        Ok(serde_json::from_value(value)?)
-    }
-}
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
 
-impl ToSql<Jsonb, Pg> for Cards {
-    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> diesel::serialize::Result {
-        // JSONB binary format: 1 byte version (currently 1), followed by JSON payload bytes.
-        let bytes = serde_json::to_vec(&self.0)
-            .map_err(Box::<dyn std::error::Error + Send + Sync>::from)?;
-        out.write_all(&[1])?; // jsonb version
-        out.write_all(&bytes)?;
-        Ok(IsNull::No)
-    }
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl FromSql<Jsonb, Pg> for $name {
+            fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+                let value = <serde_json::Value as FromSql<Jsonb, Pg>>::from_sql(bytes)?;
+                Ok(serde_json::from_value(value)?)
+            }
+        }
+
+        impl ToSql<Jsonb, Pg> for $name {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> diesel::serialize::Result {
+                // JSONB binary format: 1 byte version (currently 1), followed by JSON payload bytes.
+                let bytes = serde_json::to_vec(&self.0)
+                    .map_err(Box::<dyn std::error::Error + Send + Sync>::from)?;
+                out.write_all(&[1])?; // jsonb version
+                out.write_all(&bytes)?;
+                Ok(IsNull::No)
+            }
+        }
+    };
 }
 
+jsonb_newtype!(Cards(Vec<Card>));
+jsonb_newtype!(ReadingSpread(Spread));
+jsonb_newtype!(ReadingFairDraw(FairDraw));
+jsonb_newtype!(ReadingDeckOptions(DeckOptions));
+jsonb_newtype!(ReadingEntropy(Entropy));
+jsonb_newtype!(SpreadPositions(Vec<SpreadPosition>));
+jsonb_newtype!(ReadingStructuredInterpretation(StructuredInterpretation));
+
 #[derive(Debug, Clone, FromSqlRow, Serialize, Deserialize, AsExpression)]
 #[diesel(sql_type = Text)]
 pub enum InterpretationStatus {
@@ -110,6 +177,10 @@ pub struct User {
     pub password_digest: String,
     pub name: String,
     pub self_description: String,
+    /// Default [`webtarot_shared::style::ReadingStyle`] id of the user's readings.
+    pub reading_style: String,
+    /// Default [`webtarot_shared::style::ReadingLength`] id of the user's readings.
+    pub reading_length: String,
 }
 
 #[derive(Debug, Clone, AsChangeset)]
@@ -118,6 +189,9 @@ pub struct UpdateUserFields {
     pub name: String,
     pub self_description: String,
     pub email: String,
+    /// Left as they are when `None`.
+    pub reading_style: Option<String>,
+    pub reading_length: Option<String>,
 }
 
 #[derive(Debug, Clone, Queryable, Selectable, AsChangeset)]
@@ -142,3 +216,25 @@ pub struct NewAccessToken {
     pub last_user_agent: String,
     pub deleted_at: Option<NaiveDateTime>,
 }
+
+#[derive(Debug, Clone, Insertable, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::spreads)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CustomSpread {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub name: String,
+    pub positions: SpreadPositions,
+    pub shared: bool,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, AsChangeset)]
+#[diesel(table_name = crate::schema::spreads)]
+pub struct UpdateSpreadFields {
+    pub name: String,
+    pub positions: SpreadPositions,
+    pub shared: bool,
+}
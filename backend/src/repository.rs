@@ -1,2 +1,5 @@
+pub mod commitment_repository;
+pub mod draw_session_repository;
 pub mod interpretation_repository;
+pub mod spread_repository;
 pub mod user_repository;
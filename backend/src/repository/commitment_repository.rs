@@ -0,0 +1,73 @@
+use crate::error::AppResult;
+use crate::state::AppState;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use std::convert::Infallible;
+use std::fmt::{Debug, Formatter};
+use webtarot_shared::fair;
+use webtarot_shared::model::DeckSeed;
+
+/// How long an issued commitment can be redeemed for a reading.
+pub const COMMITMENT_TTL_SECONDS: u64 = 60 * 60;
+
+/// Keeps the secret server seeds of issued commitments until they are used by a reading.
+#[derive(Clone)]
+pub struct CommitmentRepository {
+    redis: ConnectionManager,
+}
+
+impl Debug for CommitmentRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CommitmentRepository {{ }}")
+    }
+}
+
+impl From<AppState> for CommitmentRepository {
+    fn from(state: AppState) -> Self {
+        Self {
+            redis: state.redis_connection_manager,
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for CommitmentRepository {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        _parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(state.clone().into())
+    }
+}
+
+impl CommitmentRepository {
+    fn key(commitment: &str) -> String {
+        format!("commitment:{}", commitment.to_ascii_lowercase())
+    }
+
+    /// Picks a new server seed and returns its public commitment.
+    pub async fn create(&self) -> AppResult<String> {
+        let server_seed = DeckSeed::random();
+        let commitment = fair::commit(&server_seed);
+        let mut redis = self.redis.clone();
+        let _: () = redis
+            .set_ex(
+                Self::key(&commitment),
+                server_seed.to_string(),
+                COMMITMENT_TTL_SECONDS,
+            )
+            .await?;
+        Ok(commitment)
+    }
+
+    /// Returns the server seed behind `commitment` and forgets it, so each commitment backs
+    /// at most one reading.
+    pub async fn take(&self, commitment: &str) -> AppResult<Option<DeckSeed>> {
+        let mut redis = self.redis.clone();
+        let server_seed: Option<String> = redis.get_del(Self::key(commitment)).await?;
+        Ok(server_seed.and_then(|seed| seed.parse().ok()))
+    }
+}
@@ -0,0 +1,74 @@
+use crate::entity::draw_session::DrawSession;
+use crate::error::{AppError, AppResult};
+use crate::state::AppState;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use std::convert::Infallible;
+use std::fmt::{Debug, Formatter};
+use uuid::Uuid;
+
+/// How long a shuffled deck waits for the querent to pick the cards.
+pub const DRAW_SESSION_TTL_SECONDS: u64 = 30 * 60;
+
+/// Keeps shuffled decks of interactive draws until they are finalized into readings.
+#[derive(Clone)]
+pub struct DrawSessionRepository {
+    redis: ConnectionManager,
+}
+
+impl Debug for DrawSessionRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DrawSessionRepository {{ }}")
+    }
+}
+
+impl From<AppState> for DrawSessionRepository {
+    fn from(state: AppState) -> Self {
+        Self {
+            redis: state.redis_connection_manager,
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for DrawSessionRepository {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        _parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(state.clone().into())
+    }
+}
+
+impl DrawSessionRepository {
+    fn key(id: Uuid) -> String {
+        format!("draw-session:{}", id)
+    }
+
+    pub async fn create(&self, session: &DrawSession) -> AppResult<()> {
+        let value = serde_json::to_string(session)
+            .map_err(|e| AppError::internal_with_log("Failed to serialize draw session", e))?;
+        let mut redis = self.redis.clone();
+        let _: () = redis
+            .set_ex(Self::key(session.id), value, DRAW_SESSION_TTL_SECONDS)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find(&self, id: Uuid) -> AppResult<Option<DrawSession>> {
+        let mut redis = self.redis.clone();
+        let value: Option<String> = redis.get(Self::key(id)).await?;
+        Ok(value.and_then(|value| serde_json::from_str(&value).ok()))
+    }
+
+    /// Removes the session; returns false when it was already gone, so that a session is
+    /// finalized at most once.
+    pub async fn remove(&self, id: Uuid) -> AppResult<bool> {
+        let mut redis = self.redis.clone();
+        let removed: usize = redis.del(Self::key(id)).await?;
+        Ok(removed > 0)
+    }
+}
@@ -2,8 +2,9 @@ use crate::database::DbPool;
 use crate::entity::interpretation;
 use crate::entity::interpretation::Interpretation;
 use crate::entity::reading::Reading;
+use crate::entity::reading_message::{MessageChunk, ReadingMessage};
 use crate::entity::user::User;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
 use crate::middleware::locale::Locale;
 use crate::state::AppState;
 use axum::extract::FromRequestParts;
@@ -14,17 +15,33 @@ use diesel::{
 };
 use diesel_async::RunQueryDsl;
 use metrics::{counter, histogram};
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use rust_i18n::t;
 use std::convert::Infallible;
 use std::fmt::{Debug, Formatter};
 use std::time::Instant;
 use uuid::Uuid;
-use webtarot_shared::explain::{InterpretationBackend, InterpretationService};
+use webtarot_shared::explain::{
+    Clarification, ExplainError, ExplainRequest, InterpretationBackend, InterpretationService,
+};
+use webtarot_shared::pricing::PriceTable;
+use webtarot_shared::provider::{ProviderInfo, Role, Turn};
+use webtarot_shared::style::{ReadingLength, ReadingStyle};
+
+/// How long a day's spend is kept after the day starts; only today's is read.
+const SPEND_TTL_SECONDS: i64 = 2 * 24 * 60 * 60;
 
 #[derive(Clone)]
 pub struct InterpretationRepository {
     broadcast: tokio::sync::broadcast::Sender<Interpretation>,
+    message_broadcast: tokio::sync::broadcast::Sender<MessageChunk>,
     db_pool: DbPool,
+    redis: ConnectionManager,
     interpretation_service: InterpretationService,
+    prices: PriceTable,
+    daily_budget: Option<f64>,
+    structured: bool,
 }
 
 impl Debug for InterpretationRepository {
@@ -48,26 +65,75 @@ impl From<AppState> for InterpretationRepository {
     fn from(value: AppState) -> Self {
         Self {
             broadcast: value.interpretation_broadcast,
+            message_broadcast: value.message_broadcast,
             db_pool: value.postgresql_pool,
-            interpretation_service: InterpretationService::new(
-                value.env.openai_api_key.clone(),
-                value.env.google_api_key.clone(),
-            ),
+            redis: value.redis_connection_manager,
+            interpretation_service: value.interpretation_service,
+            prices: value.env.interpretation_prices,
+            daily_budget: value.env.interpretation_daily_budget,
+            structured: value.env.structured_interpretations,
         }
     }
 }
 
 impl InterpretationRepository {
+    /// The interpretation providers enabled on this server.
+    pub fn providers(&self) -> Vec<ProviderInfo> {
+        self.interpretation_service.providers().list()
+    }
+
+    /// Rejects a `model` that is not on `backend`'s allow-list. A backend that is not enabled
+    /// is reported by the failed interpretation instead.
+    pub fn check_model(
+        &self,
+        backend: &InterpretationBackend,
+        model: Option<&str>,
+    ) -> AppResult<()> {
+        match self.interpretation_service.model(backend, model) {
+            Err(e @ ExplainError::ModelNotAllowed { .. }) => Err(AppError::ValidateError(
+                interpretation::localize_explain_error(&e),
+            )),
+            _ => Ok(()),
+        }
+    }
+
     pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<Interpretation> {
         self.broadcast.subscribe()
     }
 
+    pub fn subscribe_messages(&self) -> tokio::sync::broadcast::Receiver<MessageChunk> {
+        self.message_broadcast.subscribe()
+    }
+
     pub async fn renotify(&self, uuid: Uuid) {
         let interpretation = self.get_interpretation(uuid).await.unwrap();
         self.broadcast.send(interpretation).unwrap();
     }
 
-    pub async fn request_interpretation(&self, reading: Reading, locale: Locale, user: User) {
+    pub async fn request_interpretation(&self, mut reading: Reading, locale: Locale, user: User) {
+        // Record the model and prompt template the interpretation comes from, to compare them
+        // later
+        if let Ok(model) = self.interpretation_service.model(
+            reading
+                .backend
+                .as_ref()
+                .unwrap_or(&InterpretationBackend::ChatGPT),
+            reading.model.as_deref(),
+        ) {
+            reading.model = Some(model);
+        }
+        let template = reading.prompt_template.as_ref().map(ToString::to_string);
+        if let Ok(template) = self.interpretation_service.template(template.as_deref()) {
+            // Templates written before styles and lengths existed ignore them: do not record
+            // what the interpretation was not asked for
+            if !template.uses("style") {
+                reading.style = ReadingStyle::default();
+            }
+            if !template.uses("length") {
+                reading.length = ReadingLength::default();
+            }
+            reading.prompt_template = Some(template.id.clone());
+        }
         self.save_as_pending(reading.clone()).await;
         let cloned = self.clone();
 
@@ -88,39 +154,98 @@ impl InterpretationRepository {
             .unwrap();
     }
 
-    async fn start_interpretation_request(&self, reading: Reading, locale: Locale, _user: User) {
+    async fn start_interpretation_request(
+        &self,
+        mut reading: Reading,
+        locale: Locale,
+        _user: User,
+    ) {
         tracing::debug!("start_interpretation_request");
         rust_i18n::set_locale(&locale.0);
+        if self.budget_exceeded().await {
+            counter!("interpretation_budget_refusals").increment(1);
+            let failed = Interpretation::Failed(reading, t!("errors.budget_exceeded").to_string());
+            self.broadcast
+                .send(self.update_interpretation(failed).await)
+                .unwrap();
+            return;
+        }
         let start = Instant::now();
-        let result = self
+        // Subscribers follow the text as it is written; it is stored once, when it is done
+        let on_chunk = {
+            let broadcast = self.broadcast.clone();
+            let reading = reading.clone();
+            move |chunk: &str| {
+                // Nobody listening is not an error
+                let _ = broadcast.send(Interpretation::Streaming(
+                    reading.clone(),
+                    chunk.to_string(),
+                ));
+            }
+        };
+        let requested = reading
+            .backend
+            .clone()
+            .unwrap_or(InterpretationBackend::ChatGPT);
+        let explanation = self
             .interpretation_service
-            .explain(
-                &reading.question,
-                Some(reading.context.clone()).filter(|i| !i.trim().is_empty()),
-                &reading.cards,
-                Some(reading.user_name.clone()).filter(|i| !i.trim().is_empty()),
-                Some(reading.user_self_description.clone()).filter(|i| !i.trim().is_empty()),
-                reading
-                    .backend
-                    .clone()
-                    .unwrap_or(InterpretationBackend::ChatGPT),
+            .interpret(
+                &self.explain_request(&reading).await,
+                requested.clone(),
+                &on_chunk,
             )
             .await;
         let elapsed = start.elapsed();
+        for attempt in &explanation.attempts {
+            let status = if attempt.error.is_none() {
+                "success"
+            } else {
+                "failure"
+            };
+            counter!(
+                "interpretation_attempts",
+                "backend" => attempt.backend.id(),
+                "status" => status
+            )
+            .increment(1);
+        }
+        if explanation.result.is_ok() && explanation.backend != requested {
+            counter!(
+                "interpretation_failovers",
+                "from" => requested.id(),
+                "to" => explanation.backend.id()
+            )
+            .increment(1);
+        }
         let card_count = reading.cards.len().to_string();
         let labels = [
             (
                 "status",
-                (if result.is_ok() { "success" } else { "failure" }).to_owned(),
+                (if explanation.result.is_ok() {
+                    "success"
+                } else {
+                    "failure"
+                })
+                .to_owned(),
             ),
             ("cards", card_count),
+            ("backend", explanation.backend.id().to_owned()),
         ];
         counter!("interpretation_requests", &labels).increment(1);
         histogram!("interpretation_requests_duration_seconds", &labels)
             .record(elapsed.as_secs_f64());
-        tracing::debug!(result = ?result, ?elapsed, "start_interpretation_request result");
-        let result = match result {
-            Ok(result) => Interpretation::Done(reading, result, Utc::now().naive_utc()),
+        tracing::debug!(explanation = ?explanation, ?elapsed, "start_interpretation_request result");
+        reading.attempts = Some(explanation.attempts.len() as u32);
+        let result = match explanation.result {
+            Ok(result) => {
+                // After a failover the reading names the backend that actually answered
+                reading.backend = Some(explanation.backend);
+                reading.model = explanation.model;
+                reading.usage = explanation.usage;
+                reading.structured = explanation.structured;
+                reading.cost = self.record_usage(&reading).await;
+                Interpretation::Done(reading, result, Utc::now().naive_utc())
+            }
             Err(e) => Interpretation::Failed(reading, interpretation::localize_explain_error(&e)),
         };
         self.broadcast
@@ -128,6 +253,77 @@ impl InterpretationRepository {
             .unwrap();
     }
 
+    /// Whether today's interpretations already cost the daily budget. Interpretations under
+    /// way when it is reached still finish, so it may be overrun by their cost.
+    async fn budget_exceeded(&self) -> bool {
+        let Some(budget) = self.daily_budget else {
+            return false;
+        };
+        let mut redis = self.redis.clone();
+        let spent: redis::RedisResult<Option<f64>> = redis.get(Self::spend_key()).await;
+        match spent {
+            Ok(spent) => spent.unwrap_or_default() >= budget,
+            Err(e) => {
+                // Readings go on without a budget rather than stop with Redis
+                tracing::warn!(error = %e, "could not read the interpretation spend");
+                false
+            }
+        }
+    }
+
+    /// Counts the tokens of an interpreted `reading` and adds its cost to today's spend.
+    /// Returns the cost, when its model has a price.
+    async fn record_usage(&self, reading: &Reading) -> Option<f64> {
+        let usage = reading.usage?;
+        let backend = reading
+            .backend
+            .as_ref()
+            .map(InterpretationBackend::id)
+            .unwrap_or_default();
+        let model = reading.model.clone().unwrap_or_default();
+        let labels = [("backend", backend.to_owned()), ("model", model.clone())];
+        counter!("interpretation_prompt_tokens", &labels).increment(usage.prompt_tokens.into());
+        counter!("interpretation_completion_tokens", &labels)
+            .increment(usage.completion_tokens.into());
+        let cost = self.prices.cost(&model, &usage)?;
+        counter!("interpretation_cost_microdollars", &labels)
+            .increment((cost * 1_000_000.0).round() as u64);
+
+        let key = Self::spend_key();
+        let mut redis = self.redis.clone();
+        let recorded: redis::RedisResult<()> = async {
+            let _: f64 = redis.incr(&key, cost).await?;
+            redis.expire(&key, SPEND_TTL_SECONDS).await
+        }
+        .await;
+        if let Err(e) = recorded {
+            tracing::warn!(error = %e, cost, "could not record the interpretation spend");
+        }
+        Some(cost)
+    }
+
+    fn spend_key() -> String {
+        format!("interpretation_spend:{}", Utc::now().format("%Y-%m-%d"))
+    }
+
+    /// The request sent to the model; a clarifier reading also carries the reading it clarifies.
+    async fn explain_request(&self, reading: &Reading) -> ExplainRequest {
+        let mut request = ExplainRequest::from(reading);
+        request.structured = self.structured;
+        if let Some(clarifies) = reading.clarifies
+            && let Some(Interpretation::Done(clarified, text, _)) =
+                self.get_interpretation(clarifies.reading_id).await
+        {
+            request.spread = clarified.spread;
+            request.clarification = Some(Clarification {
+                cards: clarified.cards,
+                position: clarifies.position,
+                interpretation: text,
+            });
+        }
+        request
+    }
+
     pub async fn get_interpretation(&self, uuid: Uuid) -> Option<Interpretation> {
         let mut conn = self.db_pool.get().await.unwrap();
         let interpretation = crate::schema::readings::dsl::readings
@@ -143,6 +339,180 @@ impl InterpretationRepository {
         None
     }
 
+    /// Clarifier readings drawn for `uuid`, oldest first.
+    pub async fn get_clarifiers(&self, uuid: Uuid) -> Vec<Interpretation> {
+        use crate::schema::readings::dsl as r;
+        let mut conn = self.db_pool.get().await.unwrap();
+        r::readings
+            .select(crate::model::Reading::as_select())
+            .filter(r::clarified_reading_id.eq(uuid))
+            .order(r::created_at.asc())
+            .load::<crate::model::Reading>(&mut conn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(Interpretation::from)
+            .collect()
+    }
+
+    /// Follow-up questions on `uuid` and their answers, oldest first.
+    pub async fn get_messages(&self, uuid: Uuid) -> Vec<ReadingMessage> {
+        use crate::schema::reading_messages::dsl as m;
+        let mut conn = self.db_pool.get().await.unwrap();
+        m::reading_messages
+            .select(crate::model::ReadingMessage::as_select())
+            .filter(m::reading_id.eq(uuid))
+            .order(m::created_at.asc())
+            .load::<crate::model::ReadingMessage>(&mut conn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(ReadingMessage::from)
+            .collect()
+    }
+
+    /// Stores a follow-up `question` on an interpreted reading and has the backend that
+    /// interpreted it answer, after the interpretation and the `earlier` messages.
+    pub async fn ask(
+        &self,
+        reading: Reading,
+        interpretation: String,
+        earlier: Vec<ReadingMessage>,
+        question: String,
+        locale: Locale,
+    ) -> ReadingMessage {
+        let question = ReadingMessage::new(reading.id, Role::User, question, None);
+        self.save_message(question.clone()).await;
+        let cloned = self.clone();
+        let asked = question.clone();
+
+        tokio::spawn(async move {
+            cloned
+                .answer(reading, interpretation, earlier, asked, locale)
+                .await;
+        });
+        question
+    }
+
+    async fn answer(
+        &self,
+        reading: Reading,
+        interpretation: String,
+        earlier: Vec<ReadingMessage>,
+        question: ReadingMessage,
+        locale: Locale,
+    ) {
+        rust_i18n::set_locale(&locale.0);
+        let answer_id = Uuid::new_v4();
+        let answered = |content: String, error: Option<String>| ReadingMessage {
+            id: answer_id,
+            ..ReadingMessage::new(reading.id, Role::Assistant, content, error)
+        };
+        if self.budget_exceeded().await {
+            counter!("interpretation_budget_refusals").increment(1);
+            let refused = answered(
+                String::new(),
+                Some(t!("errors.budget_exceeded").to_string()),
+            );
+            self.save_message(refused).await;
+            self.notify_answered(reading).await;
+            return;
+        }
+
+        let mut request = self.explain_request(&reading).await;
+        request.conversation = Self::conversation(interpretation, &earlier, &question);
+        let on_chunk = {
+            let message_broadcast = self.message_broadcast.clone();
+            move |chunk: &str| {
+                // Nobody listening is not an error
+                let _ = message_broadcast.send(MessageChunk {
+                    reading_id: reading.id,
+                    message_id: answer_id,
+                    text: chunk.to_string(),
+                });
+            }
+        };
+        let backend = reading
+            .backend
+            .clone()
+            .unwrap_or(InterpretationBackend::ChatGPT);
+        let explanation = self
+            .interpretation_service
+            .interpret(&request, backend.clone(), &on_chunk)
+            .await;
+        let status = if explanation.result.is_ok() {
+            "success"
+        } else {
+            "failure"
+        };
+        counter!(
+            "interpretation_follow_ups",
+            "backend" => backend.id(),
+            "status" => status
+        )
+        .increment(1);
+        tracing::debug!(explanation = ?explanation, "answer result");
+        let answer = match explanation.result {
+            Ok(text) => {
+                // Follow-ups count towards the spend like interpretations do
+                let mut counted = reading.clone();
+                counted.model = explanation.model;
+                counted.usage = explanation.usage;
+                self.record_usage(&counted).await;
+                answered(text, None)
+            }
+            Err(e) => answered(
+                String::new(),
+                Some(interpretation::localize_explain_error(&e)),
+            ),
+        };
+        self.save_message(answer).await;
+        self.notify_answered(reading).await;
+    }
+
+    /// What the model is sent after the reading's prompt: its interpretation, the earlier
+    /// questions that were answered and their answers, then the new question.
+    fn conversation(
+        interpretation: String,
+        earlier: &[ReadingMessage],
+        question: &ReadingMessage,
+    ) -> Vec<Turn> {
+        let mut turns = vec![Turn {
+            role: Role::Assistant,
+            text: interpretation,
+        }];
+        for message in earlier {
+            if message.error.is_some() {
+                // Drop the question that went unanswered
+                turns.pop_if(|turn| turn.role == Role::User);
+                continue;
+            }
+            turns.push(message.into());
+        }
+        // A question left unanswered is replaced by the new one
+        turns.pop_if(|turn| turn.role == Role::User);
+        turns.push(question.into());
+        turns
+    }
+
+    async fn save_message(&self, message: ReadingMessage) {
+        let to_store: crate::model::ReadingMessage = message.into();
+        let mut conn = self.db_pool.get().await.unwrap();
+        diesel::insert_into(crate::schema::reading_messages::dsl::reading_messages)
+            .values(&to_store)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+    }
+
+    /// Tells subscribers of the reading that its thread has a new answer.
+    async fn notify_answered(&self, reading: Reading) {
+        if let Some(interpretation) = self.get_interpretation(reading.id).await {
+            // Nobody listening is not an error
+            let _ = self.broadcast.send(interpretation);
+        }
+    }
+
     async fn update_interpretation(&self, interpretation: Interpretation) -> Interpretation {
         let mut conn = self.db_pool.get().await.unwrap();
         let reading: crate::model::Reading = interpretation.into();
@@ -202,6 +572,7 @@ impl InterpretationRepository {
         let mut query = r::readings
             .select(crate::model::Reading::as_select())
             .filter(r::user_id.eq(user_id).and(r::deleted_at.is_null()))
+            .filter(r::clarified_reading_id.is_null())
             .into_boxed();
 
         if let Some(before) = before {
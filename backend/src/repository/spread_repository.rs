@@ -0,0 +1,165 @@
+use crate::database::DbPool;
+use crate::entity::spread::{CustomSpread, SaveSpreadRequest};
+use crate::error::{AppError, AppResult};
+use crate::model::UpdateSpreadFields;
+use crate::state::AppState;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use chrono::Utc;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+};
+use diesel_async::RunQueryDsl;
+use rust_i18n::t;
+use std::convert::Infallible;
+use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
+use uuid::Uuid;
+use webtarot_shared::model::{Spread, SpreadLayout};
+
+#[derive(Clone)]
+pub struct SpreadRepository {
+    db_pool: DbPool,
+}
+
+impl Debug for SpreadRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SpreadRepository {{ }}")
+    }
+}
+
+impl From<AppState> for SpreadRepository {
+    fn from(state: AppState) -> Self {
+        Self {
+            db_pool: state.postgresql_pool,
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for SpreadRepository {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        _parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(state.clone().into())
+    }
+}
+
+impl SpreadRepository {
+    pub async fn find(&self, id: Uuid) -> AppResult<Option<CustomSpread>> {
+        use crate::schema::spreads::dsl as s;
+        let mut conn = self.db_pool.get().await?;
+        let spread = s::spreads
+            .find(id)
+            .filter(s::deleted_at.is_null())
+            .select(crate::model::CustomSpread::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+        Ok(spread.map(CustomSpread::from))
+    }
+
+    pub async fn list_for_user(&self, user_id: Uuid) -> AppResult<Vec<CustomSpread>> {
+        use crate::schema::spreads::dsl as s;
+        let mut conn = self.db_pool.get().await?;
+        Ok(s::spreads
+            .filter(s::user_id.eq(user_id).and(s::deleted_at.is_null()))
+            .order(s::created_at.desc())
+            .select(crate::model::CustomSpread::as_select())
+            .load(&mut conn)
+            .await?
+            .into_iter()
+            .map(CustomSpread::from)
+            .collect())
+    }
+
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        request: SaveSpreadRequest,
+    ) -> AppResult<CustomSpread> {
+        request.validate()?;
+        let mut conn = self.db_pool.get().await?;
+        let spread = crate::model::CustomSpread {
+            id: Uuid::new_v4(),
+            user_id,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            name: request.name,
+            positions: request.positions.into(),
+            shared: request.shared,
+            deleted_at: None,
+        };
+        diesel::insert_into(crate::schema::spreads::table)
+            .values(spread)
+            .returning(crate::model::CustomSpread::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map(CustomSpread::from)
+            .map_err(|e| AppError::from_diesel_with_log("Failed to insert spread", e))
+    }
+
+    pub async fn update(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        request: SaveSpreadRequest,
+    ) -> AppResult<CustomSpread> {
+        use crate::schema::spreads::dsl as s;
+        request.validate()?;
+        let mut conn = self.db_pool.get().await?;
+        diesel::update(
+            s::spreads
+                .find(id)
+                .filter(s::user_id.eq(user_id).and(s::deleted_at.is_null())),
+        )
+        .set(UpdateSpreadFields {
+            name: request.name,
+            positions: request.positions.into(),
+            shared: request.shared,
+        })
+        .returning(crate::model::CustomSpread::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map(CustomSpread::from)
+        .map_err(|e| AppError::from_diesel_with_log("Failed to update spread", e))
+    }
+
+    pub async fn delete(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
+        use crate::schema::spreads::dsl as s;
+        let mut conn = self.db_pool.get().await?;
+        let deleted = diesel::update(
+            s::spreads
+                .find(id)
+                .filter(s::user_id.eq(user_id).and(s::deleted_at.is_null())),
+        )
+        .set(s::deleted_at.eq(diesel::dsl::now))
+        .execute(&mut conn)
+        .await?;
+        if deleted == 0 {
+            return Err(AppError::NotFound);
+        }
+        Ok(())
+    }
+
+    /// Resolves a spread id given when creating a reading: either a built-in layout id
+    /// or the id of a custom spread that `user_id` owns or that has been shared.
+    pub async fn resolve(&self, id: Option<&str>, user_id: Uuid) -> AppResult<Option<Spread>> {
+        let Some(id) = id else {
+            return Ok(None);
+        };
+        if let Ok(layout) = SpreadLayout::from_str(id) {
+            return Ok(Some(layout.spread()));
+        }
+        let unknown = || AppError::ValidateError(t!("errors.unknown_spread", id = id).to_string());
+        let Ok(uuid) = id.parse::<Uuid>() else {
+            return Err(unknown());
+        };
+        match self.find(uuid).await? {
+            Some(spread) if spread.is_visible_to(user_id) => Ok(Some(spread.into())),
+            _ => Err(unknown()),
+        }
+    }
+}
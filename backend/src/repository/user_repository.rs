@@ -11,6 +11,7 @@ use diesel::{OptionalExtension, QueryDsl, SelectableHelper};
 use diesel_async::pooled_connection::bb8::PooledConnection;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use uuid::Uuid;
+use webtarot_shared::style::{ReadingLength, ReadingStyle};
 
 // Type alias for pooled async Postgres connection used in repository methods
 type DbConn<'a> = PooledConnection<'a, AsyncPgConnection>;
@@ -96,6 +97,8 @@ impl UserRepository {
             name: request.name,
             self_description: request.self_description,
             password_digest: self.password_digest(&request.password),
+            reading_style: ReadingStyle::default().id().to_string(),
+            reading_length: ReadingLength::default().id().to_string(),
         };
         let access_token = crate::model::NewAccessToken {
             user_id: id,
@@ -210,6 +213,8 @@ impl From<UpdateUserRequest> for UpdateUserFields {
             name: value.name,
             self_description: value.self_description,
             email: value.email,
+            reading_style: value.reading_style.map(|style| style.id().to_string()),
+            reading_length: value.reading_length.map(|length| length.id().to_string()),
         }
     }
 }
@@ -12,6 +12,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    reading_messages (id) {
+        id -> Uuid,
+        reading_id -> Uuid,
+        created_at -> Timestamp,
+        role -> Text,
+        content -> Text,
+        error -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     readings (id) {
         id -> Uuid,
@@ -28,6 +39,40 @@ diesel::table! {
         interpretation_error -> Text,
         deleted_at -> Nullable<Timestamp>,
         interpretation_done_at -> Nullable<Timestamp>,
+        spread -> Nullable<Jsonb>,
+        seed -> Nullable<Text>,
+        fair_draw -> Nullable<Jsonb>,
+        tradition -> Text,
+        deck_options -> Nullable<Jsonb>,
+        clarified_reading_id -> Nullable<Uuid>,
+        clarified_position -> Nullable<Int4>,
+        jumpers -> Nullable<Jsonb>,
+        entropy -> Nullable<Jsonb>,
+        shuffle -> Text,
+        backend -> Nullable<Text>,
+        model -> Nullable<Text>,
+        interpretation_attempts -> Nullable<Int4>,
+        prompt_tokens -> Nullable<Int4>,
+        completion_tokens -> Nullable<Int4>,
+        cost_usd -> Nullable<Float8>,
+        prompt_template -> Nullable<Text>,
+        prompt_template_version -> Nullable<Int4>,
+        interpretation_structured -> Nullable<Jsonb>,
+        style -> Text,
+        length -> Text,
+    }
+}
+
+diesel::table! {
+    spreads (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        name -> Text,
+        positions -> Jsonb,
+        shared -> Bool,
+        deleted_at -> Nullable<Timestamp>,
     }
 }
 
@@ -40,9 +85,19 @@ diesel::table! {
         password_digest -> Text,
         name -> Text,
         self_description -> Text,
+        reading_style -> Text,
+        reading_length -> Text,
     }
 }
 
 diesel::joinable!(access_tokens -> users (user_id));
+diesel::joinable!(reading_messages -> readings (reading_id));
+diesel::joinable!(spreads -> users (user_id));
 
-diesel::allow_tables_to_appear_in_same_query!(access_tokens, readings, users,);
+diesel::allow_tables_to_appear_in_same_query!(
+    access_tokens,
+    reading_messages,
+    readings,
+    spreads,
+    users,
+);
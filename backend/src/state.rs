@@ -1,7 +1,15 @@
 use crate::database::DbPool;
 use crate::entity::interpretation::Interpretation;
+use crate::entity::reading_message::MessageChunk;
 use redis::aio::ConnectionManager;
+use std::collections::HashMap;
 use std::env;
+use webtarot_shared::explain::{InterpretationBackend, InterpretationService};
+use webtarot_shared::pricing::PriceTable;
+use webtarot_shared::provider::local::LocalConfig;
+use webtarot_shared::provider::{ModelConfig, ProviderConfig, ProviderRegistry};
+use webtarot_shared::resilience::ResilienceConfig;
+use webtarot_shared::template::PromptTemplates;
 
 #[derive(Clone)]
 pub struct AppEnvironment {
@@ -10,6 +18,31 @@ pub struct AppEnvironment {
     pub database_url: String,
     pub openai_api_key: String,
     pub google_api_key: String,
+    pub anthropic_api_key: String,
+    /// Self-hosted OpenAI-compatible model, offered when `LOCAL_LLM_URL` is set.
+    pub local_llm: Option<LocalConfig>,
+    /// Interpretation providers to offer, from the comma-separated `INTERPRETATION_PROVIDERS`
+    /// (e.g. `chatGPT,gemini`); every provider with an API key when unset.
+    pub interpretation_providers: Option<Vec<InterpretationBackend>>,
+    /// Model, allowed models and generation parameters of each provider, from `OPENAI_MODEL`,
+    /// `GEMINI_TEMPERATURE`, `ANTHROPIC_ALLOWED_MODELS` and the like.
+    pub interpretation_models: HashMap<InterpretationBackend, ModelConfig>,
+    /// Retries, circuit breakers and failover backends, from `INTERPRETATION_MAX_ATTEMPTS`,
+    /// `INTERPRETATION_FAILOVER` and the like.
+    pub interpretation_resilience: ResilienceConfig,
+    /// Prices of the models, to estimate what each interpretation costs, from
+    /// `INTERPRETATION_PRICES` on top of the list prices of the default models.
+    pub interpretation_prices: PriceTable,
+    /// US dollars all interpretations together may cost in a (UTC) day, from
+    /// `INTERPRETATION_DAILY_BUDGET_USD`; no limit when unset.
+    pub interpretation_daily_budget: Option<f64>,
+    /// Built-in prompt templates plus those under `PROMPT_TEMPLATES_DIR`, with `PROMPT_TEMPLATE`
+    /// as the default.
+    pub prompt_templates: PromptTemplates,
+    /// Whether interpretations are asked for in sections (card by card, synthesis, emotional
+    /// implications, advice) rather than free text, from `INTERPRETATION_STRUCTURED_OUTPUT`.
+    /// Structured interpretations are not streamed.
+    pub structured_interpretations: bool,
 }
 
 impl AppEnvironment {
@@ -48,6 +81,46 @@ impl AppEnvironment {
             database_url: env::var("DATABASE_URL").expect("DATABASE_URL not set"),
             openai_api_key: env::var("OPENAI_KEY").expect("OPENAI_KEY not set"),
             google_api_key: env::var("GOOG_API_KEY").unwrap_or_default(),
+            anthropic_api_key: env::var("ANTHROPIC_API_KEY").unwrap_or_default(),
+            local_llm: env::var("LOCAL_LLM_URL").ok().map(|base_url| LocalConfig {
+                base_url,
+                api_key: env::var("LOCAL_LLM_API_KEY").ok(),
+            }),
+            interpretation_models: ProviderConfig::models_from_env(),
+            interpretation_resilience: ResilienceConfig::from_env(),
+            interpretation_prices: PriceTable::from_env(),
+            prompt_templates: PromptTemplates::from_env(),
+            structured_interpretations: env::var("INTERPRETATION_STRUCTURED_OUTPUT")
+                .is_ok_and(|enabled| enabled.trim() == "true"),
+            interpretation_daily_budget: env::var("INTERPRETATION_DAILY_BUDGET_USD").ok().map(
+                |budget| {
+                    budget
+                        .trim()
+                        .parse()
+                        .unwrap_or_else(|_| panic!("Invalid INTERPRETATION_DAILY_BUDGET_USD"))
+                },
+            ),
+            interpretation_providers: env::var("INTERPRETATION_PROVIDERS").ok().map(|ids| {
+                ids.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| {
+                        id.parse()
+                            .unwrap_or_else(|_| panic!("Unknown interpretation provider: {}", id))
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    pub fn provider_config(&self) -> ProviderConfig {
+        ProviderConfig {
+            openai_api_key: self.openai_api_key.clone(),
+            google_api_key: self.google_api_key.clone(),
+            anthropic_api_key: self.anthropic_api_key.clone(),
+            local: self.local_llm.clone(),
+            enabled: self.interpretation_providers.clone(),
+            models: self.interpretation_models.clone(),
         }
     }
 }
@@ -57,7 +130,10 @@ pub struct AppState {
     pub env: AppEnvironment,
     pub redis_connection_manager: ConnectionManager,
     pub interpretation_broadcast: tokio::sync::broadcast::Sender<Interpretation>,
+    /// Pieces of answers to follow-up questions, as the model writes them.
+    pub message_broadcast: tokio::sync::broadcast::Sender<MessageChunk>,
     pub postgresql_pool: DbPool,
+    pub interpretation_service: InterpretationService,
 }
 
 impl AppState {
@@ -69,12 +145,20 @@ impl AppState {
     pub async fn from_env(env: AppEnvironment) -> Self {
         let client = redis::Client::open(env.redis_url.clone()).unwrap();
         let manager = ConnectionManager::new(client).await.unwrap();
-        let (interpretation_broadcast, _) = tokio::sync::broadcast::channel(100);
+        let (interpretation_broadcast, _) = tokio::sync::broadcast::channel(1024);
+        let (message_broadcast, _) = tokio::sync::broadcast::channel(1024);
         let postgresql_pool = crate::database::create_database_pool(env.database_url.clone()).await;
+        let interpretation_service = InterpretationService::with_resilience(
+            ProviderRegistry::from_config(&env.provider_config()),
+            env.interpretation_resilience.clone(),
+        )
+        .with_templates(env.prompt_templates.clone());
         Self {
+            interpretation_service,
             env,
             redis_connection_manager: manager,
             interpretation_broadcast,
+            message_broadcast,
             postgresql_pool,
         }
     }
@@ -1,11 +1,14 @@
 #[cfg(test)]
 use crate::entity::interpretation::Interpretation;
 use crate::entity::reading::Reading;
+use crate::model;
 use crate::repository::interpretation_repository::InterpretationRepository;
 use crate::state::AppState;
 use mockito::{Matcher, Server, ServerGuard};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::time::{Duration, timeout};
 use uuid::Uuid;
+use webtarot_shared::model::Card;
 // ensure dependency resolved
 
 /// Start a mock server for the OpenAI Chat Completions API and point the
@@ -69,7 +72,10 @@ pub async fn wait_for_done(
                     // Ignore events for other readings
                 }
             },
-            Ok(Err(_)) => {
+            Ok(Err(RecvError::Lagged(_))) => {
+                // streamed pieces crowded the channel; the Done event is still ahead
+            }
+            Ok(Err(RecvError::Closed)) => {
                 // channel closed; break with error
                 return Err("Broadcast channel closed".to_string());
             }
@@ -80,3 +86,97 @@ pub async fn wait_for_done(
     }
     Err("Timed out waiting for Interpretation::Done".to_string())
 }
+
+/// Insert a registered user along with an access token usable as a Bearer token.
+pub async fn insert_user_with_token(state: &AppState) -> (model::User, model::NewAccessToken) {
+    use chrono::Utc;
+    use diesel_async::RunQueryDsl;
+    let mut conn = state.postgresql_pool.get().await.unwrap();
+    let user = model::User {
+        id: Uuid::new_v4(),
+        created_at: Utc::now().naive_utc(),
+        updated_at: Utc::now().naive_utc(),
+        email: format!("test-{}@example.com", Uuid::new_v4()),
+        password_digest: "digest".to_string(),
+        name: "Test User".to_string(),
+        self_description: "desc".to_string(),
+        reading_style: "psychological".to_string(),
+        reading_length: "standard".to_string(),
+    };
+    diesel::insert_into(crate::schema::users::table)
+        .values(user.clone())
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let token = model::NewAccessToken {
+        user_id: user.id,
+        created_at: Utc::now().naive_utc(),
+        token: format!("token-{}", Uuid::new_v4()),
+        last_user_ip: "127.0.0.1".to_string(),
+        last_user_agent: "test-suite".to_string(),
+        deleted_at: None,
+    };
+    diesel::insert_into(crate::schema::access_tokens::table)
+        .values(token.clone())
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    (user, token)
+}
+
+/// A done reading of `cards` by `user_id`, as the database stores it; tests change what they need
+/// and insert it with [`insert_reading_row`].
+pub fn reading_row(user_id: Uuid, cards: Vec<Card>) -> model::Reading {
+    use chrono::Utc;
+    model::Reading {
+        id: Uuid::new_v4(),
+        created_at: Utc::now().naive_utc(),
+        question: "q?".to_string(),
+        context: String::new(),
+        cards: cards.into(),
+        shuffled_times: 0,
+        user_id,
+        user_name: String::new(),
+        user_self_description: String::new(),
+        interpretation_status: model::InterpretationStatus::Done,
+        interpretation_text: String::new(),
+        interpretation_error: String::new(),
+        deleted_at: None,
+        interpretation_done_at: Some(Utc::now().naive_utc()),
+        spread: None,
+        seed: None,
+        fair_draw: None,
+        tradition: "riderWaite".to_string(),
+        deck_options: None,
+        clarified_reading_id: None,
+        clarified_position: None,
+        jumpers: None,
+        entropy: None,
+        shuffle: "singlePass".to_string(),
+        backend: None,
+        model: None,
+        interpretation_attempts: None,
+        prompt_tokens: None,
+        completion_tokens: None,
+        cost_usd: None,
+        prompt_template: None,
+        prompt_template_version: None,
+        interpretation_structured: None,
+        style: "psychological".to_string(),
+        length: "standard".to_string(),
+    }
+}
+
+/// Insert a reading straight into the database, returning its id.
+pub async fn insert_reading_row(state: &AppState, reading: model::Reading) -> Uuid {
+    use diesel_async::RunQueryDsl;
+    let mut conn = state.postgresql_pool.get().await.unwrap();
+    let id = reading.id;
+    diesel::insert_into(crate::schema::readings::table)
+        .values(reading)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    id
+}
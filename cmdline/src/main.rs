@@ -1,57 +1,507 @@
-use clap::Parser;
-use webtarot_shared::explain::{InterpretationBackend, InterpretationService};
-use webtarot_shared::model::Deck;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+use webtarot_shared::entropy::{
+    BeaconEntropy, EntropySource, MIN_USER_SAMPLES, OsEntropy, UserEntropy,
+};
+use webtarot_shared::explain::{ExplainRequest, InterpretationBackend, InterpretationService};
+use webtarot_shared::fair::{VerifiableReading, verify};
+use webtarot_shared::fairness::{FairnessReport, simulate_draws, simulate_readings};
+use webtarot_shared::model::{
+    ArcanaFilter, Card, Deck, DeckOptions, DeckSeed, DeckTradition, ShuffleMethod, Spread,
+    SpreadLayout, Suit,
+};
+use webtarot_shared::pricing::PriceTable;
+use webtarot_shared::provider::local::LocalConfig;
+use webtarot_shared::provider::{ModelConfig, ProviderConfig, ProviderRegistry, ReasoningEffort};
+use webtarot_shared::resilience::ResilienceConfig;
+use webtarot_shared::style::{ReadingLength, ReadingStyle};
+use webtarot_shared::template::PromptTemplates;
 
 #[derive(Parser, Debug)]
+#[command(subcommand_negates_reqs = true)]
 struct CliArgs {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(short, long, required = true)]
+    question: Option<String>,
+    /// Number of cards to draw
+    #[arg(short = 'c', long, required_unless_present_any = ["spread", "cards"])]
+    count: Option<usize>,
+    /// Cards already drawn from a physical deck, e.g. "AC, XVI, Ten of Swords reversed";
+    /// skips shuffling
+    #[arg(long, conflicts_with_all = ["count", "seed"], value_parser = card_notation)]
+    cards: Option<String>,
+    /// Cards that jumped out of the physical deck while shuffling, in the same notation
+    #[arg(long, requires = "cards")]
+    jumpers: Option<String>,
+    /// Spread layout, e.g. threeCard or celticCross; sets the number of cards
     #[arg(short, long)]
-    question: String,
-    #[arg(short, long)]
-    cards: usize,
+    spread: Option<SpreadLayout>,
+    /// Deck tradition: riderWaite, marseille, thoth or lenormand
+    #[arg(short, long, default_value = "riderWaite")]
+    tradition: DeckTradition,
+    /// Draw from the major arcana only
+    #[arg(long, conflicts_with = "minors_only")]
+    majors_only: bool,
+    /// Draw from the minor arcana only
+    #[arg(long)]
+    minors_only: bool,
+    /// Keep only these suits among the minor arcana (repeatable): cups, pentacles, swords, wands
+    #[arg(long = "suit")]
+    suits: Vec<Suit>,
+    /// Chance of each card coming up reversed; 0 never reverses
+    #[arg(long, default_value_t = 0.5)]
+    reversal_probability: f64,
+    /// Chance of a card jumping out of the deck on each shuffle pass; 0 never does
+    #[arg(long, default_value_t = 0.0)]
+    jumper_probability: f64,
+    /// Hex seed of a stored reading; replays its exact shuffle and draw
+    #[arg(long)]
+    seed: Option<DeckSeed>,
+    /// Shuffle method: singlePass, or everyPass to replay readings made before single passes
+    #[arg(long, default_value = "singlePass")]
+    shuffle: ShuffleMethod,
+    /// Where the shuffle's randomness comes from: os, user (key presses) or beacon
+    #[arg(long, default_value = "os", conflicts_with_all = ["seed", "cards"])]
+    entropy: EntropyArg,
     #[arg(short, long, default_value_t = true)]
     explain: bool,
-    /// Backend provider: chatgpt or gemini
+    /// Backend provider: chatgpt, gemini, claude or local (any OpenAI-compatible server, e.g. Ollama)
     #[arg(short = 'b', long = "backend", default_value = "chatgpt")]
     backend: String,
+    /// Base URL of the local backend's server [default: LOCAL_LLM_URL or Ollama's]
+    #[arg(long)]
+    local_url: Option<String>,
+    /// Model to interpret with [default: the backend's, e.g. OPENAI_MODEL or gpt-5.1]
+    #[arg(long)]
+    model: Option<String>,
+    /// Sampling temperature [default: the backend's, e.g. OPENAI_TEMPERATURE]
+    #[arg(long)]
+    temperature: Option<f32>,
+    /// Longest interpretation, in tokens [default: the backend's, e.g. OPENAI_MAX_TOKENS]
+    #[arg(long)]
+    max_tokens: Option<u32>,
+    /// How much a reasoning model thinks: low, medium or high
+    #[arg(long)]
+    reasoning_effort: Option<ReasoningEffort>,
+    /// Prompt template, by name or name@version [default: PROMPT_TEMPLATE or classic]; more
+    /// are loaded from PROMPT_TEMPLATES_DIR
+    #[arg(long)]
+    template: Option<String>,
+    /// Reading style: psychological, traditional, practical or yesNo
+    #[arg(long, default_value = "psychological")]
+    style: ReadingStyle,
+    /// Reading length: brief, standard or inDepth
+    #[arg(long, default_value = "standard")]
+    length: ReadingLength,
+}
+
+/// Takes `--cards` as card notation, turning away a bare number: it used to be the number of
+/// cards to draw, which is now `--count`, and would otherwise be read as a major arcana.
+fn card_notation(value: &str) -> Result<String, String> {
+    if value.trim().parse::<usize>().is_ok() {
+        return Err(format!(
+            "para tirar {} cartas use --count {}; --cards recebe as cartas já tiradas, ex. \"AC, XVI\"",
+            value.trim(),
+            value.trim()
+        ));
+    }
+    Ok(value.to_string())
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum EntropyArg {
+    Os,
+    User,
+    Beacon,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Verifies a commit-reveal reading against its revealed server seed
+    Verify {
+        /// JSON returned by GET /api/v1/interpretation/{id}, or just its `reading`; stdin if omitted
+        file: Option<PathBuf>,
+    },
+    /// Simulates many readings and reports how far their cards are from fair
+    Fairness {
+        /// Number of readings to simulate
+        #[arg(long, default_value_t = 1_000_000)]
+        readings: usize,
+        /// Cards drawn per reading
+        #[arg(short = 'c', long, default_value_t = 3)]
+        count: usize,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VerifyInput {
+    Reading(VerifiableReading),
+    Interpretation { reading: VerifiableReading },
+}
+
+fn verify_reading(file: Option<PathBuf>) -> bool {
+    let input = match file {
+        Some(path) => std::fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).map(|_| input)
+        }
+    };
+    let reading = match input
+        .map_err(|e| e.to_string())
+        .and_then(|input| serde_json::from_str::<VerifyInput>(&input).map_err(|e| e.to_string()))
+    {
+        Ok(VerifyInput::Reading(reading) | VerifyInput::Interpretation { reading }) => reading,
+        Err(error) => {
+            println!("Leitura inválida: {}", error);
+            return false;
+        }
+    };
+
+    println!(
+        "Compromisso: {}\nSemente do servidor: {}\nSemente do cliente: {}",
+        reading.fair_draw.commitment, reading.fair_draw.server_seed, reading.fair_draw.client_seed
+    );
+    match verify(&reading) {
+        Ok(recomputed) => {
+            println!(
+                "\nVerificado: embaralhado {} vezes, as cartas conferem.",
+                recomputed.shuffled_times
+            );
+            for card in recomputed.cards.iter() {
+                println!("  * {}", card.in_tradition(reading.tradition));
+            }
+            print_jumpers(&recomputed.deck.jumpers, reading.tradition);
+            true
+        }
+        Err(error) => {
+            println!("\nFalha na verificação: {}", error);
+            false
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() {
     let args = CliArgs::parse();
+    match args.command {
+        Some(Command::Verify { file }) => {
+            if !verify_reading(file) {
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Fairness { readings, count }) => {
+            if !check_fairness(readings, count) {
+                std::process::exit(1);
+            }
+            return;
+        }
+        None => {}
+    }
     println!("{:?}", args);
-    let mut deck = Deck::default();
-    let shuffles = deck.shuffle(&args.question);
+    let question = args.question.clone().unwrap_or_default();
+    let spread = args.spread.map(|layout| layout.spread());
+    if let Some(cards) = args.cards.as_deref() {
+        let parse = |cards| match Card::parse_list(cards, args.tradition) {
+            Ok(cards) => cards,
+            Err(error) => {
+                println!("{}", error);
+                std::process::exit(1);
+            }
+        };
+        let cards = parse(cards);
+        let jumpers = args.jumpers.as_deref().map(parse).unwrap_or_default();
+        if let Some(spread) = spread.as_ref().filter(|s| s.card_count() != cards.len()) {
+            println!(
+                "A tiragem {} pede {} cartas.",
+                spread.name,
+                spread.card_count()
+            );
+            std::process::exit(1);
+        }
+        println!("A pergunta foi: {}", question);
+        print_cards(&cards, spread.as_ref(), args.tradition);
+        print_jumpers(&jumpers, args.tradition);
+        if args.explain {
+            explain(
+                ExplainRequest {
+                    question,
+                    cards,
+                    jumpers,
+                    tradition: args.tradition,
+                    spread,
+                    ..Default::default()
+                },
+                &args,
+            )
+            .await;
+        }
+        return;
+    }
+    let count = spread
+        .as_ref()
+        .map(|spread| spread.card_count())
+        .or(args.count)
+        .unwrap_or_default();
+    let deck_options = DeckOptions {
+        arcana: if args.majors_only {
+            ArcanaFilter::MajorOnly
+        } else if args.minors_only {
+            ArcanaFilter::MinorOnly
+        } else {
+            ArcanaFilter::All
+        },
+        suits: args.suits.clone(),
+        reversal_probability: args.reversal_probability,
+        jumper_probability: args.jumper_probability,
+    };
+    let deck = Deck::build_with(args.tradition, deck_options.clone()).with_shuffle(args.shuffle);
+    if let Err(error) = deck.validate_draw(count) {
+        println!("{}", error);
+        std::process::exit(1);
+    }
+    let seed = match args.seed {
+        Some(seed) => seed,
+        None => random_seed(args.entropy).await,
+    };
+    let reading = deck.seeded_reading(&question, &seed, count);
 
     println!(
-        "A pergunta foi: {}\n\nEmbaralhando {} vezes...",
-        args.question, shuffles
+        "A pergunta foi: {}\nSemente: {}\n\nEmbaralhando {} vezes...",
+        question, seed, reading.shuffled_times
     );
 
-    let cards = deck.draw(args.cards);
-    println!("\n\n");
-    for card in &cards {
-        println!("  * {}", card);
+    if args.seed.is_some() {
+        println!("\nOrdem do baralho:");
+        for card in reading.deck.cards.iter() {
+            println!("  {}", card.in_tradition(args.tradition));
+        }
     }
 
+    let cards = reading.cards;
+    print_cards(&cards, spread.as_ref(), args.tradition);
+    print_jumpers(&reading.deck.jumpers, args.tradition);
+
     if args.explain {
-        println!("Interpretando...\n\n");
-        let backend = match args.backend.to_lowercase().as_str() {
-            "gemini" => InterpretationBackend::Gemini,
-            _ => InterpretationBackend::ChatGPT,
-        };
-        let service = InterpretationService::new(
-            std::env::var("OPENAI_KEY").unwrap_or_default(),
-            std::env::var("GOOG_API_KEY").unwrap_or_default(),
+        explain(
+            ExplainRequest {
+                question,
+                cards,
+                jumpers: reading.deck.jumpers,
+                tradition: args.tradition,
+                deck_options,
+                spread,
+                ..Default::default()
+            },
+            &args,
+        )
+        .await;
+    }
+}
+
+fn check_fairness(readings: usize, count: usize) -> bool {
+    let deck = Deck::build();
+    if let Err(error) = deck.validate_draw(count) {
+        println!("{}", error);
+        return false;
+    }
+    let draws = simulate_draws(&deck, count, readings, &mut DeckSeed::random().rng());
+    print_fairness("Posições do baralho, sem embaralhar", &draws);
+    let shuffled = simulate_readings(&deck, "", count, readings, &mut DeckSeed::random().rng());
+    print_fairness("Leituras embaralhadas", &shuffled);
+    draws.fair && shuffled.fair
+}
+
+fn print_fairness(title: &str, report: &FairnessReport) {
+    println!(
+        "\n{}: {} cartas em {} tiragens\n  Qui-quadrado das cartas: {:.2} ({} graus de liberdade, limite {:.2})",
+        title,
+        report.cards_drawn,
+        report.draws,
+        report.chi_square,
+        report.degrees_of_freedom,
+        report.critical_value
+    );
+    if report.orientation.reversible > 0 {
+        println!(
+            "  Invertidas: {} de {}, esperadas {:.0} (z = {:.2}); qui-quadrado {:.2}, limite {:.2}",
+            report.orientation.reversed,
+            report.orientation.reversible,
+            report.orientation.expected_reversed,
+            report.orientation.z_score,
+            report.orientation.chi_square,
+            report.orientation.critical_value
         );
-        let explanation = service
-            .explain(&args.question, None, &cards, None, None, backend)
-            .await;
-        if let Ok(explanation) = explanation {
-            println!("{}", explanation);
-            return;
+    }
+    let mut cards = report.cards.iter().collect::<Vec<_>>();
+    cards.sort_by(|a, b| b.z_score.abs().total_cmp(&a.z_score.abs()));
+    println!("  Maiores desvios:");
+    for card in cards.iter().take(5) {
+        println!(
+            "    {}: {} (esperadas {:.0}, z = {:.2})",
+            Card {
+                arcana: card.arcana,
+                flipped: false
+            },
+            card.observed,
+            card.expected,
+            card.z_score
+        );
+    }
+    println!(
+        "  {}",
+        if report.fair {
+            "Justo."
         } else {
-            let error = explanation.unwrap_err();
-            println!("Erro: {}", error);
+            "Tendencioso!"
+        }
+    );
+}
+
+/// Obtains a fresh seed from the chosen entropy source, exiting when it fails.
+async fn random_seed(entropy: EntropyArg) -> DeckSeed {
+    let seed = match entropy {
+        EntropyArg::Os => OsEntropy.seed().await,
+        EntropyArg::User => UserEntropy::new(key_press_timings()).seed().await,
+        EntropyArg::Beacon => BeaconEntropy::default().seed().await,
+    };
+    match seed {
+        Ok((seed, _)) => seed,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Nanoseconds between presses of Enter, as the user's own noise for the shuffle.
+fn key_press_timings() -> Vec<u64> {
+    println!(
+        "Concentre-se na pergunta e pressione Enter {} vezes, sem ritmo.",
+        MIN_USER_SAMPLES
+    );
+    let mut samples = Vec::with_capacity(MIN_USER_SAMPLES);
+    let mut last = Instant::now();
+    let mut line = String::new();
+    while samples.len() < MIN_USER_SAMPLES {
+        line.clear();
+        if std::io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        samples.push(last.elapsed().as_nanos() as u64);
+        last = Instant::now();
+    }
+    samples
+}
+
+fn print_cards(cards: &[Card], spread: Option<&Spread>, tradition: DeckTradition) {
+    println!("\n\n");
+    for (i, card) in cards.iter().enumerate() {
+        let card = card.in_tradition(tradition);
+        match spread.and_then(|spread| spread.positions.get(i)) {
+            Some(position) => println!("  * {}: {}", position.name, card),
+            None => println!("  * {}", card),
+        }
+    }
+}
+
+fn print_jumpers(jumpers: &[Card], tradition: DeckTradition) {
+    if jumpers.is_empty() {
+        return;
+    }
+    println!("\nCartas que saltaram do baralho:");
+    for card in jumpers {
+        println!("  * {}", card.in_tradition(tradition));
+    }
+}
+
+async fn explain(request: ExplainRequest, args: &CliArgs) {
+    println!("Interpretando...\n\n");
+    let backend = args
+        .backend
+        .parse()
+        .unwrap_or(InterpretationBackend::ChatGPT);
+    let local = LocalConfig {
+        base_url: args
+            .local_url
+            .clone()
+            .or_else(|| std::env::var("LOCAL_LLM_URL").ok())
+            .unwrap_or(LocalConfig::default().base_url),
+        api_key: std::env::var("LOCAL_LLM_API_KEY").ok(),
+    };
+    let mut models = ModelConfig::from_env(backend.env_prefix(), backend.default_model());
+    models.model = args.model.clone().unwrap_or(models.model);
+    let params = &mut models.params;
+    params.temperature = args.temperature.or(params.temperature);
+    params.max_tokens = args.max_tokens.or(params.max_tokens);
+    params.reasoning_effort = args.reasoning_effort.or(params.reasoning_effort);
+    // Retries as the server does; failover backends are not enabled here and are skipped
+    let registry = ProviderRegistry::from_config(&ProviderConfig {
+        openai_api_key: std::env::var("OPENAI_KEY").unwrap_or_default(),
+        google_api_key: std::env::var("GOOG_API_KEY").unwrap_or_default(),
+        anthropic_api_key: std::env::var("ANTHROPIC_API_KEY").unwrap_or_default(),
+        local: Some(local),
+        // Only the chosen one, so a missing key is reported as such
+        enabled: Some(vec![backend.clone()]),
+        models: HashMap::from([(backend.clone(), models)]),
+    });
+    let service = InterpretationService::with_resilience(registry, ResilienceConfig::from_env())
+        .with_templates(PromptTemplates::from_env());
+    let request = ExplainRequest {
+        template: args.template.clone(),
+        style: args.style,
+        length: args.length,
+        ..request
+    };
+    // Print the interpretation as the model writes it
+    let print_chunk = |chunk: &str| {
+        print!("{}", chunk);
+        let _ = std::io::stdout().flush();
+    };
+    let explanation = service.interpret(&request, backend, &print_chunk).await;
+    match explanation.result {
+        Ok(_) => println!(),
+        Err(error) => println!("Erro: {}", error),
+    }
+    if explanation.attempts.len() > 1 {
+        println!("\n({} tentativas)", explanation.attempts.len());
+    }
+    if let Some(usage) = explanation.usage {
+        print!(
+            "\n({} tokens de prompt, {} de resposta",
+            usage.prompt_tokens, usage.completion_tokens
+        );
+        let model = explanation.model.as_deref().unwrap_or_default();
+        match PriceTable::from_env().cost(model, &usage) {
+            Some(cost) => println!(", US$ {:.4})", cost),
+            None => println!(")"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_card_count_given_to_cards_points_to_count() {
+        let error = CliArgs::try_parse_from(["webtarot", "-q", "Will it work?", "--cards", "3"])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("--count 3"), "{}", error);
+
+        let args =
+            CliArgs::try_parse_from(["webtarot", "-q", "Will it work?", "--cards", "III, AC"])
+                .unwrap();
+        assert_eq!(Some("III, AC"), args.cards.as_deref());
+        let args = CliArgs::try_parse_from(["webtarot", "-q", "Will it work?", "-c", "3"]).unwrap();
+        assert_eq!(Some(3), args.count);
+    }
+}
@@ -89,9 +89,31 @@ export interface Reading {
   userName: string
   userSelfDescription: string
   context: string
+  backend?: InterpretationBackend
+  model?: string | null // model the reading was interpreted with
+  attempts?: number | null // calls made to providers, retries and failovers included
+  usage?: Usage | null // tokens the interpretation took, as the provider counted them
+  cost?: number | null // estimated cost of the interpretation, in US dollars
+  promptTemplate?: TemplateRef | null // prompt template the reading was interpreted with
+  style?: ReadingStyle
+  length?: ReadingLength
 }
 
-type InterpretationBackend = 'chatGPT' | 'gemini'
+// Mirrors Rust: ReadingStyle and ReadingLength in shared/src/style.rs
+export type ReadingStyle = 'psychological' | 'traditional' | 'practical' | 'yesNo'
+export type ReadingLength = 'brief' | 'standard' | 'inDepth'
+
+export interface TemplateRef {
+  name: string
+  version: number
+}
+
+export interface Usage {
+  promptTokens: number
+  completionTokens: number
+}
+
+type InterpretationBackend = 'chatGPT' | 'gemini' | 'claude' | 'local'
 
 // Mirrors Rust: CreateReadingRequest { question: String, cards: u8 }
 export interface CreateReadingRequest {
@@ -99,6 +121,9 @@ export interface CreateReadingRequest {
   cards: number // u8 in Rust → number in TS
   context: string
   backend: InterpretationBackend
+  model?: string // one of the backend's allowed models; its default when absent
+  style?: ReadingStyle // the user's default style when absent
+  length?: ReadingLength // the user's default length when absent
 }
 
 // Mirrors Rust: CreateReadingResponse { shuffledTimes: usize, cards: Vec<Card>, interpretationId: String }
@@ -114,6 +139,36 @@ export interface GetInterpretationResult {
   error: string
   interpretation: string
   reading: Reading | null
+  structured?: StructuredInterpretation | null // sections, when asked for as structured output
+  messages?: ReadingMessage[] // follow-up questions and their answers, oldest first
+}
+
+// Mirrors Rust: StructuredInterpretation in shared/src/structured.rs
+export interface StructuredInterpretation {
+  cards: CardAnalysis[] // in reading order; a card the model skipped has no entry
+  synthesis: string
+  emotionalImplications: string
+  advice: string
+}
+
+export interface CardAnalysis {
+  number: number // the card's position in the reading, from 1
+  analysis: string
+}
+
+// Mirrors Rust: ReadingMessage in backend/src/entity/reading_message.rs
+export interface ReadingMessage {
+  id: string // UUID string
+  readingId: string
+  createdAt: string // ISO timestamp
+  role: 'user' | 'assistant'
+  content: string
+  error?: string | null // why the answer could not be written
+}
+
+// Mirrors Rust: CreateReadingMessageRequest { text: String }
+export interface CreateReadingMessageRequest {
+  text: string
 }
 
 // Mirrors Rust: CreateInterpretationRequest { question: String, cards: Vec<Card> }
@@ -122,6 +177,9 @@ export interface CreateInterpretationRequest {
   cards: Card[]
   context: string
   backend: InterpretationBackend
+  model?: string // one of the backend's allowed models; its default when absent
+  style?: ReadingStyle // the user's default style when absent
+  length?: ReadingLength // the user's default length when absent
 }
 
 // Mirrors Rust: CreateInterpretationResponse { interpretationId: Uuid }
@@ -180,10 +238,14 @@ export function interpretationText(i: Interpretation): string {
 
 // Mirrors Rust enum InterpretationsWebsocketMessage in backend/src/main.rs
 // #[serde(rename_all = "camelCase")] with externally-tagged enum variants:
-// - Subscribe { uuid: Uuid }  => { "subscribe": { "uuid": "<uuid>" } }
-// - Done { uuid: Uuid }       => { "done": { "uuid": "<uuid>" } }
+// - Subscribe { uuid: Uuid }             => { "subscribe": { "uuid": "<uuid>" } }
+// - Chunk { uuid: Uuid, text: String }   => { "chunk": { "uuid": "<uuid>", "text": "..." } }
+// - ReplyChunk { uuid, messageId, text } => { "replyChunk": { "uuid": "<uuid>", "messageId": "<uuid>", "text": "..." } }
+// - Done { uuid: Uuid }                  => { "done": { "uuid": "<uuid>" } }
 export type InterpretationsWebsocketMessage =
   | { subscribe: { uuid: string } }
+  | { chunk: { uuid: string; text: string } }
+  | { replyChunk: { uuid: string; messageId: string; text: string } }
   | { done: { uuid: string } }
 
 export const isInterpretationsWebsocketMessage = (
@@ -195,6 +257,16 @@ export const isInterpretationsWebsocketMessage = (
     const s = v.subscribe as Record<string, unknown>
     return typeof s.uuid === 'string'
   }
+  if ('chunk' in v && typeof v.chunk === 'object' && v.chunk !== null) {
+    const c = v.chunk as Record<string, unknown>
+    return typeof c.uuid === 'string' && typeof c.text === 'string'
+  }
+  if ('replyChunk' in v && typeof v.replyChunk === 'object' && v.replyChunk !== null) {
+    const r = v.replyChunk as Record<string, unknown>
+    return (
+      typeof r.uuid === 'string' && typeof r.messageId === 'string' && typeof r.text === 'string'
+    )
+  }
   if ('done' in v && typeof v.done === 'object' && v.done !== null) {
     const d = v.done as Record<string, unknown>
     return typeof d.uuid === 'string'
@@ -222,6 +294,8 @@ export type User =
         email: string
         name: string
         selfDescription: string
+        readingStyle: ReadingStyle // default style of the user's readings
+        readingLength: ReadingLength
         accessToken: AccessToken
       }
     }
@@ -250,4 +324,6 @@ export interface UpdateUserRequest {
   name: string
   selfDescription: string
   email: string
+  readingStyle?: ReadingStyle // kept when absent
+  readingLength?: ReadingLength
 }
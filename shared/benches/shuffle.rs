@@ -0,0 +1,27 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use webtarot_shared::model::{Deck, DeckOptions, DeckSeed, DeckTradition, ShuffleMethod};
+
+fn shuffle(c: &mut Criterion) {
+    let jumpers = DeckOptions {
+        jumper_probability: 0.0002,
+        ..Default::default()
+    };
+    for method in [ShuffleMethod::SinglePass, ShuffleMethod::EveryPass] {
+        for (name, options) in [("", DeckOptions::default()), ("/jumpers", jumpers.clone())] {
+            let deck = Deck::build_with(DeckTradition::RiderWaite, options).with_shuffle(method);
+            c.bench_function(&format!("seeded_reading/{}{}", method.id(), name), |b| {
+                b.iter(|| {
+                    deck.clone().seeded_reading(
+                        black_box("Will I get the job?"),
+                        black_box(&DeckSeed([7; 32])),
+                        3,
+                    )
+                })
+            });
+        }
+    }
+}
+
+criterion_group!(benches, shuffle);
+criterion_main!(benches);
@@ -0,0 +1,340 @@
+//! Where the randomness behind a shuffle comes from.
+//!
+//! Every source yields a [`DeckSeed`], which drives the portable ChaCha20 generator of
+//! [`Deck::seeded_reading`](crate::model::Deck::seeded_reading), so a reading can always be
+//! replayed from its stored seed whatever the seed was made from.
+
+use crate::model::DeckSeed;
+use rand::TryRngCore;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Distinct samples user entropy needs before it is trusted to seed a shuffle.
+pub const MIN_USER_SAMPLES: usize = 16;
+
+const DEFAULT_BEACON_URL: &str = "https://beacon.nist.gov";
+
+/// A source of randomness for shuffling.
+pub trait EntropySource {
+    /// Produces the seed a deck is shuffled and drawn with, and how it was obtained.
+    fn seed(&self) -> impl Future<Output = Result<(DeckSeed, Entropy), EntropyError>> + Send;
+}
+
+/// How the seed of a reading was obtained; stored with the reading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "camelCase")]
+pub enum Entropy {
+    /// The operating system's random number generator.
+    Os,
+    /// A seed chosen by the caller.
+    Seeded,
+    /// Timings or other noise collected by the client.
+    User { samples: usize },
+    /// A public randomness beacon pulse, identified by its URI, and the salt that was hashed
+    /// with it; the salt is absent for readings made before seeds were salted.
+    Beacon {
+        pulse: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        salt: Option<DeckSeed>,
+    },
+    /// The server and client seeds of a verifiable draw.
+    Commitment,
+}
+
+/// The entropy source requested for a reading. Defaults to the OS generator.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "camelCase")]
+pub enum EntropyChoice {
+    #[default]
+    Os,
+    Seeded {
+        seed: DeckSeed,
+    },
+    User {
+        samples: Vec<u64>,
+    },
+    Beacon,
+}
+
+impl EntropyChoice {
+    /// Obtains a seed from the chosen source.
+    pub async fn seed(&self) -> Result<(DeckSeed, Entropy), EntropyError> {
+        match self {
+            EntropyChoice::Os => OsEntropy.seed().await,
+            EntropyChoice::Seeded { seed } => SeededEntropy(*seed).seed().await,
+            EntropyChoice::User { samples } => UserEntropy::new(samples.clone()).seed().await,
+            EntropyChoice::Beacon => BeaconEntropy::default().seed().await,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum EntropyError {
+    /// Fewer distinct user samples than [`MIN_USER_SAMPLES`].
+    NotEnoughSamples {
+        min: usize,
+    },
+    Os(String),
+    BeaconRequest(Arc<reqwest::Error>),
+    BeaconStatus(reqwest::StatusCode),
+    /// The beacon answered with something that is not a pulse.
+    InvalidPulse,
+}
+
+impl fmt::Display for EntropyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntropyError::NotEnoughSamples { min } => write!(
+                f,
+                "Entropia insuficiente: envie pelo menos {} amostras distintas.",
+                min
+            ),
+            EntropyError::Os(e) => write!(f, "Falha ao ler o gerador aleatório do sistema: {}", e),
+            EntropyError::BeaconRequest(e) => {
+                write!(f, "Falha ao consultar o farol de aleatoriedade: {}", e)
+            }
+            EntropyError::BeaconStatus(status) => {
+                write!(f, "O farol de aleatoriedade retornou erro ({}).", status)
+            }
+            EntropyError::InvalidPulse => {
+                write!(f, "O farol de aleatoriedade retornou um pulso inválido.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EntropyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EntropyError::BeaconRequest(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The operating system's random number generator.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OsEntropy;
+
+impl EntropySource for OsEntropy {
+    async fn seed(&self) -> Result<(DeckSeed, Entropy), EntropyError> {
+        let mut bytes = [0u8; 32];
+        OsRng
+            .try_fill_bytes(&mut bytes)
+            .map_err(|e| EntropyError::Os(e.to_string()))?;
+        Ok((DeckSeed(bytes), Entropy::Os))
+    }
+}
+
+/// A fixed seed, e.g. to replay a stored reading.
+#[derive(Clone, Copy, Debug)]
+pub struct SeededEntropy(pub DeckSeed);
+
+impl EntropySource for SeededEntropy {
+    async fn seed(&self) -> Result<(DeckSeed, Entropy), EntropyError> {
+        Ok((self.0, Entropy::Seeded))
+    }
+}
+
+/// Noise collected by the client, such as the intervals between taps or mouse moves, hashed
+/// into a seed.
+#[derive(Clone, Debug)]
+pub struct UserEntropy {
+    samples: Vec<u64>,
+}
+
+impl UserEntropy {
+    pub fn new(samples: Vec<u64>) -> Self {
+        Self { samples }
+    }
+}
+
+impl EntropySource for UserEntropy {
+    async fn seed(&self) -> Result<(DeckSeed, Entropy), EntropyError> {
+        if self.samples.iter().collect::<HashSet<_>>().len() < MIN_USER_SAMPLES {
+            return Err(EntropyError::NotEnoughSamples {
+                min: MIN_USER_SAMPLES,
+            });
+        }
+        let mut hasher = Sha256::new();
+        for sample in &self.samples {
+            hasher.update(sample.to_be_bytes());
+        }
+        Ok((
+            DeckSeed(hasher.finalize().into()),
+            Entropy::User {
+                samples: self.samples.len(),
+            },
+        ))
+    }
+}
+
+/// Client for a NIST-style randomness beacon (version 2.0 pulses). The seed is the SHA-256 of
+/// the latest pulse's output value and a random salt drawn for each reading, so readings made
+/// within one pulse differ and cannot be predicted from the public pulse. Anyone can fetch
+/// the recorded pulse and check the seed with [`beacon_seed`] and the recorded salt.
+#[derive(Clone, Debug)]
+pub struct BeaconEntropy {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl BeaconEntropy {
+    pub fn new(base_url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent("webtarot/0.1")
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+        Self { client, base_url }
+    }
+}
+
+impl Default for BeaconEntropy {
+    /// Uses `RANDOMNESS_BEACON_URL` when set, e.g. to point at a local stand-in, and the NIST
+    /// beacon otherwise.
+    fn default() -> Self {
+        Self::new(
+            std::env::var("RANDOMNESS_BEACON_URL")
+                .unwrap_or_else(|_| DEFAULT_BEACON_URL.to_string()),
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct BeaconResponse {
+    pulse: Pulse,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Pulse {
+    uri: String,
+    output_value: String,
+}
+
+impl EntropySource for BeaconEntropy {
+    async fn seed(&self) -> Result<(DeckSeed, Entropy), EntropyError> {
+        let endpoint = format!(
+            "{}/beacon/2.0/pulse/last",
+            self.base_url.trim_end_matches('/')
+        );
+        let resp = self
+            .client
+            .get(endpoint)
+            .send()
+            .await
+            .map_err(|e| EntropyError::BeaconRequest(Arc::new(e)))?;
+        if !resp.status().is_success() {
+            return Err(EntropyError::BeaconStatus(resp.status()));
+        }
+        let pulse = resp
+            .json::<BeaconResponse>()
+            .await
+            .map_err(|_| EntropyError::InvalidPulse)?
+            .pulse;
+        let output = pulse.output_value.to_ascii_lowercase();
+        if output.is_empty() || !output.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(EntropyError::InvalidPulse);
+        }
+        let (salt, _) = OsEntropy.seed().await?;
+        Ok((
+            beacon_seed(&output, &salt),
+            Entropy::Beacon {
+                pulse: pulse.uri,
+                salt: Some(salt),
+            },
+        ))
+    }
+}
+
+/// Seed of a beacon reading: the SHA-256 of the pulse's output value, as lowercase hex, and
+/// the reading's salt.
+pub fn beacon_seed(output_value: &str, salt: &DeckSeed) -> DeckSeed {
+    let mut hasher = Sha256::new();
+    hasher.update(output_value.to_ascii_lowercase().as_bytes());
+    hasher.update(salt.0);
+    DeckSeed(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn os_and_seeded_sources() {
+        let (first, entropy) = OsEntropy.seed().await.unwrap();
+        assert_eq!(Entropy::Os, entropy);
+        assert_ne!(first, OsEntropy.seed().await.unwrap().0);
+
+        let seed = DeckSeed([7; 32]);
+        assert_eq!(
+            (seed, Entropy::Seeded),
+            EntropyChoice::Seeded { seed }.seed().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn user_entropy_needs_distinct_samples() {
+        assert!(matches!(
+            UserEntropy::new(vec![42; 64]).seed().await,
+            Err(EntropyError::NotEnoughSamples { .. })
+        ));
+
+        let samples = (0..20).map(|i| i * 37 + 11).collect::<Vec<u64>>();
+        let (seed, entropy) = UserEntropy::new(samples.clone()).seed().await.unwrap();
+        assert_eq!(Entropy::User { samples: 20 }, entropy);
+        assert_eq!(seed, UserEntropy::new(samples).seed().await.unwrap().0);
+    }
+
+    #[tokio::test]
+    async fn beacon_seeds_from_the_latest_pulse() {
+        let mut server = Server::new_async().await;
+        let output = "A".repeat(128);
+        let pulse = server
+            .mock("GET", "/beacon/2.0/pulse/last")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "pulse": {
+                        "uri": "https://beacon.example/beacon/2.0/chain/1/pulse/42",
+                        "outputValue": output,
+                        "pulseIndex": 42
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+
+        let beacon = BeaconEntropy::new(server.url());
+        let (seed, entropy) = beacon.seed().await.unwrap();
+        pulse.assert();
+        let Entropy::Beacon { pulse, salt } = entropy else {
+            panic!("not a beacon seed: {:?}", entropy);
+        };
+        assert_eq!("https://beacon.example/beacon/2.0/chain/1/pulse/42", pulse);
+        assert_eq!(beacon_seed(&output, &salt.unwrap()), seed);
+
+        // Readings of the same pulse are salted apart
+        let (other, _) = beacon.seed().await.unwrap();
+        assert_ne!(seed, other);
+
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/beacon/2.0/pulse/last")
+            .with_status(200)
+            .with_body(json!({ "pulse": { "uri": "x", "outputValue": "not hex" } }).to_string())
+            .create();
+        assert!(matches!(
+            BeaconEntropy::new(server.url()).seed().await,
+            Err(EntropyError::InvalidPulse)
+        ));
+    }
+}
@@ -1,33 +1,43 @@
-use crate::model::Card;
+use crate::model::{ArcanaFilter, Card, DeckOptions, DeckTradition, Spread};
+use crate::provider::{ChunkSink, InterpretationProvider, Prompt, ProviderRegistry, Turn, Usage};
+use crate::resilience::{CircuitBreaker, ResilienceConfig};
+use crate::structured::StructuredInterpretation;
+use crate::style::{ReadingLength, ReadingStyle};
 use crate::t;
+use crate::template::{PromptTemplate, PromptTemplates};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::error::Error as StdError;
 use std::fmt;
 use std::sync::Arc;
-use std::time::Duration;
-
-#[derive(Deserialize)]
-struct ChatResponse {
-    choices: Vec<Choice>,
-}
-
-#[derive(Deserialize)]
-struct Choice {
-    message: Message,
-}
-
-#[derive(Deserialize)]
-struct Message {
-    content: String,
-}
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
 
 pub type ExplainResult = Result<String, ExplainError>;
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// Id of an interpretation provider, as sent by clients and stored with readings.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, EnumString, IntoStaticStr,
+)]
 #[serde(rename_all = "camelCase")]
+#[strum(ascii_case_insensitive)]
 pub enum InterpretationBackend {
+    #[strum(serialize = "chatGPT")]
     ChatGPT,
+    #[strum(serialize = "gemini")]
     Gemini,
+    #[strum(serialize = "claude")]
+    Claude,
+    /// A self-hosted model behind an OpenAI-compatible API, such as Ollama or llama.cpp.
+    #[strum(serialize = "local")]
+    Local,
+}
+
+impl InterpretationBackend {
+    pub fn id(&self) -> &'static str {
+        self.into()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -38,9 +48,40 @@ pub enum ExplainError {
     ApiError {
         status: reqwest::StatusCode,
         body: String,
+        /// How long the provider asked to wait before trying again, on 429 and 503.
+        retry_after: Option<Duration>,
     },
     ParseResponse(Arc<reqwest::Error>),
+    /// An event of a streamed answer could not be read.
+    InvalidEvent(Arc<serde_json::Error>),
     EmptyResponse,
+    /// The provider is not enabled on this server.
+    ProviderUnavailable(InterpretationBackend),
+    /// A request asked for a model the provider's allow-list does not have.
+    ModelNotAllowed {
+        backend: InterpretationBackend,
+        model: String,
+    },
+    /// The provider failed too often lately and is not called until its breaker closes.
+    CircuitOpen(InterpretationBackend),
+    /// A request asked for a prompt template this service does not have.
+    UnknownTemplate(String),
+}
+
+impl ExplainError {
+    /// Whether asking again may work: timeouts, dropped connections, rate limits and server
+    /// errors.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ExplainError::Request(_) => true,
+            ExplainError::ApiError { status, .. } => {
+                status.is_server_error()
+                    || *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    || *status == reqwest::StatusCode::REQUEST_TIMEOUT
+            }
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for ExplainError {
@@ -52,7 +93,7 @@ impl fmt::Display for ExplainError {
             ),
             ExplainError::HttpClientBuild(e) => write!(f, "Erro criando cliente HTTP: {}", e),
             ExplainError::Request(e) => write!(f, "Falha ao chamar a API do provedor LLM: {}", e),
-            ExplainError::ApiError { status, body } => {
+            ExplainError::ApiError { status, body, .. } => {
                 write!(
                     f,
                     "A API do provedor LLM retornou erro ({}): {}",
@@ -62,10 +103,30 @@ impl fmt::Display for ExplainError {
             ExplainError::ParseResponse(e) => {
                 write!(f, "Falha ao ler resposta do provedor LLM: {}", e)
             }
+            ExplainError::InvalidEvent(e) => {
+                write!(f, "Falha ao ler resposta do provedor LLM: {}", e)
+            }
             ExplainError::EmptyResponse => write!(
                 f,
                 "Não foi possível obter a interpretação das cartas no momento."
             ),
+            ExplainError::ProviderUnavailable(backend) => {
+                write!(f, "O provedor {} não está disponível.", backend.id())
+            }
+            ExplainError::ModelNotAllowed { backend, model } => write!(
+                f,
+                "O modelo {} não está liberado para o provedor {}.",
+                model,
+                backend.id()
+            ),
+            ExplainError::CircuitOpen(backend) => write!(
+                f,
+                "O provedor {} está instável e foi pausado; tente novamente em instantes.",
+                backend.id()
+            ),
+            ExplainError::UnknownTemplate(template) => {
+                write!(f, "O modelo de prompt {} não existe.", template)
+            }
         }
     }
 }
@@ -76,234 +137,526 @@ impl StdError for ExplainError {
             ExplainError::HttpClientBuild(e)
             | ExplainError::Request(e)
             | ExplainError::ParseResponse(e) => Some(e),
+            ExplainError::InvalidEvent(e) => Some(e),
             _ => None,
         }
     }
 }
 
+/// The reading handed over to [`InterpretationService::explain`].
+#[derive(Clone, Debug, Default)]
+pub struct ExplainRequest {
+    pub question: String,
+    pub context: Option<String>,
+    pub cards: Vec<Card>,
+    /// Cards that jumped out of the deck while shuffling, read apart from the positions.
+    pub jumpers: Vec<Card>,
+    pub tradition: DeckTradition,
+    pub deck_options: DeckOptions,
+    pub spread: Option<Spread>,
+    pub user_name: Option<String>,
+    pub user_self_description: Option<String>,
+    /// Set when `cards` are clarifiers drawn for one position of an earlier reading.
+    pub clarification: Option<Clarification>,
+    /// Model to interpret with, from the provider's allow-list; its default model when `None`.
+    pub model: Option<String>,
+    /// Prompt template to interpret with, as taken by [`PromptTemplates::get`]; the service's
+    /// default template when `None`.
+    pub template: Option<String>,
+    /// When the question was asked, for the model's sense of time; now when `None`.
+    pub asked_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// To answer a follow-up question instead of interpreting the reading: the interpretation,
+    /// earlier questions and answers, and the question, as in [`Prompt::conversation`].
+    pub conversation: Vec<Turn>,
+    /// Ask [`interpret`](InterpretationService::interpret) for a [`StructuredInterpretation`]
+    /// from providers capable of it. Follow-up questions are always answered in free text.
+    pub structured: bool,
+    /// The interpreter's approach, told to templates as `{{style}}`.
+    pub style: ReadingStyle,
+    /// How much the interpreter writes, told to templates as `{{length}}`.
+    pub length: ReadingLength,
+}
+
+/// The earlier reading that clarifier cards are read against.
+#[derive(Clone, Debug, Default)]
+pub struct Clarification {
+    /// Cards of the original reading, in spread order.
+    pub cards: Vec<Card>,
+    /// Index in `cards` of the position being clarified.
+    pub position: usize,
+    pub interpretation: String,
+}
+
+/// A call made to a provider for one interpretation.
+#[derive(Clone, Debug)]
+pub struct Attempt {
+    pub backend: InterpretationBackend,
+    /// What the call failed with; `None` for the call that answered.
+    pub error: Option<ExplainError>,
+}
+
+/// The outcome of [`InterpretationService::interpret`].
+#[derive(Clone, Debug)]
+pub struct Explanation {
+    pub result: ExplainResult,
+    /// The backend that answered, or the last one tried.
+    pub backend: InterpretationBackend,
+    /// The model that answered, or the last one tried; `None` when no provider was called.
+    pub model: Option<String>,
+    /// Tokens the answer took, when the provider reports them.
+    pub usage: Option<Usage>,
+    /// The sections of a structured interpretation, whose Markdown rendering is the result;
+    /// `None` when the interpretation was written as free text.
+    pub structured: Option<StructuredInterpretation>,
+    /// Every call made, retries and failovers included, in order. Calls turned away by an open
+    /// circuit breaker are not made and not listed.
+    pub attempts: Vec<Attempt>,
+}
+
 #[derive(Clone)]
 pub struct InterpretationService {
-    client: reqwest::Client,
-    openai_api_key: String,
-    google_api_key: String,
+    providers: ProviderRegistry,
+    resilience: ResilienceConfig,
+    breakers: Arc<HashMap<InterpretationBackend, CircuitBreaker>>,
+    templates: Arc<PromptTemplates>,
 }
 
 impl InterpretationService {
-    pub fn new(openai_api_key: String, google_api_key: String) -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent("webtarot/0.1")
-            .timeout(Duration::from_secs(120))
-            .build()
-            .unwrap();
+    pub fn new(providers: ProviderRegistry) -> Self {
+        Self::with_resilience(providers, ResilienceConfig::default())
+    }
+
+    pub fn with_resilience(providers: ProviderRegistry, resilience: ResilienceConfig) -> Self {
+        let breakers = providers
+            .list()
+            .into_iter()
+            .map(|info| (info.id, CircuitBreaker::new(resilience.breaker.clone())))
+            .collect();
         Self {
-            client,
-            openai_api_key,
-            google_api_key,
+            providers,
+            resilience,
+            breakers: Arc::new(breakers),
+            templates: Arc::new(PromptTemplates::default()),
         }
     }
 
+    /// Interprets with `templates` instead of the built-in ones.
+    pub fn with_templates(mut self, templates: PromptTemplates) -> Self {
+        self.templates = Arc::new(templates);
+        self
+    }
+
+    pub fn providers(&self) -> &ProviderRegistry {
+        &self.providers
+    }
+
+    /// The prompt template a request asking for `requested` is interpreted with.
+    pub fn template(&self, requested: Option<&str>) -> Result<&PromptTemplate, ExplainError> {
+        self.templates
+            .get(requested)
+            .ok_or_else(|| ExplainError::UnknownTemplate(requested.unwrap_or_default().to_string()))
+    }
+
+    /// The model `backend` interprets with when a request asks for `requested`.
+    pub fn model(
+        &self,
+        backend: &InterpretationBackend,
+        requested: Option<&str>,
+    ) -> Result<String, ExplainError> {
+        let provider = self
+            .providers
+            .get(backend)
+            .ok_or_else(|| ExplainError::ProviderUnavailable(backend.clone()))?;
+        provider
+            .models()
+            .resolve(requested)
+            .map(str::to_string)
+            .ok_or_else(|| ExplainError::ModelNotAllowed {
+                backend: backend.clone(),
+                model: requested.unwrap_or_default().to_string(),
+            })
+    }
+
     pub async fn explain(
         &self,
-        question: &str,
-        context: Option<String>,
-        cards: &[Card],
-        user_name: Option<String>,
-        user_self_description: Option<String>,
+        request: &ExplainRequest,
         backend: InterpretationBackend,
     ) -> ExplainResult {
-        let user =
-            Self::get_user_prompt(question, context, cards, user_name, user_self_description);
-
-        // Compose prompts
-        let system_prompt = t!("system.prompt");
-
-        match backend {
-            InterpretationBackend::ChatGPT => {
-                // Build HTTP client
-                let client = self.client.clone();
-                let key = self.openai_api_key.clone();
-
-                let body = serde_json::json!({
-                    "model": "gpt-5.1",
-                    "messages": [
-                        {"role": "system", "content": system_prompt},
-                        {"role": "user", "content": user}
-                    ]
-                });
-
-                // Allow overriding the base URL via env var for testing
-                let base_url = std::env::var("OPENAI_BASE_URL")
-                    .unwrap_or_else(|_| "https://api.openai.com".to_string());
-                let endpoint = format!("{}/v1/chat/completions", base_url.trim_end_matches('/'));
-
-                let req = client
-                    .post(endpoint)
-                    .bearer_auth(key)
-                    .header(reqwest::header::CONTENT_TYPE, "application/json")
-                    .timeout(Duration::from_mins(5))
-                    .json(&body);
-
-                let resp = match req.send().await {
-                    Ok(r) => r,
-                    Err(e) => return Err(ExplainError::Request(Arc::new(e))),
-                };
+        let (provider, prompt, model) = self.prepare(request, backend, request.model.as_deref())?;
+        provider
+            .complete(&prompt, &model)
+            .await
+            .map(|completion| completion.text)
+    }
 
-                if !resp.status().is_success() {
-                    let status = resp.status();
-                    let text = resp.text().await.unwrap_or_default();
-                    return Err(ExplainError::ApiError { status, body: text });
-                }
+    /// Like [`explain`](Self::explain), handing the interpretation to `on_chunk` piece by
+    /// piece as the model writes it. The result is the whole interpretation.
+    pub async fn explain_streaming(
+        &self,
+        request: &ExplainRequest,
+        backend: InterpretationBackend,
+        on_chunk: &ChunkSink<'_>,
+    ) -> ExplainResult {
+        let (provider, prompt, model) = self.prepare(request, backend, request.model.as_deref())?;
+        provider
+            .stream(&prompt, &model, on_chunk)
+            .await
+            .map(|completion| completion.text)
+    }
 
-                let parsed: ChatResponse = match resp.json().await {
-                    Ok(p) => p,
-                    Err(e) => return Err(ExplainError::ParseResponse(Arc::new(e))),
+    /// Like [`explain_streaming`](Self::explain_streaming), riding out a flaky provider:
+    /// transient errors are retried with backoff, a provider that keeps failing is left alone
+    /// for a while, and when `backend` cannot answer the failover backends are asked in turn.
+    /// Nothing is retried once part of the interpretation was handed to `on_chunk`.
+    ///
+    /// A structured interpretation is not streamed: `on_chunk` gets its Markdown rendering at
+    /// once, when it is done. When the answer does not follow the schema, the interpretation is
+    /// asked for again in free text and streamed; the tokens of both calls are counted.
+    pub async fn interpret(
+        &self,
+        request: &ExplainRequest,
+        backend: InterpretationBackend,
+        on_chunk: &ChunkSink<'_>,
+    ) -> Explanation {
+        // A follow-up question stays with the backend whose interpretation it follows
+        let failover = self
+            .resilience
+            .failover
+            .iter()
+            .filter(|candidate| **candidate != backend && request.conversation.is_empty());
+        let candidates = std::iter::once(backend.clone())
+            .chain(failover.cloned())
+            .collect::<Vec<_>>();
+        let started = Instant::now();
+        let budget = self.resilience.retry.max_elapsed;
+        let mut attempts = Vec::new();
+        let mut outcome = (backend, None, ExplainError::EmptyResponse);
+        for (index, candidate) in candidates.into_iter().enumerate() {
+            // The model asked for belongs to the backend asked for; the others use their default
+            let requested = if index == 0 {
+                request.model.as_deref()
+            } else {
+                None
+            };
+            let (provider, mut prompt, model) =
+                match self.prepare(request, candidate.clone(), requested) {
+                    Ok(prepared) => prepared,
+                    Err(error) if index == 0 => {
+                        outcome = (candidate, None, error);
+                        break;
+                    }
+                    // A failover backend that is not enabled is skipped
+                    Err(_) => continue,
                 };
-
-                if let Some(first) = parsed.choices.into_iter().next()
-                    && !first.message.content.trim().is_empty()
-                {
-                    return Ok(first.message.content);
-                }
-                Err(ExplainError::EmptyResponse)
+            if request.structured
+                && request.conversation.is_empty()
+                && provider.info().capabilities.structured_output
+            {
+                prompt.response_schema = Some(StructuredInterpretation::json_schema());
             }
-            InterpretationBackend::Gemini => {
-                // Google Generative Language API (Gemini)
-                let client = self.client.clone();
-                let key = self.google_api_key.clone();
-                if key.trim().is_empty() {
-                    return Err(ExplainError::MissingApiKey);
+            let breaker = &self.breakers[&candidate];
+            let streamed = AtomicBool::new(false);
+            let sink = |chunk: &str| {
+                streamed.store(true, Ordering::Relaxed);
+                on_chunk(chunk);
+            };
+            let mut retry = 0;
+            // Tokens of a structured answer that had to be asked for again in free text
+            let mut discarded: Option<Usage> = None;
+            let error = loop {
+                if !breaker.allow() {
+                    break ExplainError::CircuitOpen(candidate.clone());
                 }
-
-                // Allow overriding the base URL via env var for testing
-                let base_url = std::env::var("GOOGLE_AI_BASE_URL")
-                    .unwrap_or_else(|_| "https://generativelanguage.googleapis.com".to_string());
-                let endpoint = format!(
-                    "{}/v1beta/models/gemini-3-flash-preview:generateContent",
-                    base_url.trim_end_matches('/')
-                );
-
-                let body = serde_json::json!({
-                    "systemInstruction": {
-                        "role": "system",
-                        "parts": [{"text": system_prompt}]
-                    },
-                    "contents": [
-                        {
-                            "role": "user",
-                            "parts": [{"text": user}]
+                let completion = match prompt.response_schema {
+                    Some(_) => provider.complete(&prompt, &model).await,
+                    None => provider.stream(&prompt, &model, &sink).await,
+                };
+                let error = match completion {
+                    Ok(completion) => {
+                        breaker.record_success();
+                        attempts.push(Attempt {
+                            backend: candidate.clone(),
+                            error: None,
+                        });
+                        let usage = match (discarded, completion.usage) {
+                            (Some(discarded), Some(usage)) => Some(Usage {
+                                prompt_tokens: discarded.prompt_tokens + usage.prompt_tokens,
+                                completion_tokens: discarded.completion_tokens
+                                    + usage.completion_tokens,
+                            }),
+                            (discarded, usage) => usage.or(discarded),
+                        };
+                        let (text, structured) = match prompt.response_schema {
+                            Some(_) => match Self::structure(request, &completion.text) {
+                                Some((text, structured)) => (text, Some(structured)),
+                                None => {
+                                    prompt.response_schema = None;
+                                    discarded = usage;
+                                    continue;
+                                }
+                            },
+                            None => (completion.text, None),
+                        };
+                        if structured.is_some() {
+                            on_chunk(&text);
                         }
-                    ]
-                });
-
-                let req = client
-                    .post(endpoint)
-                    .query(&[("key", key.as_str())])
-                    .header(reqwest::header::CONTENT_TYPE, "application/json")
-                    .timeout(Duration::from_mins(5))
-                    .json(&body);
-
-                let resp = match req.send().await {
-                    Ok(r) => r,
-                    Err(e) => return Err(ExplainError::Request(Arc::new(e))),
+                        return Explanation {
+                            result: Ok(text),
+                            backend: candidate,
+                            model: Some(model),
+                            usage,
+                            structured,
+                            attempts,
+                        };
+                    }
+                    Err(error) => error,
                 };
-
-                if !resp.status().is_success() {
-                    let status = resp.status();
-                    let text = resp.text().await.unwrap_or_default();
-                    return Err(ExplainError::ApiError { status, body: text });
+                if error.is_transient() {
+                    breaker.record_failure();
+                } else {
+                    // The provider answered; the request was at fault
+                    breaker.record_success();
                 }
-
-                // Parse Gemini response
-                #[derive(Deserialize)]
-                struct GeminiPart {
-                    text: Option<String>,
-                }
-                #[derive(Deserialize)]
-                struct GeminiContent {
-                    parts: Option<Vec<GeminiPart>>,
-                }
-                #[derive(Deserialize)]
-                struct GeminiCandidate {
-                    content: Option<GeminiContent>,
-                }
-                #[derive(Deserialize)]
-                struct GeminiResponse {
-                    candidates: Option<Vec<GeminiCandidate>>,
-                }
-
-                let parsed: GeminiResponse = match resp.json().await {
-                    Ok(p) => p,
-                    Err(e) => return Err(ExplainError::ParseResponse(Arc::new(e))),
-                };
-
-                if let Some(cands) = parsed.candidates
-                    && let Some(first) = cands.into_iter().next()
-                    && let Some(content) = first.content
-                    && let Some(parts) = content.parts
-                    && let Some(part) = parts.into_iter().find_map(|p| p.text)
-                    && !part.trim().is_empty()
-                {
-                    return Ok(part);
+                attempts.push(Attempt {
+                    backend: candidate.clone(),
+                    error: Some(error.clone()),
+                });
+                retry += 1;
+                match self.resilience.retry.delay(retry, &error) {
+                    Some(delay)
+                        if !streamed.load(Ordering::Relaxed)
+                            && started.elapsed() + delay < budget =>
+                    {
+                        tokio::time::sleep(delay).await
+                    }
+                    _ => break error,
                 }
-                Err(ExplainError::EmptyResponse)
+            };
+            let fail_over = !streamed.load(Ordering::Relaxed)
+                && started.elapsed() < budget
+                && (error.is_transient() || matches!(error, ExplainError::CircuitOpen(_)));
+            outcome = (candidate, Some(model), error);
+            if !fail_over {
+                break;
             }
         }
+        let (backend, model, error) = outcome;
+        Explanation {
+            result: Err(error),
+            backend,
+            model,
+            usage: None,
+            structured: None,
+            attempts,
+        }
     }
 
-    fn get_user_prompt(
-        question: &str,
-        context: Option<String>,
-        cards: &[Card],
-        user_name: Option<String>,
-        user_self_description: Option<String>,
-    ) -> String {
-        // Prepare a concise, helpful prompt for the model with localized card names
-        let cards_list = cards
+    /// The Markdown rendering and sections of a structured answer to `request`; `None` when it
+    /// does not follow the schema.
+    fn structure(
+        request: &ExplainRequest,
+        text: &str,
+    ) -> Option<(String, StructuredInterpretation)> {
+        let structured = StructuredInterpretation::parse(text, request.cards.len())?;
+        let titles = request
+            .cards
             .iter()
             .enumerate()
-            .map(|(i, c)| format!("{}. {}", i + 1, c))
-            .collect::<Vec<_>>()
-            .join("\n");
+            .map(|(i, card)| Self::card_title(request, i, card))
+            .collect::<Vec<_>>();
+        let markdown = structured.to_markdown(&titles, &rust_i18n::locale());
+        Some((markdown, structured))
+    }
 
-        // Include current local date/time to provide temporal context to the model
-        let now = chrono::Local::now().to_rfc3339();
+    /// The provider, prompt and model to interpret `request` with.
+    fn prepare(
+        &self,
+        request: &ExplainRequest,
+        backend: InterpretationBackend,
+        requested_model: Option<&str>,
+    ) -> Result<(&dyn InterpretationProvider, Prompt, String), ExplainError> {
+        let model = self.model(&backend, requested_model)?;
+        let provider = self
+            .providers
+            .get(&backend)
+            .ok_or(ExplainError::ProviderUnavailable(backend))?;
+        let prompt = Prompt {
+            conversation: request.conversation.clone(),
+            ..self
+                .template(request.template.as_deref())?
+                .render(&rust_i18n::locale(), &Self::prompt_variables(request))
+        };
+        Ok((provider, prompt, model))
+    }
 
-        let label_now = t!("labels.now");
-        let label_question = t!("labels.question");
-        let label_user_name = t!("labels.user_name");
-        let label_user_self_description = t!("labels.user_self_description");
-        let label_cards = t!("labels.cards_in_order");
+    /// Describes a narrowed-down deck and a non-standard reversal policy, so the model does not
+    /// read meaning into absent cards or upright positions.
+    fn get_deck_options_prompt(request: &ExplainRequest) -> String {
+        let options = &request.deck_options;
+        let mut prompt = String::new();
+        if options.is_subset() {
+            let suits = options
+                .suits
+                .iter()
+                .map(|suit| suit.name(request.tradition))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let subset = match (options.arcana, suits.is_empty()) {
+                (ArcanaFilter::MajorOnly, _) => t!("deck_subset.major_only"),
+                (ArcanaFilter::MinorOnly, true) => t!("deck_subset.minor_only"),
+                (ArcanaFilter::MinorOnly, false) => t!("deck_subset.minor_suits", suits = suits),
+                (ArcanaFilter::All, _) => t!("deck_subset.all_suits", suits = suits),
+            };
+            prompt.push_str(&format!("{} {}\n", t!("labels.deck_subset"), subset));
+        }
+        if request.tradition.has_reversals() {
+            if !options.allows_reversals(request.tradition) {
+                prompt.push_str(&format!("{}\n", t!("labels.no_reversals")));
+            } else if options.reversal_probability != 0.5 {
+                let percent = (options.reversal_probability * 100.0).round();
+                prompt.push_str(&format!(
+                    "{}\n",
+                    t!("labels.reversal_probability", percent = percent)
+                ));
+            }
+        }
+        prompt
+    }
 
-        let mut user = format!(
-            "{} {}\n{} {}\n{}\n{}",
-            label_now, now, label_question, question, label_cards, cards_list
-        );
+    /// Numbered card list, labelled with the spread positions when there is a spread.
+    fn get_cards_list(cards: &[Card], request: &ExplainRequest) -> String {
+        cards
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let title = Self::card_title(request, i, c);
+                match request
+                    .spread
+                    .as_ref()
+                    .and_then(|spread| spread.positions.get(i))
+                {
+                    Some(position) => format!("{}. {}: {}", i + 1, title, position.description),
+                    None => format!("{}. {}", i + 1, title),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
-        if let Some(ctx) = context
-            && !ctx.trim().is_empty()
+    /// The card at index `i` of the reading, with the name of its spread position.
+    fn card_title(request: &ExplainRequest, i: usize, card: &Card) -> String {
+        let card = card.in_tradition(request.tradition);
+        match request
+            .spread
+            .as_ref()
+            .and_then(|spread| spread.positions.get(i))
         {
-            user.push('\n');
-            // Keep a simple, neutral label to avoid touching i18n catalogs for now.
-            user.push_str(&format!("Context: {}", ctx));
+            Some(position) => format!("{} — {}", card, position.name),
+            None => card.to_string(),
         }
+    }
 
-        if let Some(name) = user_name {
-            user.push('\n');
-            user.push_str(&format!("{} {}", label_user_name, name));
-        }
+    /// Lays out the original reading and its interpretation before the clarifier cards.
+    fn get_clarification_prompt(request: &ExplainRequest, clarification: &Clarification) -> String {
+        let original = Self::get_cards_list(&clarification.cards, request);
+        let clarified = original
+            .lines()
+            .nth(clarification.position)
+            .unwrap_or_default()
+            .to_string();
+        let clarifiers = request
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(i, c)| format!("{}. {}", i + 1, c.in_tradition(request.tradition)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "{}\n{}\n{}\n{}\n{} {}\n{}\n{}\n{}",
+            t!("labels.original_cards"),
+            original,
+            t!("labels.original_interpretation"),
+            clarification.interpretation.trim(),
+            t!("labels.clarified_position"),
+            clarified,
+            t!("labels.clarifier_cards"),
+            clarifiers,
+            t!("labels.clarifier_focus"),
+        )
+    }
 
-        if let Some(desc) = user_self_description {
-            user.push('\n');
-            user.push_str(&format!("{} {}", label_user_self_description, desc));
-        }
-        user
+    /// What a prompt template may fill in for `request`, as listed in
+    /// [`VARIABLES`](crate::template::VARIABLES).
+    fn prompt_variables(request: &ExplainRequest) -> HashMap<&'static str, String> {
+        let non_blank = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let jumpers = request
+            .jumpers
+            .iter()
+            .map(|c| format!("* {}", c.in_tradition(request.tradition)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let clarification = request
+            .clarification
+            .as_ref()
+            .map(|clarification| Self::get_clarification_prompt(request, clarification))
+            .unwrap_or_default();
+        HashMap::from([
+            // Temporal context for the model
+            (
+                "now",
+                request
+                    .asked_at
+                    .map_or_else(chrono::Local::now, |asked_at| {
+                        asked_at.with_timezone(&chrono::Local)
+                    })
+                    .to_rfc3339(),
+            ),
+            ("question", request.question.clone()),
+            ("deck", request.tradition.to_string()),
+            (
+                "deck_options",
+                Self::get_deck_options_prompt(request)
+                    .trim_end()
+                    .to_string(),
+            ),
+            (
+                "spread",
+                request
+                    .spread
+                    .as_ref()
+                    .map(|spread| spread.name.clone())
+                    .unwrap_or_default(),
+            ),
+            ("cards", Self::get_cards_list(&request.cards, request)),
+            ("clarification", clarification),
+            ("jumpers", jumpers),
+            ("context", non_blank(&request.context)),
+            ("user_name", non_blank(&request.user_name)),
+            (
+                "user_self_description",
+                non_blank(&request.user_self_description),
+            ),
+            ("style", request.style.prompt().unwrap_or_default()),
+            ("length", request.length.prompt().unwrap_or_default()),
+        ])
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{Arcana, Card, MajorArcana};
+    use crate::model::{Arcana, Card, LenormandCard, MajorArcana, Rank, SpreadLayout, Suit};
+    use crate::provider::local::LocalConfig;
+    use crate::provider::{
+        Capabilities, Completion, ModelConfig, ProviderConfig, ProviderFuture, ProviderInfo,
+    };
+    use crate::resilience::{BreakerConfig, RetryPolicy};
     use mockito::{Matcher, Server};
     use serde_json::json;
 
@@ -364,88 +717,649 @@ mod tests {
             )
             .create();
 
-        let svc = InterpretationService::new("test_key".into(), String::new());
+        let svc = InterpretationService::new(ProviderRegistry::from_config(&ProviderConfig {
+            openai_api_key: "test_key".into(),
+            ..Default::default()
+        }));
 
-        let cards = sample_cards();
+        let request = ExplainRequest {
+            question: "Will I get the job?".to_string(),
+            cards: sample_cards(),
+            user_name: Some("Alice".to_string()),
+            user_self_description: Some("A software engineer".to_string()),
+            ..Default::default()
+        };
         let result = svc
-            .explain(
-                "Will I get the job?",
-                None,
-                &cards,
-                Some("Alice".to_string()),
-                Some("A software engineer".to_string()),
-                InterpretationBackend::ChatGPT,
-            )
+            .explain(&request, InterpretationBackend::ChatGPT)
             .await
             .expect("explain should succeed");
 
         assert_eq!(result, mocked_text);
+
+        assert!(matches!(
+            svc.explain(&request, InterpretationBackend::Gemini).await,
+            Err(ExplainError::ProviderUnavailable(
+                InterpretationBackend::Gemini
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn explain_sends_the_reading_to_the_local_model() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", Matcher::Missing)
+            .match_body(Matcher::AllOf(vec![
+                Matcher::PartialJson(json!({"model": "llama3.2"})),
+                Matcher::Regex(r"Will I get the job\?".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"choices": [{"message": {"content": "Locally"}}]}).to_string())
+            .create();
+
+        let svc = InterpretationService::new(ProviderRegistry::from_config(&ProviderConfig {
+            local: Some(LocalConfig {
+                base_url: server.url(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let request = ExplainRequest {
+            question: "Will I get the job?".to_string(),
+            cards: sample_cards(),
+            ..Default::default()
+        };
+        assert_eq!(
+            "Locally",
+            svc.explain(&request, InterpretationBackend::Local)
+                .await
+                .unwrap()
+        );
+
+        let request = ExplainRequest {
+            model: Some("gpt-5.1".to_string()),
+            ..request
+        };
+        assert!(matches!(
+            svc.explain(&request, InterpretationBackend::Local).await,
+            Err(ExplainError::ModelNotAllowed { model, .. }) if model == "gpt-5.1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn interpret_asks_for_structured_output_and_renders_it() {
+        let mut server = Server::new_async().await;
+        let answer = json!({
+            "cards": [
+                { "number": 1, "analysis": "A new start." },
+                { "number": 2, "analysis": "Choices." }
+            ],
+            "synthesis": "Go.",
+            "emotionalImplications": "Relief.",
+            "advice": "Apply."
+        });
+        let _m = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(json!({
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": { "schema": StructuredInterpretation::json_schema() }
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"choices": [{"message": {"content": answer.to_string()}}]}).to_string(),
+            )
+            .create();
+
+        let svc = InterpretationService::new(ProviderRegistry::from_config(&ProviderConfig {
+            local: Some(LocalConfig {
+                base_url: server.url(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let request = ExplainRequest {
+            question: "Will I get the job?".to_string(),
+            cards: sample_cards(),
+            structured: true,
+            ..Default::default()
+        };
+        let chunks = std::sync::Mutex::new(Vec::new());
+        let on_chunk = |chunk: &str| chunks.lock().unwrap().push(chunk.to_string());
+        let explanation = svc
+            .interpret(&request, InterpretationBackend::Local, &on_chunk)
+            .await;
+        let structured = explanation.structured.unwrap();
+        assert_eq!(2, structured.cards.len());
+        assert_eq!("Apply.", structured.advice);
+        // The text is the sections in Markdown, handed over whole
+        let text = explanation.result.unwrap();
+        assert!(text.contains("## 2. "), "{}", text);
+        assert!(text.ends_with("Apply."), "{}", text);
+        assert_eq!(vec![text], *chunks.lock().unwrap());
+    }
+
+    /// A provider answering with `answers`, in order, and counting its calls.
+    struct Scripted {
+        backend: InterpretationBackend,
+        models: ModelConfig,
+        capabilities: Capabilities,
+        answers: std::sync::Mutex<std::collections::VecDeque<ExplainResult>>,
+    }
+
+    impl Scripted {
+        fn new(backend: InterpretationBackend, answers: Vec<ExplainResult>) -> Self {
+            Self {
+                models: ModelConfig::new(backend.default_model()),
+                backend,
+                capabilities: Default::default(),
+                answers: std::sync::Mutex::new(answers.into()),
+            }
+        }
+    }
+
+    impl InterpretationProvider for Scripted {
+        fn info(&self) -> ProviderInfo {
+            ProviderInfo {
+                id: self.backend.clone(),
+                name: self.backend.id().to_string(),
+                capabilities: self.capabilities.clone(),
+                models: self.models.models(),
+            }
+        }
+
+        fn models(&self) -> &ModelConfig {
+            &self.models
+        }
+
+        fn complete<'a>(&'a self, _prompt: &'a Prompt, _model: &'a str) -> ProviderFuture<'a> {
+            let answer = self.answers.lock().unwrap().pop_front();
+            Box::pin(async move {
+                answer.expect("no answer left").map(|text| Completion {
+                    text,
+                    usage: Some(Usage {
+                        prompt_tokens: 100,
+                        completion_tokens: 20,
+                    }),
+                })
+            })
+        }
+    }
+
+    fn unavailable(status: u16, retry_after: Option<u64>) -> ExplainResult {
+        Err(ExplainError::ApiError {
+            status: reqwest::StatusCode::from_u16(status).unwrap(),
+            body: String::new(),
+            retry_after: retry_after.map(Duration::from_secs),
+        })
+    }
+
+    fn fast_retries(failover: Vec<InterpretationBackend>) -> ResilienceConfig {
+        ResilienceConfig {
+            retry: RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(10),
+                max_elapsed: Duration::from_secs(60),
+            },
+            breaker: BreakerConfig {
+                failure_threshold: 3,
+                cooldown: Duration::from_secs(60),
+            },
+            failover,
+        }
+    }
+
+    #[tokio::test]
+    async fn interpret_retries_transient_errors() {
+        let mut providers = ProviderRegistry::new();
+        providers.register(Scripted::new(
+            InterpretationBackend::ChatGPT,
+            vec![
+                unavailable(503, None),
+                unavailable(429, Some(0)),
+                Ok("Third time".to_string()),
+                unavailable(400, None),
+            ],
+        ));
+        let svc = InterpretationService::with_resilience(providers, fast_retries(vec![]));
+        let explanation = svc
+            .interpret(&path_request(), InterpretationBackend::ChatGPT, &|_| {})
+            .await;
+        assert_eq!("Third time", explanation.result.unwrap());
+        assert_eq!(
+            Some(120),
+            explanation
+                .usage
+                .map(|usage| usage.prompt_tokens + usage.completion_tokens)
+        );
+        assert_eq!(InterpretationBackend::ChatGPT, explanation.backend);
+        assert_eq!(Some("gpt-5.1"), explanation.model.as_deref());
+        assert_eq!(3, explanation.attempts.len());
+        assert!(explanation.attempts[2].error.is_none());
+
+        // A request the provider refuses is not asked again
+        let explanation = svc
+            .interpret(&path_request(), InterpretationBackend::ChatGPT, &|_| {})
+            .await;
+        assert!(matches!(
+            explanation.result,
+            Err(ExplainError::ApiError { status, .. }) if status.as_u16() == 400
+        ));
+        assert_eq!(1, explanation.attempts.len());
+    }
+
+    #[tokio::test]
+    async fn interpret_asks_again_in_free_text_when_the_structured_answer_is_malformed() {
+        let mut providers = ProviderRegistry::new();
+        providers.register(Scripted {
+            capabilities: Capabilities {
+                streaming: false,
+                structured_output: true,
+            },
+            ..Scripted::new(
+                InterpretationBackend::ChatGPT,
+                vec![
+                    Ok(r#"{"cards": [{"number": 1, "analysis": "#.to_string()),
+                    Ok("In prose".to_string()),
+                ],
+            )
+        });
+        let svc = InterpretationService::with_resilience(providers, fast_retries(vec![]));
+        let request = ExplainRequest {
+            structured: true,
+            ..path_request()
+        };
+        let chunks = std::sync::Mutex::new(Vec::new());
+        let on_chunk = |chunk: &str| chunks.lock().unwrap().push(chunk.to_string());
+        let explanation = svc
+            .interpret(&request, InterpretationBackend::ChatGPT, &on_chunk)
+            .await;
+        assert_eq!("In prose", explanation.result.unwrap());
+        assert_eq!(None, explanation.structured);
+        assert_eq!(vec!["In prose".to_string()], *chunks.lock().unwrap());
+        // Both calls are paid for
+        assert_eq!(
+            Some(Usage {
+                prompt_tokens: 200,
+                completion_tokens: 40,
+            }),
+            explanation.usage
+        );
+        assert_eq!(2, explanation.attempts.len());
+    }
+
+    #[tokio::test]
+    async fn interpret_stops_retrying_once_the_budget_is_spent() {
+        let mut providers = ProviderRegistry::new();
+        providers.register(Scripted::new(
+            InterpretationBackend::ChatGPT,
+            vec![unavailable(503, None), Ok("Too late".to_string())],
+        ));
+        providers.register(Scripted::new(
+            InterpretationBackend::Claude,
+            vec![Ok("Too late".to_string())],
+        ));
+        let mut resilience = fast_retries(vec![InterpretationBackend::Claude]);
+        resilience.retry.max_elapsed = Duration::ZERO;
+        let svc = InterpretationService::with_resilience(providers, resilience);
+        let explanation = svc
+            .interpret(&path_request(), InterpretationBackend::ChatGPT, &|_| {})
+            .await;
+        assert!(matches!(
+            explanation.result,
+            Err(ExplainError::ApiError { status, .. }) if status.as_u16() == 503
+        ));
+        assert_eq!(1, explanation.attempts.len());
+    }
+
+    #[tokio::test]
+    async fn interpret_fails_over_and_opens_the_breaker() {
+        let mut providers = ProviderRegistry::new();
+        providers.register(Scripted::new(
+            InterpretationBackend::ChatGPT,
+            vec![
+                unavailable(500, None),
+                unavailable(502, None),
+                unavailable(503, None),
+            ],
+        ));
+        providers.register(Scripted::new(
+            InterpretationBackend::Claude,
+            vec![
+                Ok("From Claude".to_string()),
+                Ok("Claude again".to_string()),
+            ],
+        ));
+        let svc = InterpretationService::with_resilience(
+            providers,
+            // Gemini is not enabled and is skipped
+            fast_retries(vec![
+                InterpretationBackend::Gemini,
+                InterpretationBackend::Claude,
+            ]),
+        );
+        let request = ExplainRequest {
+            model: Some("gpt-5.1".to_string()),
+            ..path_request()
+        };
+
+        let explanation = svc
+            .interpret(&request, InterpretationBackend::ChatGPT, &|_| {})
+            .await;
+        assert_eq!("From Claude", explanation.result.unwrap());
+        assert_eq!(InterpretationBackend::Claude, explanation.backend);
+        assert_eq!(Some("claude-sonnet-4-5"), explanation.model.as_deref());
+        let tried = explanation
+            .attempts
+            .iter()
+            .map(|attempt| (attempt.backend.id(), attempt.error.is_some()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("chatGPT", true),
+                ("chatGPT", true),
+                ("chatGPT", true),
+                ("claude", false)
+            ],
+            tried
+        );
+
+        // ChatGPT's breaker is open now, so it is not even called
+        let explanation = svc
+            .interpret(&request, InterpretationBackend::ChatGPT, &|_| {})
+            .await;
+        assert_eq!("Claude again", explanation.result.unwrap());
+        assert_eq!(1, explanation.attempts.len());
+
+        // Without a failover, an open breaker fails the reading at once
+        let mut providers = ProviderRegistry::new();
+        providers.register(Scripted::new(
+            InterpretationBackend::Gemini,
+            vec![
+                unavailable(503, None),
+                unavailable(503, None),
+                unavailable(503, None),
+            ],
+        ));
+        let svc = InterpretationService::with_resilience(providers, fast_retries(vec![]));
+        let explanation = svc
+            .interpret(&path_request(), InterpretationBackend::Gemini, &|_| {})
+            .await;
+        assert_eq!(3, explanation.attempts.len());
+        let explanation = svc
+            .interpret(&path_request(), InterpretationBackend::Gemini, &|_| {})
+            .await;
+        assert!(matches!(
+            explanation.result,
+            Err(ExplainError::CircuitOpen(InterpretationBackend::Gemini))
+        ));
+        assert!(explanation.attempts.is_empty());
     }
 
     #[test]
-    fn get_user_prompt_cases() {
-        let cards = sample_cards();
+    fn prepare_renders_the_requested_template() {
+        let svc = InterpretationService::new(ProviderRegistry::from_config(&ProviderConfig {
+            openai_api_key: "test_key".into(),
+            ..Default::default()
+        }));
+        let (_, prompt, _) = svc
+            .prepare(
+                &ExplainRequest {
+                    template: Some("classic@1".to_string()),
+                    ..path_request()
+                },
+                InterpretationBackend::ChatGPT,
+                None,
+            )
+            .unwrap();
+        assert!(prompt.user.contains("What is my path?"), "{}", prompt.user);
+        assert!(!prompt.system.is_empty());
+        assert!(!prompt.user.contains("{{"), "{}", prompt.user);
+
+        assert!(matches!(
+            svc.prepare(
+                &ExplainRequest {
+                    template: Some("classic@9".to_string()),
+                    ..path_request()
+                },
+                InterpretationBackend::ChatGPT,
+                None,
+            ),
+            Err(ExplainError::UnknownTemplate(template)) if template == "classic@9"
+        ));
+    }
+
+    #[test]
+    fn style_and_length_are_told_to_the_system_prompt() {
+        let templates = PromptTemplates::default();
+        let system = |template: Option<&str>, request: &ExplainRequest| {
+            templates
+                .get(template)
+                .unwrap()
+                .render("en", &InterpretationService::prompt_variables(request))
+                .system
+        };
+        // The defaults are what the prompt was written for
+        assert_eq!(
+            system(Some("classic@1"), &path_request()),
+            system(None, &path_request())
+        );
+
+        let styled = ExplainRequest {
+            style: ReadingStyle::YesNo,
+            length: ReadingLength::Brief,
+            ..path_request()
+        };
+        let prompt = system(None, &styled);
+        assert!(
+            prompt.contains(&format!(
+                "\n\n{}\n\n{}",
+                ReadingStyle::YesNo.prompt().unwrap(),
+                ReadingLength::Brief.prompt().unwrap()
+            )),
+            "{}",
+            prompt
+        );
+    }
+
+    /// The user prompt of the default template, in English.
+    fn user_prompt(request: &ExplainRequest) -> String {
+        PromptTemplates::default()
+            .get(None)
+            .unwrap()
+            .render("en", &InterpretationService::prompt_variables(request))
+            .user
+    }
+
+    fn path_request() -> ExplainRequest {
+        ExplainRequest {
+            question: "What is my path?".to_string(),
+            cards: sample_cards(),
+            ..Default::default()
+        }
+    }
 
-        let prompt_no_user =
-            InterpretationService::get_user_prompt("What is my path?", None, &cards, None, None);
+    #[test]
+    fn get_user_prompt_cases() {
+        let prompt_no_user = user_prompt(&path_request());
         assert!(
-            prompt_no_user.contains(t!("labels.question").as_ref()),
+            prompt_no_user.contains("Question: What is my path?"),
             "should include localized question label"
         );
         assert!(
-            prompt_no_user.contains(t!("labels.cards_in_order").as_ref()),
+            prompt_no_user.contains("Cards (in order):"),
             "should include cards label"
         );
         assert!(
-            !prompt_no_user.contains(t!("labels.user_name").as_ref()),
+            !prompt_no_user.contains("Name:"),
             "should not include user name label"
         );
         assert!(prompt_no_user.contains("1."), "should enumerate cards");
 
-        let prompt_with_name = InterpretationService::get_user_prompt(
-            "What is my path?",
-            None,
-            &cards,
-            Some("Bob".into()),
-            None,
-        );
-        assert!(prompt_with_name.contains(&format!("{} Bob", t!("labels.user_name").as_ref())));
-        assert!(!prompt_with_name.contains(t!("labels.user_self_description").as_ref()));
-
-        let prompt_with_desc = InterpretationService::get_user_prompt(
-            "What is my path?",
-            None,
-            &cards,
-            None,
-            Some("Curious learner".into()),
-        );
-        assert!(prompt_with_desc.contains(&format!(
-            "{} Curious learner",
-            t!("labels.user_self_description").as_ref()
-        )));
+        let prompt_with_name = user_prompt(&ExplainRequest {
+            user_name: Some("Bob".into()),
+            ..path_request()
+        });
+        assert!(prompt_with_name.contains("Name: Bob"));
+        assert!(!prompt_with_name.contains("About me:"));
 
-        let prompt_with_both = InterpretationService::get_user_prompt(
-            "What is my path?",
-            None,
-            &cards,
-            Some("Carol".into()),
-            Some("Explorer".into()),
-        );
-        assert!(prompt_with_both.contains(&format!("{} Carol", t!("labels.user_name").as_ref())));
-        assert!(prompt_with_both.contains(&format!(
-            "{} Explorer",
-            t!("labels.user_self_description").as_ref()
-        )));
+        let prompt_with_desc = user_prompt(&ExplainRequest {
+            user_self_description: Some("Curious learner".into()),
+            ..path_request()
+        });
+        assert!(prompt_with_desc.contains("About me: Curious learner"));
+
+        let prompt_with_both = user_prompt(&ExplainRequest {
+            user_name: Some("Carol".into()),
+            user_self_description: Some("Explorer".into()),
+            ..path_request()
+        });
+        assert!(prompt_with_both.contains("Name: Carol"));
+        assert!(prompt_with_both.contains("About me: Explorer"));
 
         // Context inclusion
-        let prompt_with_context = InterpretationService::get_user_prompt(
-            "What is my path?",
-            Some("I'm switching careers soon".into()),
-            &cards,
-            None,
-            None,
-        );
+        let prompt_with_context = user_prompt(&ExplainRequest {
+            context: Some("I'm switching careers soon".into()),
+            ..path_request()
+        });
         assert!(prompt_with_context.contains("Context:"));
         assert!(prompt_with_context.contains("switching careers"));
     }
+
+    #[test]
+    fn get_user_prompt_lists_spread_positions() {
+        let spread = SpreadLayout::ThreeCard.spread();
+        let prompt = user_prompt(&ExplainRequest {
+            spread: Some(spread.clone()),
+            ..path_request()
+        });
+        assert!(prompt.contains(&format!("Spread: {}", spread.name)));
+        for (i, (card, position)) in sample_cards().iter().zip(&spread.positions).enumerate() {
+            assert!(prompt.contains(&format!(
+                "{}. {} — {}: {}",
+                i + 1,
+                card,
+                position.name,
+                position.description
+            )));
+        }
+    }
+
+    #[test]
+    fn get_user_prompt_names_cards_in_the_tradition() {
+        let prompt = user_prompt(&ExplainRequest {
+            tradition: DeckTradition::Thoth,
+            ..path_request()
+        });
+        assert!(prompt.contains(&format!("Deck: {}", DeckTradition::Thoth)));
+        for card in sample_cards() {
+            assert!(prompt.contains(&card.in_tradition(DeckTradition::Thoth).to_string()));
+        }
+
+        let card = Card {
+            arcana: Arcana::Lenormand {
+                card: LenormandCard::Fox,
+            },
+            flipped: false,
+        };
+        let prompt = user_prompt(&ExplainRequest {
+            tradition: DeckTradition::Lenormand,
+            cards: vec![card],
+            ..path_request()
+        });
+        assert!(prompt.contains(&format!("1. {}", card)));
+    }
+
+    #[test]
+    fn get_user_prompt_reads_clarifiers_against_the_original_reading() {
+        let spread = SpreadLayout::ThreeCard.spread();
+        let clarifier = Card {
+            arcana: Arcana::Minor {
+                rank: Rank::Ace,
+                suit: Suit::Cups,
+            },
+            flipped: false,
+        };
+        let prompt = user_prompt(&ExplainRequest {
+            cards: vec![clarifier],
+            spread: Some(spread.clone()),
+            clarification: Some(Clarification {
+                cards: sample_cards(),
+                position: 1,
+                interpretation: "The Magician reversed hints at doubt.".to_string(),
+            }),
+            ..path_request()
+        });
+        assert!(!prompt.contains("Cards (in order):"));
+        assert!(prompt.contains(t!("labels.original_cards").as_ref()));
+        assert!(prompt.contains("The Magician reversed hints at doubt."));
+        assert!(prompt.contains(&format!(
+            "{} 2. {} — {}: {}",
+            t!("labels.clarified_position"),
+            sample_cards()[1],
+            spread.positions[1].name,
+            spread.positions[1].description
+        )));
+        assert!(prompt.contains(&format!(
+            "{}\n1. {}",
+            t!("labels.clarifier_cards"),
+            clarifier
+        )));
+    }
+
+    #[test]
+    fn get_user_prompt_sets_jumpers_apart() {
+        let prompt = user_prompt(&path_request());
+        assert!(!prompt.contains("Jumper cards"));
+
+        let jumper = Card {
+            arcana: Arcana::Major {
+                name: MajorArcana::Tower,
+            },
+            flipped: true,
+        };
+        let prompt = user_prompt(&ExplainRequest {
+            jumpers: vec![jumper],
+            ..path_request()
+        });
+        assert!(prompt.contains(&format!("special emphasis):\n* {}", jumper)));
+        assert!(!prompt.contains(&format!("4. {}", jumper)));
+    }
+
+    #[test]
+    fn get_user_prompt_describes_deck_options() {
+        let prompt = user_prompt(&path_request());
+        assert!(!prompt.contains(t!("labels.deck_subset").as_ref()));
+        assert!(!prompt.contains(t!("labels.no_reversals").as_ref()));
+
+        let prompt = user_prompt(&ExplainRequest {
+            deck_options: DeckOptions {
+                arcana: ArcanaFilter::MinorOnly,
+                suits: vec![Suit::Cups],
+                reversal_probability: 0.0,
+                ..Default::default()
+            },
+            ..path_request()
+        });
+        assert!(prompt.contains(&format!(
+            "{} {}",
+            t!("labels.deck_subset"),
+            t!("deck_subset.minor_suits", suits = Suit::Cups)
+        )));
+        assert!(prompt.contains(t!("labels.no_reversals").as_ref()));
+
+        let prompt = user_prompt(&ExplainRequest {
+            deck_options: DeckOptions {
+                reversal_probability: 0.25,
+                ..Default::default()
+            },
+            ..path_request()
+        });
+        assert!(prompt.contains(t!("labels.reversal_probability", percent = 25).as_ref()));
+    }
 }
@@ -0,0 +1,209 @@
+//! Commit–reveal ("provably fair") draws.
+//!
+//! Before the question is asked the server picks a secret server seed and publishes only its
+//! SHA-256 commitment. The client then contributes its own seed, and the deck seed is derived
+//! from the server seed, the client seed and the question. Once the reading is made the server
+//! seed is revealed, so anyone can check it against the commitment and recompute the draw.
+
+use crate::model::{
+    Card, Deck, DeckOptions, DeckSeed, DeckTradition, SeededReading, ShuffleMethod,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// The revealed inputs of a commit–reveal draw.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairDraw {
+    /// Hex SHA-256 of the server seed, published before the question was asked.
+    pub commitment: String,
+    pub server_seed: DeckSeed,
+    pub client_seed: String,
+}
+
+/// Returns the hex commitment published for `server_seed`.
+pub fn commit(server_seed: &DeckSeed) -> String {
+    to_hex(&Sha256::digest(server_seed.0))
+}
+
+impl FairDraw {
+    pub fn new(server_seed: DeckSeed, client_seed: String) -> Self {
+        Self {
+            commitment: commit(&server_seed),
+            server_seed,
+            client_seed,
+        }
+    }
+
+    /// Derives the seed the deck is shuffled and drawn with. Every input is length-prefixed
+    /// so different splits of the same bytes cannot collide.
+    pub fn deck_seed(&self, question: &str) -> DeckSeed {
+        let mut hasher = Sha256::new();
+        hasher.update(self.server_seed.0);
+        for part in [self.client_seed.as_bytes(), question.as_bytes()] {
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part);
+        }
+        DeckSeed(hasher.finalize().into())
+    }
+
+    /// Shuffles and draws from `deck`, which must be fresh.
+    pub fn draw(&self, deck: Deck, question: &str, count: usize) -> SeededReading {
+        deck.seeded_reading(question, &self.deck_seed(question), count)
+    }
+}
+
+/// The parts of a stored reading needed to verify it. Deserializes directly from the
+/// `reading` object returned by the API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableReading {
+    pub question: String,
+    #[serde(default)]
+    pub tradition: DeckTradition,
+    #[serde(default)]
+    pub deck_options: DeckOptions,
+    pub shuffled_times: usize,
+    pub cards: Vec<Card>,
+    #[serde(default)]
+    pub jumpers: Vec<Card>,
+    /// Absent from readings made before the method was recorded, which shuffled every pass.
+    #[serde(default = "ShuffleMethod::legacy")]
+    pub shuffle: ShuffleMethod,
+    pub fair_draw: FairDraw,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The revealed server seed does not hash to the published commitment.
+    CommitmentMismatch,
+    /// Recomputing the draw from the revealed seeds gave different cards.
+    DrawMismatch,
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationError::CommitmentMismatch => write!(
+                f,
+                "A semente do servidor não corresponde ao compromisso publicado."
+            ),
+            VerificationError::DrawMismatch => write!(
+                f,
+                "As cartas recalculadas não correspondem às cartas da leitura."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+/// Checks the commitment and recomputes the draw, returning the recomputed reading when it
+/// matches the stored one.
+pub fn verify(reading: &VerifiableReading) -> Result<SeededReading, VerificationError> {
+    let fair_draw = &reading.fair_draw;
+    if commit(&fair_draw.server_seed) != fair_draw.commitment.to_ascii_lowercase() {
+        return Err(VerificationError::CommitmentMismatch);
+    }
+    let deck = Deck::build_with(reading.tradition, reading.deck_options.clone())
+        .with_shuffle(reading.shuffle);
+    let recomputed = fair_draw.draw(deck, &reading.question, reading.cards.len());
+    if recomputed.shuffled_times != reading.shuffled_times
+        || recomputed.cards != reading.cards
+        || recomputed.deck.jumpers != reading.jumpers
+    {
+        return Err(VerificationError::DrawMismatch);
+    }
+    Ok(recomputed)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifiable(fair_draw: FairDraw, question: &str) -> VerifiableReading {
+        verifiable_with(fair_draw, question, ShuffleMethod::SinglePass)
+    }
+
+    fn verifiable_with(
+        fair_draw: FairDraw,
+        question: &str,
+        shuffle: ShuffleMethod,
+    ) -> VerifiableReading {
+        let drawn = fair_draw.draw(Deck::build().with_shuffle(shuffle), question, 5);
+        VerifiableReading {
+            question: question.to_string(),
+            tradition: DeckTradition::RiderWaite,
+            deck_options: DeckOptions::default(),
+            shuffled_times: drawn.shuffled_times,
+            cards: drawn.cards,
+            jumpers: drawn.deck.jumpers,
+            shuffle,
+            fair_draw,
+        }
+    }
+
+    #[test]
+    fn commitment_is_sha256_of_server_seed() {
+        assert_eq!(
+            "66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925",
+            commit(&DeckSeed([0; 32]))
+        );
+    }
+
+    #[test]
+    fn verify_accepts_honest_draw() {
+        let reading = verifiable(
+            FairDraw::new(DeckSeed::random(), "client".to_string()),
+            "Will I move?",
+        );
+        let recomputed = verify(&reading).unwrap();
+        assert_eq!(reading.cards, recomputed.cards);
+
+        // Readings from before the shuffle method was recorded replay every pass
+        let legacy = verifiable_with(
+            FairDraw::new(DeckSeed::random(), "client".to_string()),
+            "Will I move?",
+            ShuffleMethod::EveryPass,
+        );
+        let mut json = serde_json::to_value(&legacy).unwrap();
+        json.as_object_mut().unwrap().remove("shuffle");
+        let reading: VerifiableReading = serde_json::from_value(json).unwrap();
+        assert_eq!(ShuffleMethod::EveryPass, reading.shuffle);
+        assert_eq!(legacy.cards, verify(&reading).unwrap().cards);
+    }
+
+    #[test]
+    fn verify_rejects_tampering() {
+        let mut reading = verifiable(
+            FairDraw::new(DeckSeed::random(), "client".to_string()),
+            "Will I move?",
+        );
+        let honest = reading.clone();
+
+        reading.fair_draw.server_seed = DeckSeed::random();
+        assert_eq!(Err(VerificationError::CommitmentMismatch), verify(&reading));
+
+        let mut reading = honest.clone();
+        reading.cards.swap(0, 1);
+        assert_eq!(Err(VerificationError::DrawMismatch), verify(&reading));
+
+        let mut reading = honest;
+        reading.question = "Will I stay?".to_string();
+        assert_eq!(Err(VerificationError::DrawMismatch), verify(&reading));
+    }
+
+    #[test]
+    fn client_seed_changes_the_deck_seed() {
+        let server_seed = DeckSeed::random();
+        let a = FairDraw::new(server_seed, "a".to_string());
+        let b = FairDraw::new(server_seed, "b".to_string());
+        assert_ne!(a.deck_seed("q"), b.deck_seed("q"));
+        assert_ne!(a.deck_seed("q"), a.deck_seed("q?"));
+    }
+}
@@ -0,0 +1,345 @@
+//! Checks that draws are fair: every card as likely to come up as any other in its deck, and
+//! reversed as often as the deck options say.
+//!
+//! [`FairnessAnalysis`] collects draws together with the deck they were drawn from and compares
+//! the counts with what fair draws would give. [`simulate_draws`] and [`simulate_readings`] feed
+//! it simulated draws; the backend feeds it stored readings.
+
+use crate::model::{Arcana, Card, Deck, DeckOptions};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Standard normal quantile of the 99.9% level at which counts are flagged as biased.
+const Z_CRITICAL: f64 = 3.090232;
+
+/// Counts of drawn cards and what fair draws would have given.
+#[derive(Debug, Clone, Default)]
+pub struct FairnessAnalysis {
+    draws: usize,
+    cards_drawn: usize,
+    cards: Vec<CardTally>,
+    index: HashMap<Arcana, usize>,
+}
+
+#[derive(Debug, Clone)]
+struct CardTally {
+    arcana: Arcana,
+    observed: usize,
+    expected: f64,
+    variance: f64,
+    reversed: usize,
+    reversible: usize,
+    expected_reversed: f64,
+    reversed_variance: f64,
+}
+
+impl FairnessAnalysis {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the cards of one draw (jumpers included) and `deck`, the unshuffled deck they
+    /// were drawn from.
+    pub fn record(&mut self, deck: &Deck, drawn: &[Card]) {
+        self.draws += 1;
+        self.cards_drawn += drawn.len();
+        let share = drawn.len() as f64 / deck.cards.len() as f64;
+        for card in &deck.cards {
+            let tally = self.tally(card.arcana);
+            tally.expected += share;
+            tally.variance += share * (1.0 - share);
+        }
+        let reversal = Some(deck.options.reversal_probability.min(1.0))
+            .filter(|_| deck.options.allows_reversals(deck.tradition));
+        for card in drawn {
+            let tally = self.tally(card.arcana);
+            tally.observed += 1;
+            if let Some(probability) = reversal {
+                tally.reversible += 1;
+                tally.reversed += card.flipped as usize;
+                tally.expected_reversed += probability;
+                tally.reversed_variance += probability * (1.0 - probability);
+            }
+        }
+    }
+
+    fn tally(&mut self, arcana: Arcana) -> &mut CardTally {
+        let next = self.cards.len();
+        let index = *self.index.entry(arcana).or_insert(next);
+        if index == next {
+            self.cards.push(CardTally {
+                arcana,
+                observed: 0,
+                expected: 0.0,
+                variance: 0.0,
+                reversed: 0,
+                reversible: 0,
+                expected_reversed: 0.0,
+                reversed_variance: 0.0,
+            });
+        }
+        &mut self.cards[index]
+    }
+
+    pub fn report(&self) -> FairnessReport {
+        let cards = self
+            .cards
+            .iter()
+            .map(|tally| CardFairness {
+                arcana: tally.arcana,
+                observed: tally.observed,
+                expected: tally.expected,
+                z_score: z_score(tally.observed, tally.expected, tally.variance),
+                reversed: tally.reversed,
+                expected_reversed: tally.expected_reversed,
+                reversed_z_score: z_score(
+                    tally.reversed,
+                    tally.expected_reversed,
+                    tally.reversed_variance,
+                ),
+            })
+            .collect::<Vec<_>>();
+
+        let (cards_chi_square, degrees_of_freedom) = chi_square(
+            self.cards
+                .iter()
+                .map(|tally| (tally.observed as f64, tally.expected)),
+        );
+        let reversible = self.cards.iter().map(|t| t.reversible).sum::<usize>();
+        let reversed = self.cards.iter().map(|t| t.reversed).sum::<usize>();
+        let expected_reversed = self.cards.iter().map(|t| t.expected_reversed).sum::<f64>();
+        let reversed_variance = self.cards.iter().map(|t| t.reversed_variance).sum::<f64>();
+        // Each card comes up reversed or upright, and its draws are fixed: one degree of
+        // freedom per card that could be reversed
+        let mut orientation_chi_square = 0.0;
+        let mut orientation_degrees_of_freedom = 0;
+        for tally in self.cards.iter().filter(|tally| tally.reversible > 0) {
+            let upright = (tally.reversible - tally.reversed) as f64;
+            let expected_upright = tally.reversible as f64 - tally.expected_reversed;
+            let (sum, degrees) = chi_square(
+                [
+                    (tally.reversed as f64, tally.expected_reversed),
+                    (upright, expected_upright),
+                ]
+                .into_iter(),
+            );
+            orientation_chi_square += sum;
+            orientation_degrees_of_freedom += degrees;
+        }
+        let orientation_z_score = z_score(reversed, expected_reversed, reversed_variance);
+        let orientation_critical_value = critical_value(orientation_degrees_of_freedom);
+        let orientation = OrientationFairness {
+            reversible,
+            reversed,
+            expected_reversed,
+            z_score: orientation_z_score,
+            chi_square: orientation_chi_square,
+            degrees_of_freedom: orientation_degrees_of_freedom,
+            critical_value: orientation_critical_value,
+            fair: orientation_chi_square <= orientation_critical_value
+                && orientation_z_score.abs() <= Z_CRITICAL,
+        };
+        FairnessReport {
+            draws: self.draws,
+            cards_drawn: self.cards_drawn,
+            chi_square: cards_chi_square,
+            degrees_of_freedom,
+            critical_value: critical_value(degrees_of_freedom),
+            fair: cards_chi_square <= critical_value(degrees_of_freedom) && orientation.fair,
+            cards,
+            orientation,
+        }
+    }
+}
+
+/// How far the draws are from fair, card by card and overall.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairnessReport {
+    pub draws: usize,
+    pub cards_drawn: usize,
+    /// Pearson's chi-square of the card counts against their expected counts.
+    pub chi_square: f64,
+    pub degrees_of_freedom: usize,
+    /// Chi-square above which the counts are flagged as biased, at the 99.9% level.
+    pub critical_value: f64,
+    /// Whether neither the card counts nor the orientations are flagged as biased.
+    pub fair: bool,
+    pub cards: Vec<CardFairness>,
+    pub orientation: OrientationFairness,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardFairness {
+    pub arcana: Arcana,
+    pub observed: usize,
+    pub expected: f64,
+    /// Standard score of the count: how many standard deviations it is from the expected.
+    pub z_score: f64,
+    pub reversed: usize,
+    pub expected_reversed: f64,
+    pub reversed_z_score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrientationFairness {
+    /// Cards drawn from decks that could reverse them.
+    pub reversible: usize,
+    pub reversed: usize,
+    pub expected_reversed: f64,
+    pub z_score: f64,
+    /// Chi-square of the upright and reversed counts of every card.
+    pub chi_square: f64,
+    pub degrees_of_freedom: usize,
+    pub critical_value: f64,
+    pub fair: bool,
+}
+
+/// Draws `draws` times from `deck` as it is, without shuffling it, so that any preference for
+/// some positions shows up as bias for the cards there. Cards are only turned by shuffling,
+/// so orientations are left out.
+pub fn simulate_draws<R: Rng + ?Sized>(
+    deck: &Deck,
+    count: usize,
+    draws: usize,
+    rng: &mut R,
+) -> FairnessReport {
+    let upright = Deck {
+        options: DeckOptions {
+            reversal_probability: 0.0,
+            ..deck.options.clone()
+        },
+        ..deck.clone()
+    };
+    let mut analysis = FairnessAnalysis::new();
+    for _ in 0..draws {
+        analysis.record(&upright, &deck.draw_with(count, rng));
+    }
+    analysis.report()
+}
+
+/// Shuffles a copy of the fresh `deck` and draws `count` cards from it, `readings` times.
+pub fn simulate_readings<R: Rng + ?Sized>(
+    deck: &Deck,
+    question: &str,
+    count: usize,
+    readings: usize,
+    rng: &mut R,
+) -> FairnessReport {
+    let mut analysis = FairnessAnalysis::new();
+    for _ in 0..readings {
+        let mut shuffled = deck.clone();
+        shuffled.shuffle_with(question, rng);
+        let mut drawn = shuffled.draw_with(count, rng);
+        drawn.extend(shuffled.jumpers);
+        analysis.record(deck, &drawn);
+    }
+    analysis.report()
+}
+
+fn z_score(observed: usize, expected: f64, variance: f64) -> f64 {
+    if variance > 0.0 {
+        (observed as f64 - expected) / variance.sqrt()
+    } else {
+        0.0
+    }
+}
+
+/// Pearson's chi-square over the cells that could be observed, and its degrees of freedom.
+fn chi_square(cells: impl Iterator<Item = (f64, f64)>) -> (f64, usize) {
+    let (sum, cells) = cells.filter(|(_, expected)| *expected > 0.0).fold(
+        (0.0, 0),
+        |(sum, cells), (observed, expected)| {
+            (sum + (observed - expected).powi(2) / expected, cells + 1)
+        },
+    );
+    (sum, cells.max(1) - 1)
+}
+
+/// Wilson–Hilferty approximation of the 99.9% quantile of chi-square with `degrees` degrees
+/// of freedom.
+fn critical_value(degrees: usize) -> f64 {
+    if degrees == 0 {
+        return 0.0;
+    }
+    let k = degrees as f64;
+    let spread = 2.0 / (9.0 * k);
+    k * (1.0 - spread + Z_CRITICAL * spread.sqrt()).powi(3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{DeckTradition, ShuffleMethod};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn critical_values_match_the_tables() {
+        assert!((critical_value(3) - 16.27).abs() < 0.3);
+        assert!((critical_value(77) - 121.1).abs() < 0.5);
+    }
+
+    #[test]
+    fn draws_reach_every_position_evenly() {
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        let report = simulate_draws(&Deck::build(), 3, 20_000, &mut rng);
+        assert_eq!(60_000, report.cards_drawn);
+        assert_eq!(77, report.degrees_of_freedom);
+        assert!(
+            report.fair,
+            "{} > {}",
+            report.chi_square, report.critical_value
+        );
+
+        // Drawing as readings shuffled every pass did never reaches the last card of a cut
+        let legacy = Deck::build().with_shuffle(ShuffleMethod::EveryPass);
+        let report = simulate_draws(&legacy, 3, 20_000, &mut rng);
+        assert!(!report.fair);
+        let last = report
+            .cards
+            .iter()
+            .find(|card| card.arcana == legacy.cards[77].arcana)
+            .unwrap();
+        assert_eq!(0, last.observed);
+    }
+
+    #[test]
+    fn readings_are_fair_in_cards_and_orientation() {
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        let options = DeckOptions {
+            reversal_probability: 0.3,
+            jumper_probability: 0.0002,
+            ..Default::default()
+        };
+        let deck = Deck::build_with(DeckTradition::Marseille, options);
+        let report = simulate_readings(&deck, "Will I get the job?", 5, 10_000, &mut rng);
+        assert!(report.fair, "{:?}", report);
+        assert!(
+            (report.orientation.reversed as f64 / report.cards_drawn as f64 - 0.3).abs() < 0.01
+        );
+
+        let lenormand = Deck::build_for(DeckTradition::Lenormand);
+        let report = simulate_readings(&lenormand, "Will I get the job?", 5, 2_000, &mut rng);
+        assert!(report.fair);
+        assert_eq!(0, report.orientation.reversible);
+    }
+
+    #[test]
+    fn analysis_flags_biased_orientation() {
+        let deck = Deck::build();
+        let mut analysis = FairnessAnalysis::new();
+        for i in 0..2_000 {
+            // Every card drawn in turn, always upright although the deck reverses half
+            analysis.record(&deck, &[deck.cards[i % 78]]);
+        }
+        let report = analysis.report();
+        assert!(report.chi_square < report.critical_value);
+        assert!(!report.orientation.fair);
+        assert!(!report.fair);
+        assert!(report.cards.iter().all(|card| card.reversed_z_score < -4.0));
+    }
+}
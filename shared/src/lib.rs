@@ -2,8 +2,19 @@
 // This makes the `t!` macro available to translate strings at runtime.
 rust_i18n::i18n!("locales");
 
+pub mod entropy;
 pub mod explain;
+pub mod fair;
+pub mod fairness;
+pub mod meaning;
 pub mod model;
+pub mod notation;
+pub mod pricing;
+pub mod provider;
+pub mod resilience;
+pub mod structured;
+pub mod style;
+pub mod template;
 
 // Re-export the `t!` macro so modules can `use crate::t`.
 pub use rust_i18n::t;
@@ -0,0 +1,488 @@
+use crate::model::{Arcana, DeckTradition, MajorArcana, Rank, Suit};
+use crate::t;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::LazyLock;
+
+/// Locale used when a meaning is requested in a language the corpus does not cover.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Localised texts for every tarot card, keyed by the same names as `card.major` and
+/// `card.suit`/`card.rank` in the locale files.
+static CORPORA: LazyLock<HashMap<&'static str, Corpus>> = LazyLock::new(|| {
+    [
+        ("en", include_str!("../meanings/en.json")),
+        ("pt", include_str!("../meanings/pt.json")),
+    ]
+    .into_iter()
+    .map(|(locale, json)| {
+        let corpus = serde_json::from_str(json)
+            .unwrap_or_else(|e| panic!("invalid meanings corpus for {}: {}", locale, e));
+        (locale, corpus)
+    })
+    .collect()
+});
+
+#[derive(Debug, Deserialize)]
+struct Corpus {
+    major: HashMap<String, MeaningText>,
+    minor: HashMap<String, HashMap<String, MeaningText>>,
+}
+
+/// Corpus for `locale` ("pt", "en", "pt-BR"...), falling back to English.
+fn corpus(locale: &str) -> &'static Corpus {
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    CORPORA
+        .get(language)
+        .unwrap_or_else(|| &CORPORA[DEFAULT_LOCALE])
+}
+
+impl Corpus {
+    fn text(&self, arcana: &Arcana) -> Option<&MeaningText> {
+        match arcana {
+            Arcana::Major { name } => self.major.get(&format!("{:?}", name)),
+            Arcana::Minor { rank, suit } => self
+                .minor
+                .get(&format!("{:?}", suit))
+                .and_then(|ranks| ranks.get(&format!("{:?}", rank))),
+            Arcana::Lenormand { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MeaningText {
+    upright: Orientation,
+    reversed: Orientation,
+    long: String,
+}
+
+/// What a card says when it falls in one orientation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Orientation {
+    pub keywords: Vec<String>,
+    pub short: String,
+}
+
+/// The traditional meaning of a tarot card: localised keywords and texts plus the
+/// correspondences (element, astrology, numerology) that do not depend on the language.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardMeaning {
+    pub upright: Orientation,
+    pub reversed: Orientation,
+    /// A short paragraph describing the card and how its reading changes when reversed.
+    pub long: String,
+    pub element: Element,
+    pub astrology: Astrology,
+    /// Rider–Waite number for the major arcana, pip value for the minor; none for courts.
+    pub numerology: Option<u8>,
+    /// Answer the card gives to a yes/no question when upright.
+    pub polarity: Polarity,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Element {
+    Fire,
+    Water,
+    Air,
+    Earth,
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", t!(format!("meaning.element.{:?}", self)))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Planet {
+    Sun,
+    Moon,
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+    Pluto,
+}
+
+impl Display for Planet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", t!(format!("meaning.planet.{:?}", self)))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum ZodiacSign {
+    Aries,
+    Taurus,
+    Gemini,
+    Cancer,
+    Leo,
+    Virgo,
+    Libra,
+    Scorpio,
+    Sagittarius,
+    Capricorn,
+    Aquarius,
+    Pisces,
+}
+
+impl Display for ZodiacSign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", t!(format!("meaning.sign.{:?}", self)))
+    }
+}
+
+/// Golden Dawn attribution: a planet, a sign, or a planet in the sign of its decan for
+/// the numbered minor arcana. Aces and pages are the roots of their element and have none.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Astrology {
+    pub planet: Option<Planet>,
+    pub sign: Option<ZodiacSign>,
+}
+
+impl Display for Astrology {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.planet, self.sign) {
+            (Some(planet), Some(sign)) => write!(
+                f,
+                "{}",
+                t!("meaning.planet_in_sign", planet = planet, sign = sign)
+            ),
+            (Some(planet), None) => write!(f, "{}", planet),
+            (None, Some(sign)) => write!(f, "{}", sign),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Polarity {
+    Yes,
+    No,
+    Maybe,
+}
+
+impl Display for Polarity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", t!(format!("meaning.polarity.{:?}", self)))
+    }
+}
+
+impl Arcana {
+    /// Meaning of a tarot card in `locale` ("pt", "en", "pt-BR"...), falling back to
+    /// English for languages without a corpus. Lenormand cards have no tarot meaning.
+    pub fn meaning(&self, locale: &str) -> Option<CardMeaning> {
+        let text = corpus(locale).text(self)?.clone();
+        let (element, astrology, numerology, polarity) = match self {
+            Arcana::Major { name } => (
+                name.element(),
+                name.astrology(),
+                Some(name.number(DeckTradition::RiderWaite)),
+                name.polarity(),
+            ),
+            Arcana::Minor { rank, suit } => (
+                suit.element(),
+                minor_astrology(*rank, *suit),
+                rank.value(),
+                minor_polarity(*rank, *suit),
+            ),
+            Arcana::Lenormand { .. } => return None,
+        };
+        Some(CardMeaning {
+            upright: text.upright,
+            reversed: text.reversed,
+            long: text.long,
+            element,
+            astrology,
+            numerology,
+            polarity,
+        })
+    }
+}
+
+impl MajorArcana {
+    pub fn element(&self) -> Element {
+        use MajorArcana::*;
+        match self {
+            Fool | Magician | Lovers | Justice | Star => Element::Air,
+            HighPriestess | Chariot | HangedMan | Death | Moon => Element::Water,
+            Empress | Hierophant | Hermit | Devil | World => Element::Earth,
+            Emperor | Strength | WheelOfFortune | Temperance | Tower | Sun | Judgement => {
+                Element::Fire
+            }
+        }
+    }
+
+    pub fn astrology(&self) -> Astrology {
+        use MajorArcana::*;
+        let (planet, sign) = match self {
+            Fool => (Some(Planet::Uranus), None),
+            Magician => (Some(Planet::Mercury), None),
+            HighPriestess => (Some(Planet::Moon), None),
+            Empress => (Some(Planet::Venus), None),
+            Emperor => (None, Some(ZodiacSign::Aries)),
+            Hierophant => (None, Some(ZodiacSign::Taurus)),
+            Lovers => (None, Some(ZodiacSign::Gemini)),
+            Chariot => (None, Some(ZodiacSign::Cancer)),
+            Strength => (None, Some(ZodiacSign::Leo)),
+            Hermit => (None, Some(ZodiacSign::Virgo)),
+            WheelOfFortune => (Some(Planet::Jupiter), None),
+            Justice => (None, Some(ZodiacSign::Libra)),
+            HangedMan => (Some(Planet::Neptune), None),
+            Death => (None, Some(ZodiacSign::Scorpio)),
+            Temperance => (None, Some(ZodiacSign::Sagittarius)),
+            Devil => (None, Some(ZodiacSign::Capricorn)),
+            Tower => (Some(Planet::Mars), None),
+            Star => (None, Some(ZodiacSign::Aquarius)),
+            Moon => (None, Some(ZodiacSign::Pisces)),
+            Sun => (Some(Planet::Sun), None),
+            Judgement => (Some(Planet::Pluto), None),
+            World => (Some(Planet::Saturn), None),
+        };
+        Astrology { planet, sign }
+    }
+
+    pub fn polarity(&self) -> Polarity {
+        use MajorArcana::*;
+        match self {
+            Death | Devil | Tower | Moon => Polarity::No,
+            HighPriestess | Hermit | Justice | HangedMan => Polarity::Maybe,
+            _ => Polarity::Yes,
+        }
+    }
+}
+
+impl Rank {
+    /// Pip value from 1 (ace) to 10; court cards have none.
+    pub fn value(&self) -> Option<u8> {
+        match self {
+            Rank::Page | Rank::Knight | Rank::Queen | Rank::King => None,
+            _ => Some(*self as u8 + 1),
+        }
+    }
+}
+
+impl Suit {
+    pub fn element(&self) -> Element {
+        match self {
+            Suit::Cups => Element::Water,
+            Suit::Pentacles => Element::Earth,
+            Suit::Swords => Element::Air,
+            Suit::Wands => Element::Fire,
+        }
+    }
+
+    /// Cardinal, fixed and mutable signs of the suit's element, in decan order.
+    fn signs(&self) -> [ZodiacSign; 3] {
+        use ZodiacSign::*;
+        match self {
+            Suit::Cups => [Cancer, Scorpio, Pisces],
+            Suit::Pentacles => [Capricorn, Taurus, Virgo],
+            Suit::Swords => [Libra, Aquarius, Gemini],
+            Suit::Wands => [Aries, Leo, Sagittarius],
+        }
+    }
+}
+
+fn minor_astrology(rank: Rank, suit: Suit) -> Astrology {
+    use Planet::*;
+    // Chaldean order, starting from Mars on the first decan of Aries.
+    const DECAN_RULERS: [Planet; 7] = [Mars, Sun, Venus, Mercury, Moon, Saturn, Jupiter];
+    let [cardinal, fixed, mutable] = suit.signs();
+    match rank.value() {
+        Some(pip @ 2..=10) => {
+            let sign = match pip {
+                2..=4 => cardinal,
+                5..=7 => fixed,
+                _ => mutable,
+            };
+            // Each sign holds three decans, counted through the zodiac from Aries.
+            let decan = sign as usize * 3 + (pip as usize - 2) % 3;
+            Astrology {
+                planet: Some(DECAN_RULERS[decan % 7]),
+                sign: Some(sign),
+            }
+        }
+        _ => Astrology {
+            planet: None,
+            sign: match rank {
+                Rank::Queen => Some(cardinal),
+                Rank::King => Some(fixed),
+                Rank::Knight => Some(mutable),
+                _ => None,
+            },
+        },
+    }
+}
+
+fn minor_polarity(rank: Rank, suit: Suit) -> Polarity {
+    use Polarity::*;
+    use Rank::*;
+    match (suit, rank) {
+        (Suit::Cups, Four | Seven) => Maybe,
+        (Suit::Cups, Five | Eight) => No,
+        (Suit::Pentacles, Two | Four | Seven) => Maybe,
+        (Suit::Pentacles, Five) => No,
+        (Suit::Swords, Ace | Six) => Yes,
+        (Suit::Swords, Two | Four | Page | Knight | Queen | King) => Maybe,
+        (Suit::Swords, _) => No,
+        (Suit::Wands, Seven | Nine) => Maybe,
+        (Suit::Wands, Five | Ten) => No,
+        _ => Yes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    fn assert_complete(meaning: &CardMeaning, arcana: &Arcana, locale: &str) {
+        for orientation in [&meaning.upright, &meaning.reversed] {
+            assert!(
+                !orientation.keywords.is_empty()
+                    && orientation.keywords.iter().all(|k| !k.trim().is_empty()),
+                "{:?} has empty keywords in {}",
+                arcana,
+                locale
+            );
+            assert!(
+                !orientation.short.trim().is_empty(),
+                "{:?} has no short meaning in {}",
+                arcana,
+                locale
+            );
+        }
+        assert!(
+            !meaning.long.trim().is_empty(),
+            "{:?} has no long meaning in {}",
+            arcana,
+            locale
+        );
+    }
+
+    #[test]
+    fn every_major_arcana_has_a_meaning() {
+        for locale in ["en", "pt"] {
+            for name in MajorArcana::iter() {
+                let arcana = Arcana::Major { name };
+                let meaning = arcana
+                    .meaning(locale)
+                    .unwrap_or_else(|| panic!("{:?} is missing in {}", name, locale));
+                assert_complete(&meaning, &arcana, locale);
+                assert!(meaning.astrology.planet.is_some() != meaning.astrology.sign.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn every_minor_arcana_has_a_meaning() {
+        for locale in ["en", "pt"] {
+            for suit in Suit::iter() {
+                for rank in Rank::iter() {
+                    let arcana = Arcana::Minor { rank, suit };
+                    let meaning = arcana.meaning(locale).unwrap_or_else(|| {
+                        panic!("{:?} of {:?} is missing in {}", rank, suit, locale)
+                    });
+                    assert_complete(&meaning, &arcana, locale);
+                    assert_eq!(suit.element(), meaning.element);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn corpora_have_no_unknown_cards() {
+        for (locale, corpus) in CORPORA.iter() {
+            assert_eq!(22, corpus.major.len(), "major arcana in {}", locale);
+            assert_eq!(4, corpus.minor.len(), "suits in {}", locale);
+            for ranks in corpus.minor.values() {
+                assert_eq!(14, ranks.len(), "ranks in {}", locale);
+            }
+        }
+    }
+
+    #[test]
+    fn meaning_falls_back_to_english() {
+        let fool = Arcana::Major {
+            name: MajorArcana::Fool,
+        };
+        let pt = fool.meaning("pt").unwrap();
+        assert_eq!(pt, fool.meaning("pt-BR").unwrap());
+        assert_ne!(pt.long, fool.meaning("en").unwrap().long);
+        assert_eq!(fool.meaning("en"), fool.meaning("fr"));
+        assert_eq!(Some(0), pt.numerology);
+    }
+
+    #[test]
+    fn minor_arcana_follow_the_decans() {
+        let two_of_wands = Arcana::Minor {
+            rank: Rank::Two,
+            suit: Suit::Wands,
+        };
+        let ten_of_pentacles = Arcana::Minor {
+            rank: Rank::Ten,
+            suit: Suit::Pentacles,
+        };
+        let knight_of_cups = Arcana::Minor {
+            rank: Rank::Knight,
+            suit: Suit::Cups,
+        };
+        let astrology = |arcana: Arcana| arcana.meaning("en").unwrap().astrology;
+        assert_eq!(
+            Astrology {
+                planet: Some(Planet::Mars),
+                sign: Some(ZodiacSign::Aries)
+            },
+            astrology(two_of_wands)
+        );
+        assert_eq!(
+            Astrology {
+                planet: Some(Planet::Mercury),
+                sign: Some(ZodiacSign::Virgo)
+            },
+            astrology(ten_of_pentacles)
+        );
+        assert_eq!(
+            Astrology {
+                planet: None,
+                sign: Some(ZodiacSign::Pisces)
+            },
+            astrology(knight_of_cups)
+        );
+        assert_eq!(Some(10), ten_of_pentacles.meaning("en").unwrap().numerology);
+        assert_eq!(None, knight_of_cups.meaning("en").unwrap().numerology);
+    }
+
+    #[test]
+    fn correspondences_are_translated() {
+        let astrology = Astrology {
+            planet: Some(Planet::Mars),
+            sign: Some(ZodiacSign::Aries),
+        };
+        assert_eq!("Mars in Aries", astrology.to_string());
+        assert_eq!("Water", Element::Water.to_string());
+        assert_eq!("Yes", Polarity::Yes.to_string());
+        assert_eq!("No", Polarity::No.to_string());
+    }
+
+    #[test]
+    fn lenormand_cards_have_no_tarot_meaning() {
+        let rider = Arcana::Lenormand {
+            card: crate::model::LenormandCard::Rider,
+        };
+        assert_eq!(None, rider.meaning("en"));
+    }
+}
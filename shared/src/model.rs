@@ -1,66 +1,440 @@
 use crate::t;
 use rand::distr::uniform::SampleRange;
 use rand::seq::SliceRandom;
-use rand::{random, random_range, rng};
+use rand::{Rng, SeedableRng, random, rng};
+use rand_chacha::ChaCha20Rng;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::str::FromStr;
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
 
 const MAX_SHUFFLES: usize = 7033;
-const MAX_DRAWS: usize = 13;
+pub const MAX_DRAWS: usize = 13;
+/// Most cards that can jump out of the deck during a single shuffle.
+pub const MAX_JUMPERS: usize = 3;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+/// The deck a reading is drawn from. The tarot traditions share the same 78 cards but
+/// number and name them differently; Lenormand is a separate 36-card deck.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Hash,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum DeckTradition {
+    #[default]
+    RiderWaite,
+    Marseille,
+    Thoth,
+    Lenormand,
+}
+
+impl DeckTradition {
+    pub fn id(&self) -> &'static str {
+        self.into()
+    }
+
+    pub fn card_count(&self) -> usize {
+        match self {
+            DeckTradition::Lenormand => 36,
+            _ => 78,
+        }
+    }
+
+    /// Lenormand cards are read upright only.
+    pub fn has_reversals(&self) -> bool {
+        !matches!(self, DeckTradition::Lenormand)
+    }
+}
+
+impl Display for DeckTradition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", t!(format!("card.tradition.{}", self.id())))
+    }
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    EnumString,
+    IntoStaticStr,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Hash,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ArcanaFilter {
+    #[default]
+    All,
+    MajorOnly,
+    MinorOnly,
+}
+
+/// Restricts which cards go into a deck and how often they come up reversed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeckOptions {
+    pub arcana: ArcanaFilter,
+    /// Suits kept among the minor arcana; every suit when empty.
+    pub suits: Vec<Suit>,
+    /// Chance of each card being reversed when shuffled; 0 never reverses.
+    pub reversal_probability: f64,
+    /// Chance of a card jumping out of the deck on each shuffle pass; 0, the default, never
+    /// does. A shuffle makes up to 7032 passes, so useful values are small (around 0.0002).
+    pub jumper_probability: f64,
+}
+
+impl Default for DeckOptions {
+    fn default() -> Self {
+        Self {
+            arcana: ArcanaFilter::All,
+            suits: Vec::new(),
+            reversal_probability: 0.5,
+            jumper_probability: 0.0,
+        }
+    }
+}
+
+impl DeckOptions {
+    pub fn includes(&self, arcana: &Arcana) -> bool {
+        match arcana {
+            Arcana::Major { .. } => self.arcana != ArcanaFilter::MinorOnly,
+            Arcana::Minor { suit, .. } => {
+                self.arcana != ArcanaFilter::MajorOnly
+                    && (self.suits.is_empty() || self.suits.contains(suit))
+            }
+            // Subsets are a tarot notion
+            Arcana::Lenormand { .. } => self.arcana == ArcanaFilter::All && self.suits.is_empty(),
+        }
+    }
+
+    /// Whether the deck is narrowed down from the tradition's full deck.
+    pub fn is_subset(&self) -> bool {
+        self.arcana != ArcanaFilter::All || !self.suits.is_empty()
+    }
+
+    pub fn allows_reversals(&self, tradition: DeckTradition) -> bool {
+        tradition.has_reversals() && self.reversal_probability > 0.0
+    }
+
+    /// Most cards a shuffle can set aside as jumpers.
+    pub fn max_jumpers(&self) -> usize {
+        if self.jumper_probability > 0.0 {
+            MAX_JUMPERS
+        } else {
+            0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DrawError {
+    InvalidReversalProbability,
+    InvalidJumperProbability,
+    /// The deck options leave too few cards to draw from.
+    NotEnoughCards,
+    TooManyCards {
+        max: usize,
+    },
+    /// Cut positions must be increasing and leave no pile empty.
+    InvalidCut,
+    InvalidPile,
+    /// Picked positions must be distinct and fall inside the chosen pile.
+    InvalidPick,
+    WrongPickCount {
+        expected: usize,
+    },
+}
+
+impl Display for DrawError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrawError::InvalidReversalProbability => {
+                write!(f, "A probabilidade de inversão deve estar entre 0 e 1.")
+            }
+            DrawError::InvalidJumperProbability => {
+                write!(
+                    f,
+                    "A probabilidade de cartas saltarem deve estar entre 0 e 1."
+                )
+            }
+            DrawError::NotEnoughCards => {
+                write!(f, "As opções escolhidas deixam cartas de menos no baralho.")
+            }
+            DrawError::TooManyCards { max } => {
+                write!(f, "Este baralho permite tirar no máximo {} cartas.", max)
+            }
+            DrawError::InvalidCut => write!(f, "Corte inválido do baralho."),
+            DrawError::InvalidPile => write!(f, "Monte inexistente."),
+            DrawError::InvalidPick => write!(f, "Posição inválida no monte escolhido."),
+            DrawError::WrongPickCount { expected } => {
+                write!(f, "Escolha exatamente {} cartas.", expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DrawError {}
+
+/// How a deck is shuffled. Both methods give the same distribution of decks, reversals and
+/// jumpers, but not the same deck for a given seed, so readings record theirs to replay.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    EnumString,
+    IntoStaticStr,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Hash,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ShuffleMethod {
+    /// A single pass, whatever the number of shuffles.
+    #[default]
+    SinglePass,
+    /// A full pass, turning every card again, for each shuffle counted; how readings were
+    /// shuffled (and drawn, see [`Deck::draw_with`]) before [`ShuffleMethod::SinglePass`].
+    EveryPass,
+}
+
+impl ShuffleMethod {
+    pub fn id(&self) -> &'static str {
+        self.into()
+    }
+
+    /// The method of readings stored before the method was recorded.
+    pub fn legacy() -> Self {
+        ShuffleMethod::EveryPass
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Deck {
+    pub tradition: DeckTradition,
+    pub options: DeckOptions,
     pub cards: Vec<Card>,
+    /// Cards that jumped out while shuffling; they are no longer in `cards`.
+    pub jumpers: Vec<Card>,
+    pub shuffle: ShuffleMethod,
 }
 
 impl Deck {
     pub fn build() -> Deck {
-        let mut cards = Vec::with_capacity(78);
-        for major in MajorArcana::iter() {
-            cards.push(Card {
-                arcana: Arcana::Major { name: major },
-                flipped: false,
-            })
-        }
-        for suit in Suit::iter() {
-            for rank in Rank::iter() {
-                cards.push(Card {
-                    arcana: Arcana::Minor { rank, suit },
-                    flipped: false,
-                })
+        Self::build_for(DeckTradition::RiderWaite)
+    }
+
+    pub fn build_for(tradition: DeckTradition) -> Deck {
+        Self::build_with(tradition, DeckOptions::default())
+    }
+
+    /// Builds an unshuffled deck with the major arcana in the tradition's numbering, keeping
+    /// only the cards `options` allows.
+    pub fn build_with(tradition: DeckTradition, options: DeckOptions) -> Deck {
+        let upright = |arcana| Card {
+            arcana,
+            flipped: false,
+        };
+        let mut cards = Vec::with_capacity(tradition.card_count());
+        if tradition == DeckTradition::Lenormand {
+            cards.extend(LenormandCard::iter().map(|card| upright(Arcana::Lenormand { card })));
+        } else {
+            let mut majors = MajorArcana::iter().collect::<Vec<_>>();
+            majors.sort_by_key(|major| major.number(tradition));
+            for major in majors {
+                cards.push(upright(Arcana::Major { name: major }))
             }
+            for suit in Suit::iter() {
+                for rank in Rank::iter() {
+                    cards.push(upright(Arcana::Minor { rank, suit }))
+                }
+            }
+        }
+        cards.retain(|card| options.includes(&card.arcana));
+        Deck {
+            tradition,
+            options,
+            cards,
+            jumpers: Vec::new(),
+            shuffle: ShuffleMethod::default(),
         }
-        Deck { cards }
+    }
+
+    /// Shuffles with `shuffle` instead, e.g. to replay a reading stored with another method.
+    pub fn with_shuffle(mut self, shuffle: ShuffleMethod) -> Deck {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Removes the cards already on the table, whichever way up they were drawn.
+    pub fn without(mut self, drawn: &[Card]) -> Deck {
+        self.cards
+            .retain(|card| !drawn.iter().any(|drawn| drawn.arcana == card.arcana));
+        self
+    }
+
+    /// Most cards a single reading can draw from this deck, leaving room for jumpers.
+    pub fn max_draws(&self) -> usize {
+        MAX_DRAWS.min(
+            self.cards
+                .len()
+                .saturating_sub(1 + self.options.max_jumpers()),
+        )
+    }
+
+    /// Checks the deck options and that `count` cards can be drawn.
+    pub fn validate_draw(&self, count: usize) -> Result<(), DrawError> {
+        if !(0.0..=1.0).contains(&self.options.reversal_probability) {
+            return Err(DrawError::InvalidReversalProbability);
+        }
+        if !(0.0..=1.0).contains(&self.options.jumper_probability) {
+            return Err(DrawError::InvalidJumperProbability);
+        }
+        if self.max_draws() == 0 {
+            return Err(DrawError::NotEnoughCards);
+        }
+        if count > self.max_draws() {
+            return Err(DrawError::TooManyCards {
+                max: self.max_draws(),
+            });
+        }
+        Ok(())
     }
 
     pub fn shuffle(&mut self, question: &str) -> usize {
-        let mut hasher = DefaultHasher::new();
-        question.hash(&mut hasher);
-        let question_hash = (hasher.finish() % (MAX_SHUFFLES as u64)) as usize;
-        let base_shuffle_count = (random::<u32>() % (MAX_SHUFFLES as u32)) as usize;
+        self.shuffle_with(question, &mut rng())
+    }
+
+    /// Shuffles using the given random number generator. The number of shuffles mixes the
+    /// question into the first value drawn from `rng`. With a jumper probability, each shuffle
+    /// may also set a card aside in `jumpers`, up to [`MAX_JUMPERS`].
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, question: &str, rng: &mut R) -> usize {
+        let question_hash = (stable_hash(question) % (MAX_SHUFFLES as u64)) as usize;
+        let base_shuffle_count = (rng.random::<u32>() % (MAX_SHUFFLES as u32)) as usize;
         let shuffles = (question_hash + base_shuffle_count) % MAX_SHUFFLES;
-        let mut rng = rng();
+        match self.shuffle {
+            ShuffleMethod::SinglePass => self.shuffle_once(shuffles, rng),
+            ShuffleMethod::EveryPass => self.shuffle_every_pass(shuffles, rng),
+        }
+        shuffles
+    }
+
+    /// Has the outcome of `shuffles` full passes in one: only the last uniform shuffle and the
+    /// last turn of each card show, and cards jump out of a uniformly shuffled deck, so the
+    /// jumpers are as good as the top cards of the final order.
+    fn shuffle_once<R: Rng + ?Sized>(&mut self, shuffles: usize, rng: &mut R) {
+        if shuffles == 0 {
+            return;
+        }
+        let jumpers = self.jumper_count(shuffles, rng).min(self.cards.len());
+        self.cards.shuffle(rng);
+        self.turn(rng);
+        let top = self.cards.len() - jumpers;
+        self.jumpers.extend(self.cards.drain(top..));
+    }
+
+    fn shuffle_every_pass<R: Rng + ?Sized>(&mut self, shuffles: usize, rng: &mut R) {
         for _ in 0..shuffles {
-            self.cards.shuffle(&mut rng);
+            self.cards.shuffle(rng);
+            self.turn(rng);
+            // Only consult `rng` when jumpers are on, so seeds recorded before replay the same
+            if self.jumpers.len() < self.options.max_jumpers()
+                && rng.random_bool(self.options.jumper_probability.min(1.0))
+            {
+                let jumper = self.cards.remove(rng.random_range(0..self.cards.len()));
+                self.jumpers.push(jumper);
+            }
+        }
+    }
+
+    /// Number of cards that jump out over `shuffles` passes. Each pass is a Bernoulli trial, so
+    /// the passes between jumps are geometric and only the jumps themselves are sampled.
+    fn jumper_count<R: Rng + ?Sized>(&self, shuffles: usize, rng: &mut R) -> usize {
+        let room = self
+            .options
+            .max_jumpers()
+            .saturating_sub(self.jumpers.len());
+        let miss = (-self.options.jumper_probability.min(1.0)).ln_1p();
+        let mut jumpers = 0;
+        let mut pass = 0usize;
+        while jumpers < room {
+            let gap = (((1.0 - rng.random::<f64>()).ln() / miss) as usize).saturating_add(1);
+            pass = pass.saturating_add(gap);
+            if pass > shuffles {
+                break;
+            }
+            jumpers += 1;
+        }
+        jumpers
+    }
+
+    /// Turns every card up or down by the reversal probability.
+    fn turn<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if self.options.allows_reversals(self.tradition) {
+            let probability = self.options.reversal_probability;
             for card in self.cards.iter_mut() {
-                card.flipped = random::<bool>();
+                card.flipped = reversed(probability, rng);
             }
         }
-        shuffles
     }
 
     pub fn draw(&self, count: usize) -> Vec<Card> {
+        self.draw_with(count, &mut rng())
+    }
+
+    /// Draws `count` distinct cards. The deck is cut in three and the cards come from one cut,
+    /// picked in proportion to its size so every position is equally likely, unless the deck
+    /// is too small for each cut to hold the whole draw.
+    pub fn draw_with<R: Rng + ?Sized>(&self, count: usize, rng: &mut R) -> Vec<Card> {
         let mut indices: Vec<usize> = Vec::with_capacity(count);
-        let slices = self.slice();
-        let slice = slices[random_range(0..3)];
+        let len = self.cards.len();
+        let min_cut = MAX_DRAWS.min(len / 4);
+        // Readings shuffled every pass chose the cut evenly and never drew the last card of
+        // it; they keep doing so to replay.
+        let legacy = self.shuffle == ShuffleMethod::EveryPass;
+        let slice = if count < min_cut && len > 8 + 3 * min_cut {
+            let [first, second, third] = self.slice(min_cut, rng);
+            if legacy {
+                [first, second, third][rng.random_range(0..3)]
+            } else {
+                let at = rng.random_range(0..len);
+                if at < first.len() {
+                    first
+                } else if at < first.len() + second.len() {
+                    second
+                } else {
+                    third
+                }
+            }
+        } else {
+            &self.cards[..]
+        };
+        let end = if legacy { slice.len() - 1 } else { slice.len() };
         for _ in 0..count {
-            let mut index = random_range(0..(slice.len() - 1)) as usize;
+            let mut index = rng.random_range(0..end);
             while indices.contains(&index) {
-                index = random_range(0..(slice.len() - 1)) as usize;
+                index = rng.random_range(0..end);
             }
             indices.push(index);
         }
@@ -68,13 +442,69 @@ impl Deck {
         indices.into_iter().map(|i| slice[i]).collect()
     }
 
-    fn slice(&self) -> [&[Card]; 3] {
-        let mut rng = rng();
-        let second_slice_start_index = (MAX_DRAWS..(70 - MAX_DRAWS * 2))
-            .sample_single(&mut rng)
+    /// Draws the cards picked by hand: the deck is cut into piles starting at each position
+    /// in `cuts`, and the cards at `picks` are taken, in that order, from pile `pile`.
+    pub fn pick(
+        &self,
+        count: usize,
+        cuts: &[usize],
+        pile: usize,
+        picks: &[usize],
+    ) -> Result<Vec<Card>, DrawError> {
+        let len = self.cards.len();
+        let mut bounds = Vec::with_capacity(cuts.len() + 2);
+        bounds.push(0);
+        bounds.extend_from_slice(cuts);
+        bounds.push(len);
+        if bounds.windows(2).any(|bound| bound[0] >= bound[1]) {
+            return Err(DrawError::InvalidCut);
+        }
+        let cards = pile
+            .checked_add(1)
+            .and_then(|end| bounds.get(pile..=end))
+            .map(|bound| &self.cards[bound[0]..bound[1]])
+            .ok_or(DrawError::InvalidPile)?;
+        if picks.len() != count {
+            return Err(DrawError::WrongPickCount { expected: count });
+        }
+        picks
+            .iter()
+            .enumerate()
+            .map(|(i, pick)| {
+                if picks[..i].contains(pick) {
+                    return Err(DrawError::InvalidPick);
+                }
+                cards.get(*pick).copied().ok_or(DrawError::InvalidPick)
+            })
+            .collect()
+    }
+
+    /// Shuffles this (fresh) deck and draws `count` cards with a generator seeded from `seed`.
+    /// The same deck, question, seed and count always yield the same deck order and cards.
+    pub fn seeded_reading(
+        mut self,
+        question: &str,
+        seed: &DeckSeed,
+        count: usize,
+    ) -> SeededReading {
+        let mut rng = seed.rng();
+        let shuffled_times = self.shuffle_with(question, &mut rng);
+        let cards = self.draw_with(count, &mut rng);
+        SeededReading {
+            deck: self,
+            shuffled_times,
+            cards,
+        }
+    }
+
+    fn slice<R: Rng + ?Sized>(&self, min_slice: usize, rng: &mut R) -> [&[Card]; 3] {
+        let len = self.cards.len();
+        let second_slice_start_index = (min_slice..(len - 8 - min_slice * 2))
+            .sample_single(rng)
             .unwrap();
-        let third_slice_start_index = ((second_slice_start_index + MAX_DRAWS)..(77 - MAX_DRAWS))
-            .sample_single(&mut rng)
+        let third_slice_start_index = ((second_slice_start_index + min_slice)
+            ..(len - 1 - min_slice))
+            .sample_single(rng)
             .unwrap();
         [
             &self.cards[0..second_slice_start_index],
@@ -90,6 +520,97 @@ impl Default for Deck {
     }
 }
 
+/// Keeps the `random::<bool>()` draw for even odds so seeds recorded before reversal
+/// probabilities were configurable replay identically.
+fn reversed<R: Rng + ?Sized>(probability: f64, rng: &mut R) -> bool {
+    if probability == 0.5 {
+        rng.random::<bool>()
+    } else {
+        rng.random_bool(probability.min(1.0))
+    }
+}
+
+/// FNV-1a: unlike `DefaultHasher`, its output is stable across Rust releases and platforms,
+/// which seeded readings rely on to be replayable.
+fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// 256-bit seed for the portable ChaCha20 generator used by seeded readings.
+/// Serialized as a lowercase hex string.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DeckSeed(pub [u8; 32]);
+
+impl DeckSeed {
+    pub fn random() -> Self {
+        Self(random())
+    }
+
+    pub fn rng(&self) -> ChaCha20Rng {
+        ChaCha20Rng::from_seed(self.0)
+    }
+}
+
+impl Display for DeckSeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidSeed;
+
+impl Display for InvalidSeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "a seed must be 64 hexadecimal characters")
+    }
+}
+
+impl std::error::Error for InvalidSeed {}
+
+impl FromStr for DeckSeed {
+    type Err = InvalidSeed;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `from_str_radix` would take a sign, so "+a" would read as "0a"
+        if s.len() != 64 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(InvalidSeed);
+        }
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| InvalidSeed)?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl TryFrom<String> for DeckSeed {
+    type Error = InvalidSeed;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<DeckSeed> for String {
+    fn from(value: DeckSeed) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeededReading {
+    pub deck: Deck,
+    pub shuffled_times: usize,
+    pub cards: Vec<Card>,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Card {
@@ -97,14 +618,40 @@ pub struct Card {
     pub flipped: bool,
 }
 
+impl Card {
+    /// Displays the card with the names used by `tradition`.
+    pub fn in_tradition(&self, tradition: DeckTradition) -> TraditionCard {
+        TraditionCard {
+            card: *self,
+            tradition,
+        }
+    }
+}
+
 impl Display for Card {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let flipped_suffix: Cow<'static, str> = if self.flipped {
+        write!(f, "{}", self.in_tradition(DeckTradition::RiderWaite))
+    }
+}
+
+pub struct TraditionCard {
+    card: Card,
+    tradition: DeckTradition,
+}
+
+impl Display for TraditionCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let flipped_suffix: Cow<'static, str> = if self.card.flipped {
             t!("card.flipped_suffix")
         } else {
             Cow::Borrowed("")
         };
-        write!(f, "{}{}", self.arcana, flipped_suffix)
+        write!(
+            f,
+            "{}{}",
+            self.card.arcana.name(self.tradition),
+            flipped_suffix
+        )
     }
 }
 
@@ -113,19 +660,48 @@ impl Display for Card {
 pub enum Arcana {
     Major { name: MajorArcana },
     Minor { rank: Rank, suit: Suit },
+    Lenormand { card: LenormandCard },
 }
 
-impl Display for Arcana {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+impl Arcana {
+    pub fn name(&self, tradition: DeckTradition) -> Cow<'static, str> {
+        self.name_in(tradition, &rust_i18n::locale())
+    }
+
+    /// Name of the card in `tradition`, translated to `locale` rather than the current one.
+    pub fn name_in(&self, tradition: DeckTradition, locale: &str) -> Cow<'static, str> {
         match self {
-            Arcana::Major { name, .. } => write!(f, "{}", name),
-            Arcana::Minor { rank, suit } => {
-                write!(f, "{}", t!("card.minor_format", rank = rank, suit = suit))
-            }
+            Arcana::Major { name } => name.name_in(tradition, locale),
+            Arcana::Minor { rank, suit } => t!(
+                "card.minor_format",
+                locale = locale,
+                rank = rank.name_in(tradition, locale),
+                suit = suit.name_in(tradition, locale)
+            ),
+            Arcana::Lenormand { card } => t!(format!("card.lenormand.{:?}", card), locale = locale),
         }
     }
 }
 
+impl Display for Arcana {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name(DeckTradition::RiderWaite))
+    }
+}
+
+/// Looks up `card.<tradition>.<section>.<key>`, for the names a tradition changes.
+fn tradition_name(
+    tradition: DeckTradition,
+    section: &str,
+    key: impl std::fmt::Debug,
+    locale: &str,
+) -> Option<Cow<'static, str>> {
+    crate::_rust_i18n_try_translate(
+        locale,
+        format!("card.{}.{}.{:?}", tradition.id(), section, key),
+    )
+}
+
 #[derive(Copy, Clone, EnumIter, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "camelCase")]
 pub enum MajorArcana {
@@ -153,6 +729,30 @@ pub enum MajorArcana {
     World,
 }
 
+impl MajorArcana {
+    /// Position in the tradition's numbering. Marseille and Thoth number Justice (Adjustment)
+    /// VIII and Strength (Lust) XI, the reverse of Rider–Waite.
+    pub fn number(&self, tradition: DeckTradition) -> u8 {
+        let rider_waite = MajorArcana::iter()
+            .position(|major| major == *self)
+            .unwrap() as u8;
+        match (tradition, self) {
+            (DeckTradition::Marseille | DeckTradition::Thoth, MajorArcana::Strength) => 11,
+            (DeckTradition::Marseille | DeckTradition::Thoth, MajorArcana::Justice) => 8,
+            _ => rider_waite,
+        }
+    }
+
+    pub fn name(&self, tradition: DeckTradition) -> Cow<'static, str> {
+        self.name_in(tradition, &rust_i18n::locale())
+    }
+
+    pub fn name_in(&self, tradition: DeckTradition, locale: &str) -> Cow<'static, str> {
+        tradition_name(tradition, "major", self, locale)
+            .unwrap_or_else(|| t!(format!("card.major.{:?}", self), locale = locale))
+    }
+}
+
 impl Display for MajorArcana {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let label = match self {
@@ -202,6 +802,17 @@ pub enum Rank {
     King,
 }
 
+impl Rank {
+    pub fn name(&self, tradition: DeckTradition) -> Cow<'static, str> {
+        self.name_in(tradition, &rust_i18n::locale())
+    }
+
+    pub fn name_in(&self, tradition: DeckTradition, locale: &str) -> Cow<'static, str> {
+        tradition_name(tradition, "rank", self, locale)
+            .unwrap_or_else(|| t!(format!("card.rank.{:?}", self), locale = locale))
+    }
+}
+
 impl Display for Rank {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let label = match self {
@@ -224,8 +835,9 @@ impl Display for Rank {
     }
 }
 
-#[derive(Copy, Clone, EnumIter, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[derive(Copy, Clone, EnumIter, EnumString, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
 pub enum Suit {
     Cups,
     Pentacles,
@@ -233,6 +845,17 @@ pub enum Suit {
     Wands,
 }
 
+impl Suit {
+    pub fn name(&self, tradition: DeckTradition) -> Cow<'static, str> {
+        self.name_in(tradition, &rust_i18n::locale())
+    }
+
+    pub fn name_in(&self, tradition: DeckTradition, locale: &str) -> Cow<'static, str> {
+        tradition_name(tradition, "suit", self, locale)
+            .unwrap_or_else(|| t!(format!("card.suit.{:?}", self), locale = locale))
+    }
+}
+
 impl Display for Suit {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let label = match self {
@@ -245,16 +868,472 @@ impl Display for Suit {
     }
 }
 
+/// The 36 cards of the Lenormand deck, in their traditional numbering.
+#[derive(Copy, Clone, EnumIter, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum LenormandCard {
+    Rider,
+    Clover,
+    Ship,
+    House,
+    Tree,
+    Clouds,
+    Snake,
+    Coffin,
+    Bouquet,
+    Scythe,
+    Whip,
+    Birds,
+    Child,
+    Fox,
+    Bear,
+    Stars,
+    Stork,
+    Dog,
+    Tower,
+    Garden,
+    Mountain,
+    Crossroads,
+    Mice,
+    Heart,
+    Ring,
+    Book,
+    Letter,
+    Man,
+    Woman,
+    Lily,
+    Sun,
+    Moon,
+    Key,
+    Fish,
+    Anchor,
+    Cross,
+}
+
+impl LenormandCard {
+    pub fn number(&self) -> u8 {
+        LenormandCard::iter()
+            .position(|card| card == *self)
+            .unwrap() as u8
+            + 1
+    }
+}
+
+impl Display for LenormandCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", t!(format!("card.lenormand.{:?}", self)))
+    }
+}
+
+/// A spread layout resolved to the current locale: each drawn card is read in the
+/// position with the same index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Spread {
+    pub id: String,
+    pub name: String,
+    pub positions: Vec<SpreadPosition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadPosition {
+    pub name: String,
+    pub description: String,
+    /// Optional layout coordinates used by clients to place the card when rendering.
+    #[serde(default)]
+    pub x: Option<f32>,
+    #[serde(default)]
+    pub y: Option<f32>,
+}
+
+impl Spread {
+    pub fn card_count(&self) -> usize {
+        self.positions.len()
+    }
+}
+
+#[derive(
+    Copy,
+    Clone,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Hash,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum SpreadLayout {
+    ThreeCard,
+    SituationActionOutcome,
+    MindBodySpirit,
+    FiveCardCross,
+    Horseshoe,
+    RelationshipCross,
+    CelticCross,
+}
+
+impl SpreadLayout {
+    pub fn id(&self) -> &'static str {
+        self.into()
+    }
+
+    /// Locale keys of each position, in drawing order.
+    fn position_keys(&self) -> &'static [&'static str] {
+        match self {
+            SpreadLayout::ThreeCard => &["past", "present", "future"],
+            SpreadLayout::SituationActionOutcome => &["situation", "action", "outcome"],
+            SpreadLayout::MindBodySpirit => &["mind", "body", "spirit"],
+            SpreadLayout::FiveCardCross => &["present", "past", "future", "cause", "potential"],
+            SpreadLayout::Horseshoe => &[
+                "past",
+                "present",
+                "hiddenInfluences",
+                "obstacles",
+                "externalInfluences",
+                "advice",
+                "outcome",
+            ],
+            SpreadLayout::RelationshipCross => {
+                &["you", "partner", "connection", "challenge", "outcome"]
+            }
+            SpreadLayout::CelticCross => &[
+                "present",
+                "challenge",
+                "foundation",
+                "recentPast",
+                "potential",
+                "nearFuture",
+                "self",
+                "environment",
+                "hopesAndFears",
+                "outcome",
+            ],
+        }
+    }
+
+    pub fn spread(&self) -> Spread {
+        let id = self.id();
+        let positions = self
+            .position_keys()
+            .iter()
+            .map(|key| SpreadPosition {
+                name: t!(format!("spread.position.{}.name", key)).to_string(),
+                description: t!(format!("spread.position.{}.description", key)).to_string(),
+                x: None,
+                y: None,
+            })
+            .collect();
+        Spread {
+            id: id.to_string(),
+            name: t!(format!("spread.layout.{}", id)).to_string(),
+            positions,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn pick_takes_cards_from_the_chosen_pile() {
+        let deck = Deck::build();
+        let picked = deck.pick(3, &[20, 50], 1, &[0, 29, 5]).unwrap();
+        assert_eq!(vec![deck.cards[20], deck.cards[49], deck.cards[25]], picked);
+        let whole = deck.pick(1, &[], 0, &[77]).unwrap();
+        assert_eq!(vec![deck.cards[77]], whole);
+
+        assert_eq!(Err(DrawError::InvalidCut), deck.pick(1, &[50, 20], 0, &[0]));
+        assert_eq!(Err(DrawError::InvalidCut), deck.pick(1, &[0], 0, &[0]));
+        assert_eq!(Err(DrawError::InvalidCut), deck.pick(1, &[78], 0, &[0]));
+        assert_eq!(
+            Err(DrawError::InvalidPile),
+            deck.pick(1, &[20, 50], 3, &[0])
+        );
+        assert_eq!(
+            Err(DrawError::InvalidPile),
+            deck.pick(1, &[20, 50], usize::MAX, &[0])
+        );
+        assert_eq!(
+            Err(DrawError::InvalidPick),
+            deck.pick(1, &[20, 50], 1, &[30])
+        );
+        assert_eq!(
+            Err(DrawError::InvalidPick),
+            deck.pick(2, &[20, 50], 1, &[4, 4])
+        );
+        assert_eq!(
+            Err(DrawError::WrongPickCount { expected: 3 }),
+            deck.pick(3, &[20, 50], 1, &[4])
+        );
+    }
+
     #[test]
     fn deck_build_works() {
         let deck = Deck::build();
         assert_eq!(78, deck.cards.len());
     }
 
+    #[test]
+    fn tradition_decks_are_numbered_by_tradition() {
+        let rider_waite = Deck::build_for(DeckTradition::RiderWaite);
+        assert_eq!(Deck::build(), rider_waite);
+        let strength = Arcana::Major {
+            name: MajorArcana::Strength,
+        };
+        assert_eq!(strength, rider_waite.cards[8].arcana);
+
+        for tradition in [DeckTradition::Marseille, DeckTradition::Thoth] {
+            let deck = Deck::build_for(tradition);
+            assert_eq!(78, deck.cards.len());
+            assert_eq!(
+                Arcana::Major {
+                    name: MajorArcana::Justice
+                },
+                deck.cards[8].arcana
+            );
+            assert_eq!(strength, deck.cards[11].arcana);
+        }
+
+        let lenormand = Deck::build_for(DeckTradition::Lenormand);
+        assert_eq!(36, lenormand.cards.len());
+        assert_eq!(1, LenormandCard::Rider.number());
+        assert_eq!(36, LenormandCard::Cross.number());
+    }
+
+    #[test]
+    fn tradition_names_fall_back_to_rider_waite() {
+        let strength = MajorArcana::Strength;
+        assert_eq!(
+            strength.to_string(),
+            strength.name(DeckTradition::RiderWaite)
+        );
+        assert_ne!(strength.to_string(), strength.name(DeckTradition::Thoth));
+        assert_ne!(
+            MajorArcana::Tower.to_string(),
+            MajorArcana::Tower.name(DeckTradition::Marseille)
+        );
+        assert_eq!(
+            MajorArcana::Moon.to_string(),
+            MajorArcana::Moon.name(DeckTradition::Thoth)
+        );
+        assert_ne!(
+            Rank::King.to_string(),
+            Rank::King.name(DeckTradition::Thoth)
+        );
+        assert_ne!(
+            Suit::Pentacles.to_string(),
+            Suit::Pentacles.name(DeckTradition::Thoth)
+        );
+        assert_eq!(
+            Suit::Cups.to_string(),
+            Suit::Cups.name(DeckTradition::Thoth)
+        );
+    }
+
+    #[test]
+    fn deck_options_filter_cards() {
+        let majors = Deck::build_with(
+            DeckTradition::RiderWaite,
+            DeckOptions {
+                arcana: ArcanaFilter::MajorOnly,
+                ..Default::default()
+            },
+        );
+        assert_eq!(22, majors.cards.len());
+
+        let swords_and_cups = Deck::build_with(
+            DeckTradition::Thoth,
+            DeckOptions {
+                arcana: ArcanaFilter::MinorOnly,
+                suits: vec![Suit::Swords, Suit::Cups],
+                ..Default::default()
+            },
+        );
+        assert_eq!(28, swords_and_cups.cards.len());
+        assert!(swords_and_cups.cards.iter().all(|card| matches!(
+            card.arcana,
+            Arcana::Minor {
+                suit: Suit::Swords | Suit::Cups,
+                ..
+            }
+        )));
+
+        let with_suit = Deck::build_with(
+            DeckTradition::RiderWaite,
+            DeckOptions {
+                suits: vec![Suit::Wands],
+                ..Default::default()
+            },
+        );
+        assert_eq!(22 + 14, with_suit.cards.len());
+    }
+
+    #[test]
+    fn small_decks_draw_from_the_whole_deck() {
+        let deck = Deck::build_with(
+            DeckTradition::RiderWaite,
+            DeckOptions {
+                arcana: ArcanaFilter::MajorOnly,
+                ..Default::default()
+            },
+        );
+        let reading = deck.seeded_reading("this is a question", &DeckSeed::random(), 10);
+        assert_eq!(10, reading.cards.len());
+        let drawn = reading
+            .cards
+            .iter()
+            .map(|c| c.arcana)
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(10, drawn.len());
+    }
+
+    #[test]
+    fn without_removes_drawn_cards_either_way_up() {
+        let drawn = vec![
+            Card {
+                arcana: Arcana::Major {
+                    name: MajorArcana::Tower,
+                },
+                flipped: true,
+            },
+            Card {
+                arcana: Arcana::Minor {
+                    rank: Rank::Ace,
+                    suit: Suit::Cups,
+                },
+                flipped: false,
+            },
+        ];
+        let remainder = Deck::build().without(&drawn);
+        assert_eq!(76, remainder.cards.len());
+        assert!(
+            remainder
+                .cards
+                .iter()
+                .all(|card| drawn.iter().all(|drawn| drawn.arcana != card.arcana))
+        );
+    }
+
+    #[test]
+    fn reversal_probability_is_respected() {
+        let never = DeckOptions {
+            reversal_probability: 0.0,
+            ..Default::default()
+        };
+        let reading = Deck::build_with(DeckTradition::RiderWaite, never).seeded_reading(
+            "this is a question",
+            &DeckSeed::random(),
+            10,
+        );
+        assert!(reading.deck.cards.iter().all(|card| !card.flipped));
+
+        let always = DeckOptions {
+            reversal_probability: 1.0,
+            ..Default::default()
+        };
+        let reading = Deck::build_with(DeckTradition::RiderWaite, always).seeded_reading(
+            "this is a question",
+            &DeckSeed::random(),
+            10,
+        );
+        // A question hashing to zero shuffles would leave the deck untouched
+        if reading.shuffled_times > 0 {
+            assert!(reading.deck.cards.iter().all(|card| card.flipped));
+        }
+    }
+
+    #[test]
+    fn validate_draw_checks_options_and_count() {
+        assert_eq!(Ok(()), Deck::build().validate_draw(MAX_DRAWS));
+        assert_eq!(
+            Err(DrawError::TooManyCards { max: MAX_DRAWS }),
+            Deck::build().validate_draw(MAX_DRAWS + 1)
+        );
+        let invalid = DeckOptions {
+            reversal_probability: 1.5,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(DrawError::InvalidReversalProbability),
+            Deck::build_with(DeckTradition::RiderWaite, invalid).validate_draw(3)
+        );
+        let empty = DeckOptions {
+            arcana: ArcanaFilter::MajorOnly,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(DrawError::NotEnoughCards),
+            Deck::build_with(DeckTradition::Lenormand, empty).validate_draw(3)
+        );
+        let jumpers = DeckOptions {
+            arcana: ArcanaFilter::MinorOnly,
+            suits: vec![Suit::Cups],
+            jumper_probability: 0.001,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(DrawError::TooManyCards {
+                max: 14 - 1 - MAX_JUMPERS
+            }),
+            Deck::build_with(DeckTradition::RiderWaite, jumpers).validate_draw(MAX_DRAWS)
+        );
+        let invalid = DeckOptions {
+            jumper_probability: -0.1,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(DrawError::InvalidJumperProbability),
+            Deck::build_with(DeckTradition::RiderWaite, invalid).validate_draw(3)
+        );
+    }
+
+    #[test]
+    fn jumpers_leave_the_deck_while_shuffling() {
+        let reading = Deck::build().seeded_reading("this is a question", &DeckSeed([7; 32]), 3);
+        assert!(reading.deck.jumpers.is_empty());
+
+        let always = DeckOptions {
+            jumper_probability: 1.0,
+            ..Default::default()
+        };
+        let reading = Deck::build_with(DeckTradition::RiderWaite, always).seeded_reading(
+            "this is a question",
+            &DeckSeed([7; 32]),
+            MAX_DRAWS,
+        );
+        let jumpers = &reading.deck.jumpers;
+        assert_eq!(MAX_JUMPERS, jumpers.len());
+        assert_eq!(78 - MAX_JUMPERS, reading.deck.cards.len());
+        assert!(
+            reading
+                .cards
+                .iter()
+                .all(|card| { jumpers.iter().all(|jumper| jumper.arcana != card.arcana) })
+        );
+    }
+
+    #[test]
+    fn lenormand_readings_are_upright() {
+        let deck = Deck::build_for(DeckTradition::Lenormand);
+        let count = deck.max_draws();
+        let reading = deck.seeded_reading("this is a question", &DeckSeed::random(), count);
+        assert_eq!(count, reading.cards.len());
+        assert!(reading.cards.iter().all(|card| !card.flipped));
+        assert!(
+            reading
+                .cards
+                .iter()
+                .all(|card| matches!(card.arcana, Arcana::Lenormand { .. }))
+        );
+    }
+
     #[test]
     fn shuffle_works() {
         let mut deck = Deck::build();
@@ -279,8 +1358,212 @@ mod tests {
     #[test]
     fn slice_works() {
         let deck = Deck::build();
-        let slices = deck.slice();
+        let slices = deck.slice(MAX_DRAWS, &mut rng());
         assert_eq!(3, slices.len());
         assert_eq!(78, slices[0].len() + slices[1].len() + slices[2].len());
     }
+
+    #[test]
+    fn spread_layouts_are_localized() {
+        for locale in ["pt", "en"] {
+            for layout in SpreadLayout::iter() {
+                let key = format!("spread.layout.{}", layout.id());
+                assert_ne!(key, t!(&key, locale = locale), "{} missing", key);
+                assert!(layout.position_keys().len() <= MAX_DRAWS);
+                for position in layout.position_keys() {
+                    for field in ["name", "description"] {
+                        let key = format!("spread.position.{}.{}", position, field);
+                        assert_ne!(key, t!(&key, locale = locale), "{} missing", key);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn spread_resolves_positions_in_order() {
+        let spread = SpreadLayout::ThreeCard.spread();
+        assert_eq!("threeCard", spread.id);
+        assert_eq!(3, spread.card_count());
+        assert_eq!(t!("spread.position.past.name"), spread.positions[0].name);
+        assert_eq!(t!("spread.position.future.name"), spread.positions[2].name);
+    }
+
+    #[test]
+    fn spread_layout_parses_from_id() {
+        assert_eq!(
+            SpreadLayout::CelticCross,
+            "celticCross".parse::<SpreadLayout>().unwrap()
+        );
+        assert!("celtic".parse::<SpreadLayout>().is_err());
+    }
+
+    #[test]
+    fn seeded_reading_is_reproducible() {
+        let seed = DeckSeed::random();
+        let first = Deck::build().seeded_reading("this is a question", &seed, 6);
+        let second = Deck::build().seeded_reading("this is a question", &seed, 6);
+        assert_eq!(first, second);
+        assert_eq!(6, first.cards.len());
+
+        let other = Deck::build().seeded_reading("this is a question", &DeckSeed::random(), 6);
+        assert_ne!(first.deck, other.deck);
+    }
+
+    #[test]
+    fn seeded_reading_is_stable() {
+        // Guards against accidental changes to the hash, generator or algorithm: stored
+        // seeds must keep replaying to the same cards.
+        let reading = Deck::build()
+            .with_shuffle(ShuffleMethod::EveryPass)
+            .seeded_reading("Will I get the job?", &DeckSeed([7; 32]), 3);
+        assert_eq!(1290, reading.shuffled_times);
+        assert_eq!(
+            vec![
+                Card {
+                    arcana: Arcana::Minor {
+                        rank: Rank::Ace,
+                        suit: Suit::Swords
+                    },
+                    flipped: true
+                },
+                Card {
+                    arcana: Arcana::Major {
+                        name: MajorArcana::WheelOfFortune
+                    },
+                    flipped: false
+                },
+                Card {
+                    arcana: Arcana::Major {
+                        name: MajorArcana::HighPriestess
+                    },
+                    flipped: true
+                },
+            ],
+            reading.cards
+        );
+        assert_eq!(stable_hash("Will I get the job?"), 0x044a624f8f91cb4b);
+    }
+
+    #[test]
+    fn single_pass_is_stable() {
+        let reading = Deck::build().seeded_reading("Will I get the job?", &DeckSeed([7; 32]), 3);
+        assert_eq!(1290, reading.shuffled_times);
+        assert_eq!(
+            vec![
+                Card {
+                    arcana: Arcana::Major {
+                        name: MajorArcana::Empress
+                    },
+                    flipped: true
+                },
+                Card {
+                    arcana: Arcana::Minor {
+                        rank: Rank::Ten,
+                        suit: Suit::Pentacles
+                    },
+                    flipped: false
+                },
+                Card {
+                    arcana: Arcana::Minor {
+                        rank: Rank::Ten,
+                        suit: Suit::Wands
+                    },
+                    flipped: false
+                },
+            ],
+            reading.cards
+        );
+    }
+
+    fn chi_square(observed: &[usize], expected: &[f64]) -> f64 {
+        let total = observed.iter().sum::<usize>() as f64;
+        observed
+            .iter()
+            .zip(expected)
+            .map(|(o, e)| (*o as f64 - e * total).powi(2) / (e * total))
+            .sum()
+    }
+
+    #[test]
+    fn single_pass_keeps_the_distribution_of_every_pass() {
+        // Six shuffles of the 14 cups, with reversals and frequent jumpers, many times over
+        // with each method; both must match the distribution worked out by hand.
+        const SAMPLES: usize = 20_000;
+        const SHUFFLES: usize = 6;
+        let options = DeckOptions {
+            arcana: ArcanaFilter::MinorOnly,
+            suits: vec![Suit::Cups],
+            reversal_probability: 0.3,
+            jumper_probability: 0.2,
+        };
+        let fresh = Deck::build_with(DeckTradition::RiderWaite, options);
+        let size = fresh.cards.len();
+        let index = |card: &Card| fresh.cards.iter().position(|c| c.arcana == card.arcana);
+
+        // Jumps are binomial over the shuffles, capped at MAX_JUMPERS
+        let binomial = |k: usize| {
+            let choose = (0..k).fold(1.0, |c, i| c * (SHUFFLES - i) as f64 / (i + 1) as f64);
+            choose * 0.2f64.powi(k as i32) * 0.8f64.powi((SHUFFLES - k) as i32)
+        };
+        let mut jumps = (0..MAX_JUMPERS).map(binomial).collect::<Vec<_>>();
+        jumps.push(1.0 - jumps.iter().sum::<f64>());
+        let uniform = vec![1.0 / size as f64; size];
+
+        for method in [ShuffleMethod::SinglePass, ShuffleMethod::EveryPass] {
+            let mut rng = ChaCha20Rng::seed_from_u64(7);
+            let mut jumper_counts = vec![0; MAX_JUMPERS + 1];
+            let mut top_cards = vec![0; size];
+            let mut jumpers = vec![0; size];
+            let (mut flipped, mut turned) = (0, 0);
+            for _ in 0..SAMPLES {
+                let mut deck = fresh.clone().with_shuffle(method);
+                match method {
+                    ShuffleMethod::SinglePass => deck.shuffle_once(SHUFFLES, &mut rng),
+                    ShuffleMethod::EveryPass => deck.shuffle_every_pass(SHUFFLES, &mut rng),
+                }
+                assert_eq!(size, deck.cards.len() + deck.jumpers.len());
+                jumper_counts[deck.jumpers.len()] += 1;
+                top_cards[index(&deck.cards[0]).unwrap()] += 1;
+                for jumper in &deck.jumpers {
+                    jumpers[index(jumper).unwrap()] += 1;
+                }
+                for card in deck.cards.iter().chain(&deck.jumpers) {
+                    turned += 1;
+                    flipped += card.flipped as usize;
+                }
+            }
+            // 99.9% critical values for 3 and 13 degrees of freedom
+            assert!(chi_square(&jumper_counts, &jumps) < 16.27, "{:?}", method);
+            assert!(chi_square(&top_cards, &uniform) < 34.53, "{:?}", method);
+            assert!(chi_square(&jumpers, &uniform) < 34.53, "{:?}", method);
+            let rate = flipped as f64 / turned as f64;
+            assert!((rate - 0.3).abs() < 0.005, "{:?}: {}", method, rate);
+        }
+
+        // Without a single shuffle the deck stays as built
+        for method in [ShuffleMethod::SinglePass, ShuffleMethod::EveryPass] {
+            let mut deck = fresh.clone().with_shuffle(method);
+            match method {
+                ShuffleMethod::SinglePass => deck.shuffle_once(0, &mut rng()),
+                ShuffleMethod::EveryPass => deck.shuffle_every_pass(0, &mut rng()),
+            }
+            assert_eq!(fresh.clone().with_shuffle(method), deck);
+        }
+    }
+
+    #[test]
+    fn deck_seed_round_trips_through_hex() {
+        let seed = DeckSeed::random();
+        let hex = seed.to_string();
+        assert_eq!(64, hex.len());
+        assert_eq!(seed, hex.parse().unwrap());
+        assert_eq!(
+            serde_json::to_string(&seed).unwrap(),
+            format!("\"{}\"", hex)
+        );
+        assert!("xyz".parse::<DeckSeed>().is_err());
+        assert!("zz".repeat(32).parse::<DeckSeed>().is_err());
+        assert!(format!("+a{}", "0".repeat(62)).parse::<DeckSeed>().is_err());
+    }
 }
@@ -0,0 +1,327 @@
+//! Parsing cards typed by hand, for people who draw from a physical deck: localised names
+//! ("Ás de Copas", "Ten of Swords reversed"), Roman numerals for the major arcana ("XVI")
+//! and compact codes ("AC", "10S-R").
+
+use crate::model::{Arcana, Card, Deck, DeckTradition, LenormandCard, MajorArcana, Rank, Suit};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// Trailing words that mark a card as reversed: "reversed", "(invertido)", "-R"...
+const REVERSED_MARKERS: [&str; 6] = ["reversed", "inverted", "invertido", "invertida", "rev", "r"];
+const ARTICLES: [&str; 5] = ["the", "o", "a", "os", "as"];
+const ROMAN_NUMERALS: [&str; 22] = [
+    "", "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x", "xi", "xii", "xiii", "xiv",
+    "xv", "xvi", "xvii", "xviii", "xix", "xx", "xxi",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    Empty,
+    Unknown(String),
+    /// A reversed card was given for a deck that is read upright only.
+    NotReversible(String),
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "Nenhuma carta informada."),
+            ParseCardError::Unknown(card) => write!(f, "Carta desconhecida: {}", card),
+            ParseCardError::NotReversible(card) => {
+                write!(f, "Este baralho não usa cartas invertidas: {}", card)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+impl Card {
+    /// Parses a card using the names and numbering of `tradition`, in any available locale.
+    pub fn parse_in(s: &str, tradition: DeckTradition) -> Result<Card, ParseCardError> {
+        let mut words = words(s);
+        let flipped = words.len() > 1
+            && words
+                .last()
+                .is_some_and(|word| REVERSED_MARKERS.contains(&word.as_str()));
+        if flipped {
+            words.pop();
+        }
+        let arcana = parse_words(&words, s, tradition)?;
+        if flipped && !tradition.has_reversals() {
+            return Err(ParseCardError::NotReversible(s.trim().to_string()));
+        }
+        Ok(Card { arcana, flipped })
+    }
+
+    /// Parses a comma, semicolon or newline separated list of cards.
+    pub fn parse_list(s: &str, tradition: DeckTradition) -> Result<Vec<Card>, ParseCardError> {
+        s.split([',', ';', '\n'])
+            .filter(|card| !card.trim().is_empty())
+            .map(|card| Card::parse_in(card, tradition))
+            .collect()
+    }
+}
+
+impl Arcana {
+    /// Parses an upright card name, numeral or code using the conventions of `tradition`.
+    pub fn parse_in(s: &str, tradition: DeckTradition) -> Result<Arcana, ParseCardError> {
+        parse_words(&words(s), s, tradition)
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Card::parse_in(s, DeckTradition::RiderWaite)
+    }
+}
+
+impl FromStr for Arcana {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Arcana::parse_in(s, DeckTradition::RiderWaite)
+    }
+}
+
+/// Lowercases, strips accents and splits on anything that is not a letter or digit.
+fn words(s: &str) -> Vec<String> {
+    let folded: String = s
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect();
+    folded.split_whitespace().map(str::to_string).collect()
+}
+
+fn parse_words(
+    words: &[String],
+    input: &str,
+    tradition: DeckTradition,
+) -> Result<Arcana, ParseCardError> {
+    if words.is_empty() {
+        return Err(ParseCardError::Empty);
+    }
+    let key = words.join(" ");
+    let arcana = match tradition {
+        DeckTradition::Lenormand => key
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| LenormandCard::iter().nth(number.checked_sub(1)?))
+            .map(|card| Arcana::Lenormand { card }),
+        _ => parse_major_number(&key, tradition).or_else(|| parse_code(&key)),
+    };
+    arcana
+        .or_else(|| find_by_name(&key, tradition))
+        // Names shared by every tarot tradition, e.g. "Knight of Wands" in a Thoth reading.
+        .or_else(|| match tradition {
+            DeckTradition::Marseille | DeckTradition::Thoth => {
+                find_by_name(&key, DeckTradition::RiderWaite)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| ParseCardError::Unknown(input.trim().to_string()))
+}
+
+/// "XVI" or "16", numbered as `tradition` numbers its major arcana.
+fn parse_major_number(key: &str, tradition: DeckTradition) -> Option<Arcana> {
+    let number = key.parse::<u8>().ok().or_else(|| {
+        ROMAN_NUMERALS
+            .iter()
+            .position(|numeral| !numeral.is_empty() && *numeral == key)
+            .map(|number| number as u8)
+    })?;
+    MajorArcana::iter()
+        .find(|major| major.number(tradition) == number)
+        .map(|name| Arcana::Major { name })
+}
+
+/// Rank followed by the suit initial: "AC", "10S", "KnW", "QP".
+fn parse_code(key: &str) -> Option<Arcana> {
+    let suit = match key.chars().last()? {
+        'c' => Suit::Cups,
+        'p' => Suit::Pentacles,
+        's' => Suit::Swords,
+        'w' => Suit::Wands,
+        _ => return None,
+    };
+    let rank = match &key[..key.len() - 1] {
+        "a" | "1" => Rank::Ace,
+        "p" | "j" => Rank::Page,
+        "n" | "kn" => Rank::Knight,
+        "q" => Rank::Queen,
+        "k" => Rank::King,
+        pip => {
+            Rank::iter().find(|rank| rank.value().is_some_and(|value| value.to_string() == pip))?
+        }
+    };
+    Some(Arcana::Minor { rank, suit })
+}
+
+fn find_by_name(key: &str, tradition: DeckTradition) -> Option<Arcana> {
+    let locales = rust_i18n::available_locales!();
+    Deck::build_for(tradition)
+        .cards
+        .into_iter()
+        .map(|card| card.arcana)
+        .find(|arcana| {
+            locales.iter().any(|locale| {
+                let name = words(&arcana.name_in(tradition, locale));
+                let without_article = match name.split_first() {
+                    Some((first, rest))
+                        if !rest.is_empty() && ARTICLES.contains(&first.as_str()) =>
+                    {
+                        rest
+                    }
+                    _ => &name[..],
+                };
+                name.join(" ") == key || without_article.join(" ") == key
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minor(rank: Rank, suit: Suit, flipped: bool) -> Card {
+        Card {
+            arcana: Arcana::Minor { rank, suit },
+            flipped,
+        }
+    }
+
+    fn major(name: MajorArcana, flipped: bool) -> Card {
+        Card {
+            arcana: Arcana::Major { name },
+            flipped,
+        }
+    }
+
+    #[test]
+    fn parses_localised_names() {
+        assert_eq!(
+            Ok(minor(Rank::Ace, Suit::Cups, false)),
+            "Ás de Copas".parse()
+        );
+        assert_eq!(
+            Ok(minor(Rank::Ten, Suit::Swords, true)),
+            "Ten of Swords reversed".parse()
+        );
+        assert_eq!(Ok(major(MajorArcana::Fool, false)), "the fool".parse());
+        assert_eq!(Ok(major(MajorArcana::Fool, false)), "Louco".parse());
+        assert_eq!(
+            Ok(major(MajorArcana::HighPriestess, true)),
+            "A Suma Sacerdotisa (invertida)".parse()
+        );
+        assert_eq!(
+            Ok(major(MajorArcana::Sun, true)),
+            "The Sun (reversed)".parse()
+        );
+    }
+
+    #[test]
+    fn parses_numerals_and_codes() {
+        assert_eq!(Ok(major(MajorArcana::Tower, false)), "XVI".parse());
+        assert_eq!(Ok(major(MajorArcana::Fool, false)), "0".parse());
+        assert_eq!(Ok(major(MajorArcana::World, true)), "xxi r".parse());
+        assert_eq!(Ok(minor(Rank::Ace, Suit::Cups, false)), "AC".parse());
+        assert_eq!(Ok(minor(Rank::Ten, Suit::Swords, true)), "10S-R".parse());
+        assert_eq!(Ok(minor(Rank::Knight, Suit::Wands, false)), "KnW".parse());
+        assert_eq!(Ok(minor(Rank::Page, Suit::Pentacles, false)), "PP".parse());
+    }
+
+    #[test]
+    fn parses_in_the_tradition() {
+        assert_eq!(
+            Ok(major(MajorArcana::Justice, false)),
+            Card::parse_in("VIII", DeckTradition::Marseille)
+        );
+        assert_eq!(
+            Ok(major(MajorArcana::Strength, false)),
+            Card::parse_in("VIII", DeckTradition::RiderWaite)
+        );
+        assert_eq!(
+            Ok(minor(Rank::King, Suit::Wands, false)),
+            Card::parse_in("Knight of Wands", DeckTradition::Thoth)
+        );
+        assert_eq!(
+            Ok(minor(Rank::Page, Suit::Pentacles, false)),
+            Card::parse_in("Princess of Disks", DeckTradition::Thoth)
+        );
+        assert_eq!(
+            Ok(Arcana::Lenormand {
+                card: LenormandCard::Tower
+            }),
+            Arcana::parse_in("19", DeckTradition::Lenormand)
+        );
+        assert_eq!(
+            Ok(Arcana::Lenormand {
+                card: LenormandCard::Clover
+            }),
+            Arcana::parse_in("O Trevo", DeckTradition::Lenormand)
+        );
+        assert_eq!(
+            Err(ParseCardError::NotReversible(
+                "The Ship reversed".to_string()
+            )),
+            Card::parse_in("The Ship reversed", DeckTradition::Lenormand)
+        );
+    }
+
+    #[test]
+    fn every_name_parses_back_to_its_card() {
+        for tradition in DeckTradition::iter() {
+            for card in Deck::build_for(tradition).cards {
+                for locale in ["en", "pt"] {
+                    let name = card.arcana.name_in(tradition, locale);
+                    assert_eq!(
+                        Ok(card.arcana),
+                        Arcana::parse_in(&name, tradition),
+                        "{} in {:?}",
+                        name,
+                        tradition
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_cards() {
+        assert_eq!(
+            Err(ParseCardError::Unknown("Eleven of Cups".to_string())),
+            "Eleven of Cups".parse::<Card>()
+        );
+        assert_eq!(Err(ParseCardError::Empty), " ".parse::<Card>());
+        assert!("XXII".parse::<Arcana>().is_err());
+        assert!("AX".parse::<Arcana>().is_err());
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(
+            Ok(vec![
+                minor(Rank::Ace, Suit::Cups, false),
+                major(MajorArcana::Tower, true),
+                minor(Rank::Queen, Suit::Swords, false),
+            ]),
+            Card::parse_list(
+                "AC, XVI reversed; Rainha de Espadas",
+                DeckTradition::RiderWaite
+            )
+        );
+    }
+}
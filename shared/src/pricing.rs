@@ -0,0 +1,117 @@
+//! What interpretations cost, from the tokens the providers report.
+
+use crate::provider::Usage;
+use std::collections::HashMap;
+
+/// Price of a model, in US dollars per million tokens.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModelPrice {
+    pub prompt: f64,
+    pub completion: f64,
+}
+
+impl ModelPrice {
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        (usage.prompt_tokens as f64 * self.prompt
+            + usage.completion_tokens as f64 * self.completion)
+            / 1_000_000.0
+    }
+}
+
+/// Prices of the models interpretations may use. Models missing from it have no known cost.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceTable {
+    prices: HashMap<String, ModelPrice>,
+}
+
+impl Default for PriceTable {
+    /// List prices of the default models of each hosted provider.
+    fn default() -> Self {
+        let mut table = Self::empty();
+        for (model, prompt, completion) in [
+            (crate::provider::openai::DEFAULT_MODEL, 1.25, 10.0),
+            ("gpt-5-mini", 0.25, 2.0),
+            (crate::provider::gemini::DEFAULT_MODEL, 0.5, 3.0),
+            (crate::provider::anthropic::DEFAULT_MODEL, 3.0, 15.0),
+        ] {
+            table.insert(model, ModelPrice { prompt, completion });
+        }
+        table
+    }
+}
+
+impl PriceTable {
+    pub fn empty() -> Self {
+        Self {
+            prices: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, model: impl Into<String>, price: ModelPrice) {
+        self.prices.insert(model.into(), price);
+    }
+
+    pub fn price(&self, model: &str) -> Option<ModelPrice> {
+        self.prices.get(model).copied()
+    }
+
+    /// What `usage` cost on `model`, in US dollars; `None` when the model has no price.
+    pub fn cost(&self, model: &str, usage: &Usage) -> Option<f64> {
+        self.price(model).map(|price| price.cost(usage))
+    }
+
+    /// The default prices, with those in `INTERPRETATION_PRICES` added or overriding them:
+    /// comma-separated `model=prompt/completion` entries in US dollars per million tokens, e.g.
+    /// `gpt-5.1=1.25/10,llama3.2=0/0`.
+    ///
+    /// # Panics
+    ///
+    /// When an entry cannot be parsed.
+    pub fn from_env() -> Self {
+        let mut table = Self::default();
+        let Ok(prices) = std::env::var("INTERPRETATION_PRICES") else {
+            return table;
+        };
+        for entry in prices.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let parsed = entry.split_once('=').and_then(|(model, price)| {
+                let (prompt, completion) = price.split_once('/')?;
+                let price = ModelPrice {
+                    prompt: prompt.trim().parse().ok()?,
+                    completion: completion.trim().parse().ok()?,
+                };
+                Some((model.trim(), price))
+            });
+            let Some((model, price)) = parsed else {
+                panic!("Invalid INTERPRETATION_PRICES entry: {}", entry);
+            };
+            table.insert(model, price);
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_table_costs_tokens_per_million() {
+        let mut table = PriceTable::default();
+        let usage = Usage {
+            prompt_tokens: 2_000,
+            completion_tokens: 500,
+        };
+        let cost = table.cost("claude-sonnet-4-5", &usage).unwrap();
+        assert!((cost - 0.0135).abs() < 1e-12, "{}", cost);
+        assert_eq!(None, table.cost("llama3.2", &usage));
+
+        table.insert(
+            "llama3.2",
+            ModelPrice {
+                prompt: 0.0,
+                completion: 0.0,
+            },
+        );
+        assert_eq!(Some(0.0), table.cost("llama3.2", &usage));
+    }
+}
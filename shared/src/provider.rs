@@ -0,0 +1,613 @@
+//! Interpretation providers: the LLM APIs a reading can be interpreted with.
+//!
+//! Each provider lives in its own module and implements [`InterpretationProvider`]. The
+//! [`ProviderRegistry`] holds the providers enabled by the [`ProviderConfig`] and is what
+//! [`InterpretationService`](crate::explain::InterpretationService) dispatches to.
+
+pub mod anthropic;
+pub mod gemini;
+pub mod local;
+pub mod openai;
+
+use crate::explain::{ExplainError, ExplainResult, InterpretationBackend};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumString, IntoStaticStr};
+
+/// Longest a single interpretation request may take, streamed answer included. Retries are
+/// bounded by [`RetryPolicy::max_elapsed`](crate::resilience::RetryPolicy::max_elapsed)
+/// instead.
+const REQUEST_TIMEOUT: Duration = Duration::from_mins(5);
+
+pub type ProviderFuture<'a> = Pin<Box<dyn Future<Output = CompletionResult> + Send + 'a>>;
+
+pub type CompletionResult = Result<Completion, ExplainError>;
+
+/// A provider's answer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Completion {
+    pub text: String,
+    /// Tokens the answer took, when the provider reports them.
+    pub usage: Option<Usage>,
+}
+
+/// Tokens one call took, as the provider counted them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Usage {
+    pub prompt_tokens: u32,
+    /// Tokens of the answer, reasoning included.
+    pub completion_tokens: u32,
+}
+
+/// Receives the pieces of an answer as the model writes them.
+pub type ChunkSink<'a> = dyn Fn(&str) + Send + Sync + 'a;
+
+/// The prompts sent to a provider for one interpretation, and the conversation that followed it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Prompt {
+    pub system: String,
+    pub user: String,
+    /// Turns after `user`, oldest first, when answering a follow-up question: the
+    /// interpretation, then earlier questions and answers, ending with the question to answer.
+    /// Empty for the interpretation itself.
+    pub conversation: Vec<Turn>,
+    /// JSON schema the answer must follow, for providers capable of structured output; free
+    /// text when `None`.
+    pub response_schema: Option<serde_json::Value>,
+}
+
+impl Prompt {
+    /// The user prompt and the conversation after it.
+    pub fn messages(&self) -> impl Iterator<Item = (Role, &str)> {
+        std::iter::once((Role::User, self.user.as_str())).chain(
+            self.conversation
+                .iter()
+                .map(|turn| (turn.role, turn.text.as_str())),
+        )
+    }
+}
+
+/// Who wrote a turn of a conversation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+impl Role {
+    /// The role as OpenAI-compatible and Anthropic APIs name it.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Turn {
+    pub role: Role,
+    pub text: String,
+}
+
+/// An LLM API that interprets readings.
+pub trait InterpretationProvider: Send + Sync {
+    fn info(&self) -> ProviderInfo;
+
+    /// The model to interpret with, the ones a request may pick instead and their parameters.
+    fn models(&self) -> &ModelConfig;
+
+    /// Sends `prompt` to `model` and returns its answer.
+    fn complete<'a>(&'a self, prompt: &'a Prompt, model: &'a str) -> ProviderFuture<'a>;
+
+    /// Like [`complete`](Self::complete), also handing every piece of the answer to `on_chunk`
+    /// as it is written. Providers that cannot stream hand over the whole answer at once.
+    fn stream<'a>(
+        &'a self,
+        prompt: &'a Prompt,
+        model: &'a str,
+        on_chunk: &'a ChunkSink<'a>,
+    ) -> ProviderFuture<'a> {
+        Box::pin(async move {
+            let completion = self.complete(prompt, model).await?;
+            on_chunk(&completion.text);
+            Ok(completion)
+        })
+    }
+}
+
+/// How much a reasoning model thinks before answering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumString, IntoStaticStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    pub fn id(&self) -> &'static str {
+        self.into()
+    }
+}
+
+/// Generation parameters; the provider's defaults apply to the ones left unset.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationParams {
+    pub temperature: Option<f32>,
+    /// Longest answer, in tokens.
+    pub max_tokens: Option<u32>,
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
+/// The model a provider interprets with and the other models a request may pick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelConfig {
+    pub model: String,
+    /// Models a request may ask for instead of `model`.
+    pub allowed_models: Vec<String>,
+    pub params: GenerationParams,
+}
+
+impl ModelConfig {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            allowed_models: Vec::new(),
+            params: GenerationParams::default(),
+        }
+    }
+
+    /// Reads `{prefix}_MODEL`, `{prefix}_ALLOWED_MODELS` (comma-separated),
+    /// `{prefix}_TEMPERATURE`, `{prefix}_MAX_TOKENS` and `{prefix}_REASONING_EFFORT`
+    /// (`low`, `medium` or `high`).
+    ///
+    /// # Panics
+    ///
+    /// When a parameter is set to something it cannot be parsed as.
+    pub fn from_env(prefix: &str, default_model: &str) -> Self {
+        fn var(prefix: &str, name: &str) -> Option<String> {
+            std::env::var(format!("{}_{}", prefix, name))
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        }
+        fn parse<T: FromStr>(prefix: &str, name: &str) -> Option<T> {
+            var(prefix, name).map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Invalid {}_{}: {}", prefix, name, value))
+            })
+        }
+        Self {
+            model: var(prefix, "MODEL").unwrap_or_else(|| default_model.to_string()),
+            allowed_models: var(prefix, "ALLOWED_MODELS")
+                .map(|models| {
+                    models
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|model| !model.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            params: GenerationParams {
+                temperature: parse(prefix, "TEMPERATURE"),
+                max_tokens: parse(prefix, "MAX_TOKENS"),
+                reasoning_effort: parse(prefix, "REASONING_EFFORT"),
+            },
+        }
+    }
+
+    /// Every model a request may ask for, the default first.
+    pub fn models(&self) -> Vec<String> {
+        let mut models = vec![self.model.clone()];
+        for model in &self.allowed_models {
+            if !models.contains(model) {
+                models.push(model.clone());
+            }
+        }
+        models
+    }
+
+    /// The model to use for a request asking for `requested`, unless it is not allowed.
+    pub fn resolve<'a>(&'a self, requested: Option<&'a str>) -> Option<&'a str> {
+        match requested {
+            None => Some(&self.model),
+            Some(model)
+                if model == self.model || self.allowed_models.iter().any(|m| m == model) =>
+            {
+                Some(model)
+            }
+            Some(_) => None,
+        }
+    }
+}
+
+/// What the frontend shows in its provider selector.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderInfo {
+    /// The value to send as `backend` when creating a reading.
+    pub id: InterpretationBackend,
+    pub name: String,
+    pub capabilities: Capabilities,
+    /// Models a reading may ask for, the one used by default first.
+    pub models: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// Sends the interpretation as it is written.
+    pub streaming: bool,
+    /// Can be held to a JSON schema.
+    pub structured_output: bool,
+}
+
+/// Credentials of the providers and which of them to offer.
+#[derive(Clone, Debug, Default)]
+pub struct ProviderConfig {
+    pub openai_api_key: String,
+    pub google_api_key: String,
+    pub anthropic_api_key: String,
+    /// The self-hosted model to offer, if any.
+    pub local: Option<local::LocalConfig>,
+    /// Providers to enable, in the order they are listed; every provider with an API key, and
+    /// the local one when configured, when `None`.
+    pub enabled: Option<Vec<InterpretationBackend>>,
+    /// Models and parameters of each provider; its default model for the ones left out.
+    pub models: HashMap<InterpretationBackend, ModelConfig>,
+}
+
+impl ProviderConfig {
+    /// Every provider's models from the environment; see [`ModelConfig::from_env`] and
+    /// [`InterpretationBackend::env_prefix`].
+    pub fn models_from_env() -> HashMap<InterpretationBackend, ModelConfig> {
+        InterpretationBackend::iter()
+            .map(|backend| {
+                let models = ModelConfig::from_env(backend.env_prefix(), backend.default_model());
+                (backend, models)
+            })
+            .collect()
+    }
+
+    fn models(&self, backend: &InterpretationBackend) -> ModelConfig {
+        self.models
+            .get(backend)
+            .cloned()
+            .unwrap_or_else(|| ModelConfig::new(backend.default_model()))
+    }
+}
+
+impl InterpretationBackend {
+    /// Prefix of the environment variables [`ModelConfig::from_env`] reads for this provider.
+    pub fn env_prefix(&self) -> &'static str {
+        match self {
+            InterpretationBackend::ChatGPT => "OPENAI",
+            InterpretationBackend::Gemini => "GEMINI",
+            InterpretationBackend::Claude => "ANTHROPIC",
+            InterpretationBackend::Local => "LOCAL_LLM",
+        }
+    }
+
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            InterpretationBackend::ChatGPT => openai::DEFAULT_MODEL,
+            InterpretationBackend::Gemini => gemini::DEFAULT_MODEL,
+            InterpretationBackend::Claude => anthropic::DEFAULT_MODEL,
+            InterpretationBackend::Local => local::DEFAULT_MODEL,
+        }
+    }
+}
+
+/// The enabled providers, in the order they are offered.
+#[derive(Clone, Default)]
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn InterpretationProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(config: &ProviderConfig) -> Self {
+        let enabled = config.enabled.clone().unwrap_or_else(|| {
+            [
+                (InterpretationBackend::ChatGPT, &config.openai_api_key),
+                (InterpretationBackend::Gemini, &config.google_api_key),
+                (InterpretationBackend::Claude, &config.anthropic_api_key),
+            ]
+            .into_iter()
+            .filter(|(_, key)| !key.trim().is_empty())
+            .map(|(backend, _)| backend)
+            .chain(config.local.as_ref().map(|_| InterpretationBackend::Local))
+            .collect()
+        });
+        let mut registry = Self::new();
+        for backend in enabled {
+            let models = config.models(&backend);
+            match backend {
+                InterpretationBackend::ChatGPT => registry.register(openai::OpenAiProvider::new(
+                    config.openai_api_key.clone(),
+                    models,
+                )),
+                InterpretationBackend::Gemini => registry.register(gemini::GeminiProvider::new(
+                    config.google_api_key.clone(),
+                    models,
+                )),
+                InterpretationBackend::Claude => registry.register(
+                    anthropic::AnthropicProvider::new(config.anthropic_api_key.clone(), models),
+                ),
+                InterpretationBackend::Local => registry.register(local::LocalProvider::new(
+                    config.local.clone().unwrap_or_default(),
+                    models,
+                )),
+            }
+        }
+        registry
+    }
+
+    /// Adds `provider`, replacing any provider registered with the same id.
+    pub fn register(&mut self, provider: impl InterpretationProvider + 'static) {
+        let id = provider.info().id;
+        self.providers.retain(|p| p.info().id != id);
+        self.providers.push(Arc::new(provider));
+    }
+
+    pub fn get(&self, backend: &InterpretationBackend) -> Option<&dyn InterpretationProvider> {
+        self.providers
+            .iter()
+            .find(|p| p.info().id == *backend)
+            .map(|p| p.as_ref())
+    }
+
+    pub fn list(&self) -> Vec<ProviderInfo> {
+        self.providers.iter().map(|p| p.info()).collect()
+    }
+}
+
+pub(crate) fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .user_agent("webtarot/0.1")
+        .timeout(Duration::from_secs(120))
+        .build()
+        .unwrap()
+}
+
+/// Posts `body`, failing on transport errors and non-success statuses.
+async fn post(
+    request: reqwest::RequestBuilder,
+    body: &serde_json::Value,
+) -> Result<reqwest::Response, ExplainError> {
+    let resp = request
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .timeout(REQUEST_TIMEOUT)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| ExplainError::Request(Arc::new(e)))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        // Only the delay in seconds; no provider sends the HTTP date form
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        let body = resp.text().await.unwrap_or_default();
+        return Err(ExplainError::ApiError {
+            status,
+            body,
+            retry_after,
+        });
+    }
+    Ok(resp)
+}
+
+/// Posts `body`, failing on transport errors and non-success statuses, and parses the answer.
+pub(crate) async fn post_json<T: DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    body: &serde_json::Value,
+) -> Result<T, ExplainError> {
+    post(request, body)
+        .await?
+        .json()
+        .await
+        .map_err(|e| ExplainError::ParseResponse(Arc::new(e)))
+}
+
+/// Posts `body` asking for server-sent events and hands the data of each event to `on_event`,
+/// parsed as `E`, until the stream ends or `on_event` fails. Returns the answer instead, parsed
+/// as `T`, when the server sends it whole rather than as events.
+pub(crate) async fn post_events<E: DeserializeOwned, T: DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    body: &serde_json::Value,
+    mut on_event: impl FnMut(E) -> Result<(), ExplainError>,
+) -> Result<Option<T>, ExplainError> {
+    let mut resp = post(request, body).await?;
+    let is_event_stream = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    if !is_event_stream {
+        return resp
+            .json()
+            .await
+            .map(Some)
+            .map_err(|e| ExplainError::ParseResponse(Arc::new(e)));
+    }
+
+    let mut dispatch = |data: &str| match data {
+        // OpenAI ends its streams with a sentinel rather than JSON
+        "[DONE]" => Ok(()),
+        data => on_event(
+            serde_json::from_str(data).map_err(|e| ExplainError::InvalidEvent(Arc::new(e)))?,
+        ),
+    };
+    let mut buffer = Vec::new();
+    let mut data = String::new();
+    while let Some(bytes) = resp
+        .chunk()
+        .await
+        .map_err(|e| ExplainError::Request(Arc::new(e)))?
+    {
+        buffer.extend_from_slice(&bytes);
+        while let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+            let line = buffer.drain(..=end).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                // A blank line ends the event
+                if !data.is_empty() {
+                    dispatch(&data)?;
+                    data.clear();
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                if !data.is_empty() {
+                    data.push('\n');
+                }
+                data.push_str(value.strip_prefix(' ').unwrap_or(value));
+            }
+        }
+    }
+    if !data.is_empty() {
+        dispatch(&data)?;
+    }
+    Ok(None)
+}
+
+/// The answer, unless the model said nothing.
+pub(crate) fn non_empty(text: Option<String>) -> ExplainResult {
+    text.filter(|text| !text.trim().is_empty())
+        .ok_or(ExplainError::EmptyResponse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_enables_providers_with_keys() {
+        let ids = |registry: &ProviderRegistry| {
+            registry
+                .list()
+                .into_iter()
+                .map(|info| info.id)
+                .collect::<Vec<_>>()
+        };
+        let mut config = ProviderConfig {
+            openai_api_key: "sk-test".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            vec![InterpretationBackend::ChatGPT],
+            ids(&ProviderRegistry::from_config(&config))
+        );
+
+        config.google_api_key = "goog".to_string();
+        let registry = ProviderRegistry::from_config(&config);
+        assert_eq!(
+            vec![
+                InterpretationBackend::ChatGPT,
+                InterpretationBackend::Gemini
+            ],
+            ids(&registry)
+        );
+        assert_eq!("Gemini", registry.list()[1].name);
+
+        config.enabled = Some(vec![InterpretationBackend::Gemini]);
+        let registry = ProviderRegistry::from_config(&config);
+        assert_eq!(vec![InterpretationBackend::Gemini], ids(&registry));
+        assert!(registry.get(&InterpretationBackend::ChatGPT).is_none());
+
+        config.enabled = None;
+        config.anthropic_api_key = "sk-ant".to_string();
+        config.local = Some(local::LocalConfig::default());
+        let registry = ProviderRegistry::from_config(&config);
+        assert_eq!(
+            vec![
+                InterpretationBackend::ChatGPT,
+                InterpretationBackend::Gemini,
+                InterpretationBackend::Claude,
+                InterpretationBackend::Local
+            ],
+            ids(&registry)
+        );
+        assert_eq!("Local (llama3.2)", registry.list()[3].name);
+    }
+
+    #[test]
+    fn model_config_resolves_requests_against_the_allow_list() {
+        let models = ModelConfig {
+            allowed_models: vec!["gpt-5-mini".to_string(), "gpt-5.1".to_string()],
+            ..ModelConfig::new("gpt-5.1")
+        };
+        assert_eq!(vec!["gpt-5.1", "gpt-5-mini"], models.models());
+        assert_eq!(Some("gpt-5.1"), models.resolve(None));
+        assert_eq!(Some("gpt-5-mini"), models.resolve(Some("gpt-5-mini")));
+        assert_eq!(None, models.resolve(Some("gpt-4o")));
+
+        let registry = ProviderRegistry::from_config(&ProviderConfig {
+            openai_api_key: "sk-test".to_string(),
+            models: HashMap::from([(InterpretationBackend::ChatGPT, models)]),
+            ..Default::default()
+        });
+        assert_eq!(vec!["gpt-5.1", "gpt-5-mini"], registry.list()[0].models);
+    }
+
+    #[test]
+    fn model_config_reads_the_environment() {
+        unsafe {
+            std::env::set_var("WEBTAROT_TEST_MODEL", "qwen3:8b");
+            std::env::set_var("WEBTAROT_TEST_ALLOWED_MODELS", "llama3.2, gemma3 ,");
+            std::env::set_var("WEBTAROT_TEST_TEMPERATURE", "0.25");
+            std::env::set_var("WEBTAROT_TEST_MAX_TOKENS", "1500");
+            std::env::set_var("WEBTAROT_TEST_REASONING_EFFORT", "High");
+        }
+        assert_eq!(
+            ModelConfig {
+                model: "qwen3:8b".to_string(),
+                allowed_models: vec!["llama3.2".to_string(), "gemma3".to_string()],
+                params: GenerationParams {
+                    temperature: Some(0.25),
+                    max_tokens: Some(1500),
+                    reasoning_effort: Some(ReasoningEffort::High),
+                },
+            },
+            ModelConfig::from_env("WEBTAROT_TEST", "llama3.2")
+        );
+        assert_eq!(
+            ModelConfig::new("gpt-5.1"),
+            ModelConfig::from_env("WEBTAROT_UNSET", "gpt-5.1")
+        );
+    }
+
+    #[test]
+    fn backend_ids_parse_like_they_serialize() {
+        for backend in InterpretationBackend::iter() {
+            let json = serde_json::to_string(&backend).unwrap();
+            assert_eq!(format!("\"{}\"", backend.id()), json);
+            assert_eq!(backend, backend.id().parse().unwrap());
+        }
+        assert_eq!(
+            InterpretationBackend::ChatGPT,
+            "chatgpt".parse::<InterpretationBackend>().unwrap()
+        );
+        assert!("grok".parse::<InterpretationBackend>().is_err());
+    }
+}
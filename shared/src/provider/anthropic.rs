@@ -0,0 +1,425 @@
+//! Anthropic Messages API (Claude).
+
+use super::{
+    Capabilities, ChunkSink, Completion, CompletionResult, InterpretationProvider, ModelConfig,
+    Prompt, ProviderFuture, ProviderInfo, ReasoningEffort, Usage,
+};
+use crate::explain::{ExplainError, InterpretationBackend};
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+const API_VERSION: &str = "2023-06-01";
+/// Beta that holds answers to the JSON schema in `output_format`.
+const STRUCTURED_OUTPUTS_BETA: &str = "structured-outputs-2025-11-13";
+pub const DEFAULT_MODEL: &str = "claude-sonnet-4-5";
+/// The Messages API needs a ceiling on the answer; a long spread stays well below it.
+const MAX_TOKENS: u32 = 4096;
+
+#[derive(Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+    usage: Option<MessageUsage>,
+}
+
+#[derive(Deserialize)]
+struct MessageUsage {
+    #[serde(default)]
+    input_tokens: u32,
+    #[serde(default)]
+    output_tokens: u32,
+}
+
+/// The message a stream starts with, before any content.
+#[derive(Deserialize)]
+struct MessageStart {
+    usage: Option<MessageUsage>,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    text: Option<String>,
+}
+
+/// One event of a streamed message; only text deltas, usage and errors matter here.
+#[derive(Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    delta: Option<ContentBlock>,
+    error: Option<ErrorDetail>,
+    /// On `message_start`, with the prompt's tokens.
+    message: Option<MessageStart>,
+    /// On `message_delta`, with the answer's tokens so far.
+    usage: Option<MessageUsage>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+}
+
+pub struct AnthropicProvider {
+    client: reqwest::Client,
+    api_key: String,
+    models: ModelConfig,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: String, models: ModelConfig) -> Self {
+        Self {
+            client: super::http_client(),
+            api_key,
+            models,
+        }
+    }
+
+    async fn message(
+        &self,
+        prompt: &Prompt,
+        model: &str,
+        on_chunk: Option<&ChunkSink<'_>>,
+    ) -> CompletionResult {
+        if self.api_key.trim().is_empty() {
+            return Err(ExplainError::MissingApiKey);
+        }
+
+        // Allow overriding the base URL via env var for testing
+        let base_url =
+            std::env::var("ANTHROPIC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let endpoint = format!("{}/v1/messages", base_url.trim_end_matches('/'));
+
+        let params = &self.models.params;
+        let max_tokens = params.max_tokens.unwrap_or(MAX_TOKENS);
+        let mut body = serde_json::json!({
+            "model": model,
+            "max_tokens": max_tokens,
+            "system": prompt.system,
+            "messages": prompt
+                .messages()
+                .map(|(role, text)| serde_json::json!({"role": role.id(), "content": text}))
+                .collect::<Vec<_>>()
+        });
+        match params.reasoning_effort.map(thinking_budget) {
+            // Thinking counts towards the answer's tokens and takes no temperature
+            Some(budget) => {
+                body["max_tokens"] = (max_tokens + budget).into();
+                body["thinking"] =
+                    serde_json::json!({ "type": "enabled", "budget_tokens": budget });
+            }
+            None => {
+                if let Some(temperature) = params.temperature {
+                    body["temperature"] = temperature.into();
+                }
+            }
+        }
+
+        let mut request = self
+            .client
+            .post(endpoint)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", API_VERSION);
+        if let Some(schema) = &prompt.response_schema {
+            request = request.header("anthropic-beta", STRUCTURED_OUTPUTS_BETA);
+            body["output_format"] = serde_json::json!({ "type": "json_schema", "schema": schema });
+        }
+        // A refusal comes back without text blocks and reads as an empty answer
+        let join = |parsed: MessagesResponse| {
+            let text = parsed
+                .content
+                .into_iter()
+                .filter_map(|block| block.text)
+                .collect::<String>();
+            super::non_empty(Some(text)).map(|text| Completion {
+                text,
+                usage: parsed.usage.map(|usage| Usage {
+                    prompt_tokens: usage.input_tokens,
+                    completion_tokens: usage.output_tokens,
+                }),
+            })
+        };
+
+        let Some(on_chunk) = on_chunk else {
+            let parsed: MessagesResponse = super::post_json(request, &body)
+                .await
+                .map_err(readable_error)?;
+            return join(parsed);
+        };
+        body["stream"] = true.into();
+        let mut text = String::new();
+        let mut usage = None;
+        let whole: Option<MessagesResponse> =
+            super::post_events(request, &body, |event: StreamEvent| {
+                match (event.kind.as_str(), event.delta, event.error) {
+                    ("content_block_delta", Some(ContentBlock { text: Some(piece) }), _) => {
+                        on_chunk(&piece);
+                        text.push_str(&piece);
+                    }
+                    ("message_start", ..) => {
+                        if let Some(start) = event.message.and_then(|message| message.usage) {
+                            usage = Some(Usage {
+                                prompt_tokens: start.input_tokens,
+                                completion_tokens: start.output_tokens,
+                            });
+                        }
+                    }
+                    ("message_delta", ..) => {
+                        if let (Some(usage), Some(delta)) = (usage.as_mut(), event.usage) {
+                            usage.completion_tokens = delta.output_tokens;
+                        }
+                    }
+                    // Overloads can also arrive in the middle of a stream
+                    ("error", _, Some(error)) => {
+                        return Err(ExplainError::ApiError {
+                            status: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                            body: format!("{}: {}", error.kind, error.message),
+                            retry_after: None,
+                        });
+                    }
+                    _ => {}
+                }
+                Ok(())
+            })
+            .await
+            .map_err(readable_error)?;
+        match whole {
+            // The server ignored `stream` and answered at once
+            Some(parsed) => {
+                let completion = join(parsed)?;
+                on_chunk(&completion.text);
+                Ok(completion)
+            }
+            None => super::non_empty(Some(text)).map(|text| Completion { text, usage }),
+        }
+    }
+}
+
+/// Tokens Claude may think for at each effort, with extended thinking.
+fn thinking_budget(effort: ReasoningEffort) -> u32 {
+    match effort {
+        ReasoningEffort::Low => 1024,
+        ReasoningEffort::Medium => 4096,
+        ReasoningEffort::High => 16384,
+    }
+}
+
+impl InterpretationProvider for AnthropicProvider {
+    fn info(&self) -> ProviderInfo {
+        ProviderInfo {
+            id: InterpretationBackend::Claude,
+            name: "Claude".to_string(),
+            capabilities: Capabilities {
+                streaming: true,
+                structured_output: true,
+            },
+            models: self.models.models(),
+        }
+    }
+
+    fn models(&self) -> &ModelConfig {
+        &self.models
+    }
+
+    fn complete<'a>(&'a self, prompt: &'a Prompt, model: &'a str) -> ProviderFuture<'a> {
+        Box::pin(self.message(prompt, model, None))
+    }
+
+    fn stream<'a>(
+        &'a self,
+        prompt: &'a Prompt,
+        model: &'a str,
+        on_chunk: &'a ChunkSink<'a>,
+    ) -> ProviderFuture<'a> {
+        Box::pin(self.message(prompt, model, Some(on_chunk)))
+    }
+}
+
+/// Replaces the JSON of an API error with its type and message.
+fn readable_error(error: ExplainError) -> ExplainError {
+    match error {
+        ExplainError::ApiError {
+            status,
+            body,
+            retry_after,
+        } => {
+            let body = match serde_json::from_str::<ErrorResponse>(&body) {
+                Ok(parsed) => format!("{}: {}", parsed.error.kind, parsed.error.message),
+                Err(_) => body,
+            };
+            ExplainError::ApiError {
+                status,
+                body,
+                retry_after,
+            }
+        }
+        error => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::GenerationParams;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn prompt() -> Prompt {
+        Prompt {
+            system: "system".to_string(),
+            user: "user".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn anthropic_sends_the_system_prompt_and_joins_or_streams_the_text() {
+        let mut server = Server::new_async().await;
+        unsafe {
+            std::env::set_var("ANTHROPIC_BASE_URL", server.url());
+        }
+        let _ok = server
+            .mock("POST", "/v1/messages")
+            .match_header("x-api-key", "sk-ant")
+            .match_header("anthropic-version", API_VERSION)
+            .match_body(Matcher::PartialJson(json!({
+                "model": "claude-opus-4-1",
+                "max_tokens": 3024,
+                "thinking": { "type": "enabled", "budget_tokens": 1024 },
+                "system": "system",
+                "messages": [{ "role": "user", "content": "user" }]
+            })))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "content": [
+                        { "type": "thinking", "thinking": "The querent asks…" },
+                        { "type": "text", "text": "Claude " },
+                        { "type": "text", "text": "says" }
+                    ],
+                    "stop_reason": "end_turn"
+                })
+                .to_string(),
+            )
+            .create();
+        let _overloaded = server
+            .mock("POST", "/v1/messages")
+            .match_header("x-api-key", "busy")
+            .with_status(529)
+            .with_body(
+                json!({
+                    "type": "error",
+                    "error": { "type": "overloaded_error", "message": "Overloaded" }
+                })
+                .to_string(),
+            )
+            .create();
+
+        let models = ModelConfig {
+            params: GenerationParams {
+                temperature: Some(0.2),
+                max_tokens: Some(2000),
+                reasoning_effort: Some(ReasoningEffort::Low),
+            },
+            ..ModelConfig::new(DEFAULT_MODEL)
+        };
+        let provider = AnthropicProvider::new("sk-ant".to_string(), models);
+        assert_eq!(
+            "Claude says",
+            provider
+                .complete(&prompt(), "claude-opus-4-1")
+                .await
+                .unwrap()
+                .text
+        );
+
+        match AnthropicProvider::new("busy".to_string(), ModelConfig::new(DEFAULT_MODEL))
+            .complete(&prompt(), DEFAULT_MODEL)
+            .await
+        {
+            Err(ExplainError::ApiError { status, body, .. }) => {
+                assert_eq!(529, status.as_u16());
+                assert_eq!("overloaded_error: Overloaded", body);
+            }
+            other => panic!("expected an API error, got {:?}", other),
+        }
+        assert!(matches!(
+            AnthropicProvider::new(String::new(), ModelConfig::new(DEFAULT_MODEL))
+                .complete(&prompt(), DEFAULT_MODEL)
+                .await,
+            Err(ExplainError::MissingApiKey)
+        ));
+
+        // Streamed, the answer arrives as text deltas among other events
+        let events = [
+            json!({ "type": "message_start",
+                    "message": { "content": [], "usage": { "input_tokens": 412, "output_tokens": 1 } } }),
+            json!({ "type": "content_block_delta", "index": 0,
+                    "delta": { "type": "thinking_delta", "thinking": "Hmm" } }),
+            json!({ "type": "content_block_delta", "index": 1,
+                    "delta": { "type": "text_delta", "text": "The Tower " } }),
+            json!({ "type": "content_block_delta", "index": 1,
+                    "delta": { "type": "text_delta", "text": "falls." } }),
+            json!({ "type": "message_delta", "delta": { "stop_reason": "end_turn" },
+                    "usage": { "output_tokens": 57 } }),
+            json!({ "type": "message_stop" }),
+        ];
+        let body = events
+            .iter()
+            .map(|event| format!("event: {}\ndata: {}\n\n", event["type"], event))
+            .collect::<String>();
+        let _stream = server
+            .mock("POST", "/v1/messages")
+            .match_header("x-api-key", "sk-stream")
+            .match_body(Matcher::PartialJson(json!({ "stream": true })))
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(body)
+            .create();
+        let _failing = server
+            .mock("POST", "/v1/messages")
+            .match_header("x-api-key", "sk-overloaded")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(format!(
+                "event: error\ndata: {}\n\n",
+                json!({ "type": "error",
+                        "error": { "type": "overloaded_error", "message": "Overloaded" } })
+            ))
+            .create();
+
+        let chunks = Mutex::new(Vec::new());
+        let on_chunk = |chunk: &str| chunks.lock().unwrap().push(chunk.to_string());
+        let provider =
+            AnthropicProvider::new("sk-stream".to_string(), ModelConfig::new(DEFAULT_MODEL));
+        let completion = provider
+            .stream(&prompt(), DEFAULT_MODEL, &on_chunk)
+            .await
+            .unwrap();
+        assert_eq!("The Tower falls.", completion.text);
+        assert_eq!(
+            Some(Usage {
+                prompt_tokens: 412,
+                completion_tokens: 57
+            }),
+            completion.usage
+        );
+        assert_eq!(vec!["The Tower ", "falls."], *chunks.lock().unwrap());
+
+        match AnthropicProvider::new("sk-overloaded".to_string(), ModelConfig::new(DEFAULT_MODEL))
+            .stream(&prompt(), DEFAULT_MODEL, &on_chunk)
+            .await
+        {
+            Err(ExplainError::ApiError { body, .. }) => {
+                assert_eq!("overloaded_error: Overloaded", body)
+            }
+            other => panic!("expected an API error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Google Generative Language API (Gemini).
+
+use super::{
+    Capabilities, ChunkSink, Completion, CompletionResult, InterpretationProvider, ModelConfig,
+    Prompt, ProviderFuture, ProviderInfo, Role, Usage,
+};
+use crate::explain::{ExplainError, InterpretationBackend};
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+pub const DEFAULT_MODEL: &str = "gemini-3-flash-preview";
+
+#[derive(Deserialize)]
+struct GeminiPart {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GeminiContent {
+    parts: Option<Vec<GeminiPart>>,
+}
+
+#[derive(Deserialize)]
+struct GeminiCandidate {
+    content: Option<GeminiContent>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    candidates: Option<Vec<GeminiCandidate>>,
+    /// Totals so far; a stream repeats them on every piece.
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u32,
+    #[serde(default)]
+    candidates_token_count: u32,
+    /// Thinking is billed as output.
+    #[serde(default)]
+    thoughts_token_count: u32,
+}
+
+impl From<UsageMetadata> for Usage {
+    fn from(value: UsageMetadata) -> Self {
+        Self {
+            prompt_tokens: value.prompt_token_count,
+            completion_tokens: value.candidates_token_count + value.thoughts_token_count,
+        }
+    }
+}
+
+impl GeminiResponse {
+    /// Text of the first candidate: its first part, or every part of a streamed piece.
+    fn text(&mut self, every_part: bool) -> Option<String> {
+        let mut parts = self
+            .candidates
+            .take()?
+            .into_iter()
+            .next()?
+            .content?
+            .parts?
+            .into_iter()
+            .filter_map(|part| part.text);
+        if every_part {
+            Some(parts.collect())
+        } else {
+            parts.next()
+        }
+    }
+}
+
+pub struct GeminiProvider {
+    client: reqwest::Client,
+    api_key: String,
+    models: ModelConfig,
+}
+
+impl GeminiProvider {
+    pub fn new(api_key: String, models: ModelConfig) -> Self {
+        Self {
+            client: super::http_client(),
+            api_key,
+            models,
+        }
+    }
+
+    async fn generate(
+        &self,
+        prompt: &Prompt,
+        model: &str,
+        on_chunk: Option<&ChunkSink<'_>>,
+    ) -> CompletionResult {
+        if self.api_key.trim().is_empty() {
+            return Err(ExplainError::MissingApiKey);
+        }
+
+        // Allow overriding the base URL via env var for testing
+        let base_url =
+            std::env::var("GOOGLE_AI_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let method = match on_chunk {
+            Some(_) => "streamGenerateContent",
+            None => "generateContent",
+        };
+        let endpoint = format!(
+            "{}/v1beta/models/{}:{}",
+            base_url.trim_end_matches('/'),
+            model,
+            method
+        );
+
+        let mut body = serde_json::json!({
+            "systemInstruction": {
+                "role": "system",
+                "parts": [{"text": prompt.system}]
+            },
+            "contents": prompt
+                .messages()
+                .map(|(role, text)| {
+                    // Gemini calls the assistant the model
+                    let role = match role {
+                        Role::User => "user",
+                        Role::Assistant => "model",
+                    };
+                    serde_json::json!({"role": role, "parts": [{"text": text}]})
+                })
+                .collect::<Vec<_>>()
+        });
+        let params = &self.models.params;
+        let mut config = serde_json::Map::new();
+        if let Some(temperature) = params.temperature {
+            config.insert("temperature".into(), temperature.into());
+        }
+        if let Some(max_tokens) = params.max_tokens {
+            config.insert("maxOutputTokens".into(), max_tokens.into());
+        }
+        if let Some(effort) = params.reasoning_effort {
+            config.insert(
+                "thinkingConfig".into(),
+                serde_json::json!({ "thinkingLevel": effort.id() }),
+            );
+        }
+        if let Some(schema) = &prompt.response_schema {
+            config.insert("responseMimeType".into(), "application/json".into());
+            config.insert("responseJsonSchema".into(), schema.clone());
+        }
+        if !config.is_empty() {
+            body["generationConfig"] = config.into();
+        }
+
+        let request = self
+            .client
+            .post(endpoint)
+            .query(&[("key", self.api_key.as_str())]);
+        let Some(on_chunk) = on_chunk else {
+            let parsed: GeminiResponse = super::post_json(request, &body).await?;
+            return Self::completion(parsed);
+        };
+        let mut text = String::new();
+        let mut usage = None;
+        let whole: Option<GeminiResponse> = super::post_events(
+            request.query(&[("alt", "sse")]),
+            &body,
+            |mut event: GeminiResponse| {
+                if let Some(piece) = event.text(true).filter(|piece| !piece.is_empty()) {
+                    on_chunk(&piece);
+                    text.push_str(&piece);
+                }
+                usage = event.usage_metadata.map(Into::into).or(usage);
+                Ok(())
+            },
+        )
+        .await?;
+        match whole {
+            // The server ignored `alt=sse` and answered at once
+            Some(parsed) => {
+                let completion = Self::completion(parsed)?;
+                on_chunk(&completion.text);
+                Ok(completion)
+            }
+            None => super::non_empty(Some(text)).map(|text| Completion { text, usage }),
+        }
+    }
+
+    fn completion(mut parsed: GeminiResponse) -> CompletionResult {
+        let text = super::non_empty(parsed.text(false))?;
+        Ok(Completion {
+            text,
+            usage: parsed.usage_metadata.map(Into::into),
+        })
+    }
+}
+
+impl InterpretationProvider for GeminiProvider {
+    fn info(&self) -> ProviderInfo {
+        ProviderInfo {
+            id: InterpretationBackend::Gemini,
+            name: "Gemini".to_string(),
+            capabilities: Capabilities {
+                streaming: true,
+                structured_output: true,
+            },
+            models: self.models.models(),
+        }
+    }
+
+    fn models(&self) -> &ModelConfig {
+        &self.models
+    }
+
+    fn complete<'a>(&'a self, prompt: &'a Prompt, model: &'a str) -> ProviderFuture<'a> {
+        Box::pin(self.generate(prompt, model, None))
+    }
+
+    fn stream<'a>(
+        &'a self,
+        prompt: &'a Prompt,
+        model: &'a str,
+        on_chunk: &'a ChunkSink<'a>,
+    ) -> ProviderFuture<'a> {
+        Box::pin(self.generate(prompt, model, Some(on_chunk)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{GenerationParams, ReasoningEffort};
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn gemini_sends_the_prompts_and_reads_the_first_part() {
+        let mut server = Server::new_async().await;
+        unsafe {
+            std::env::set_var("GOOGLE_AI_BASE_URL", server.url());
+        }
+        let _m = server
+            .mock(
+                "POST",
+                "/v1beta/models/gemini-3-flash-preview:generateContent",
+            )
+            .match_query(Matcher::UrlEncoded("key".into(), "goog".into()))
+            .match_body(Matcher::PartialJson(json!({
+                "systemInstruction": { "parts": [{ "text": "system" }] },
+                "contents": [{ "role": "user", "parts": [{ "text": "user" }] }],
+                "generationConfig": {
+                    "temperature": 0.5,
+                    "thinkingConfig": { "thinkingLevel": "low" }
+                }
+            })))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "candidates": [{ "content": { "parts": [{ "text": "Gemini says" }] } }],
+                    "usageMetadata": {
+                        "promptTokenCount": 380,
+                        "candidatesTokenCount": 120,
+                        "thoughtsTokenCount": 64
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+
+        let prompt = Prompt {
+            system: "system".to_string(),
+            user: "user".to_string(),
+            ..Default::default()
+        };
+        let models = ModelConfig {
+            params: GenerationParams {
+                temperature: Some(0.5),
+                max_tokens: None,
+                reasoning_effort: Some(ReasoningEffort::Low),
+            },
+            ..ModelConfig::new(DEFAULT_MODEL)
+        };
+        let provider = GeminiProvider::new("goog".to_string(), models.clone());
+        let completion = provider.complete(&prompt, DEFAULT_MODEL).await.unwrap();
+        assert_eq!("Gemini says", completion.text);
+        // Thinking is billed with the answer
+        assert_eq!(
+            Some(Usage {
+                prompt_tokens: 380,
+                completion_tokens: 184
+            }),
+            completion.usage
+        );
+        assert!(matches!(
+            GeminiProvider::new(String::new(), models)
+                .complete(&prompt, DEFAULT_MODEL)
+                .await,
+            Err(ExplainError::MissingApiKey)
+        ));
+    }
+}
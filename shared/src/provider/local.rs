@@ -0,0 +1,308 @@
+//! Self-hosted models behind an OpenAI-compatible API: Ollama, llama.cpp's server, vLLM and
+//! the like. Questions never leave the machines running them.
+
+use super::openai::{ChatModel, chat_completion};
+use super::{
+    Capabilities, ChunkSink, CompletionResult, InterpretationProvider, ModelConfig, Prompt,
+    ProviderFuture, ProviderInfo,
+};
+use crate::explain::InterpretationBackend;
+
+/// Where Ollama listens by default.
+pub const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+pub const DEFAULT_MODEL: &str = "llama3.2";
+
+/// Endpoint of a local provider; its models are configured like any other provider's.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalConfig {
+    /// Server root, without the `/v1` the chat completions endpoint sits under.
+    pub base_url: String,
+    /// Sent as a bearer token, for servers behind a proxy that asks for one.
+    pub api_key: Option<String>,
+}
+
+impl Default for LocalConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: None,
+        }
+    }
+}
+
+pub struct LocalProvider {
+    client: reqwest::Client,
+    config: LocalConfig,
+    models: ModelConfig,
+}
+
+impl LocalProvider {
+    /// `models` names models as the server knows them (e.g. `llama3.2` for Ollama).
+    pub fn new(config: LocalConfig, models: ModelConfig) -> Self {
+        Self {
+            client: super::http_client(),
+            config,
+            models,
+        }
+    }
+
+    async fn chat(
+        &self,
+        prompt: &Prompt,
+        model: &str,
+        on_chunk: Option<&ChunkSink<'_>>,
+    ) -> CompletionResult {
+        let api_key = self
+            .config
+            .api_key
+            .as_deref()
+            .filter(|key| !key.trim().is_empty());
+        chat_completion(
+            &self.client,
+            &self.config.base_url,
+            api_key,
+            ChatModel {
+                model,
+                params: &self.models.params,
+                max_tokens_field: "max_tokens",
+            },
+            prompt,
+            on_chunk,
+        )
+        .await
+    }
+}
+
+impl InterpretationProvider for LocalProvider {
+    fn info(&self) -> ProviderInfo {
+        ProviderInfo {
+            id: InterpretationBackend::Local,
+            name: format!("Local ({})", self.models.model),
+            // Ollama, llama.cpp and vLLM all take OpenAI's `response_format`
+            capabilities: Capabilities {
+                streaming: true,
+                structured_output: true,
+            },
+            models: self.models.models(),
+        }
+    }
+
+    fn models(&self) -> &ModelConfig {
+        &self.models
+    }
+
+    fn complete<'a>(&'a self, prompt: &'a Prompt, model: &'a str) -> ProviderFuture<'a> {
+        Box::pin(self.chat(prompt, model, None))
+    }
+
+    fn stream<'a>(
+        &'a self,
+        prompt: &'a Prompt,
+        model: &'a str,
+        on_chunk: &'a ChunkSink<'a>,
+    ) -> ProviderFuture<'a> {
+        Box::pin(self.chat(prompt, model, Some(on_chunk)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::ExplainError;
+    use crate::provider::{GenerationParams, Role, Turn, Usage};
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn prompt() -> Prompt {
+        Prompt {
+            system: "system".to_string(),
+            user: "Will I get the job?".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn local_sends_the_model_and_reads_the_first_choice() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", Matcher::Missing)
+            .match_body(Matcher::PartialJson(json!({
+                "model": "qwen3:8b",
+                "temperature": 0.5,
+                "max_tokens": 800,
+                "messages": [
+                    { "role": "system", "content": "system" },
+                    { "role": "user", "content": "Will I get the job?" }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "choices": [{ "message": { "content": "Yes" } }] }).to_string())
+            .create();
+
+        let provider = LocalProvider::new(
+            LocalConfig {
+                base_url: format!("{}/", server.url()),
+                api_key: None,
+            },
+            ModelConfig {
+                params: GenerationParams {
+                    temperature: Some(0.5),
+                    max_tokens: Some(800),
+                    reasoning_effort: None,
+                },
+                ..ModelConfig::new("qwen3:8b")
+            },
+        );
+        assert_eq!("Local (qwen3:8b)", provider.info().name);
+        assert_eq!(
+            "Yes",
+            provider.complete(&prompt(), "qwen3:8b").await.unwrap().text
+        );
+    }
+
+    #[tokio::test]
+    async fn local_sends_the_conversation_after_the_prompt() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(json!({
+                "messages": [
+                    { "role": "system", "content": "system" },
+                    { "role": "user", "content": "Will I get the job?" },
+                    { "role": "assistant", "content": "Yes." },
+                    { "role": "user", "content": "When?" }
+                ]
+            })))
+            .with_status(200)
+            .with_body(json!({ "choices": [{ "message": { "content": "Soon." } }] }).to_string())
+            .create();
+
+        let provider = LocalProvider::new(
+            LocalConfig {
+                base_url: server.url(),
+                api_key: None,
+            },
+            ModelConfig::new(DEFAULT_MODEL),
+        );
+        let prompt = Prompt {
+            conversation: vec![
+                Turn {
+                    role: Role::Assistant,
+                    text: "Yes.".to_string(),
+                },
+                Turn {
+                    role: Role::User,
+                    text: "When?".to_string(),
+                },
+            ],
+            ..prompt()
+        };
+        assert_eq!(
+            "Soon.",
+            provider
+                .complete(&prompt, DEFAULT_MODEL)
+                .await
+                .unwrap()
+                .text
+        );
+    }
+
+    #[tokio::test]
+    async fn local_sends_the_api_key_and_reports_server_errors() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer local-key")
+            .with_status(404)
+            .with_body(r#"{"error":"model \"llama3.2\" not found"}"#)
+            .create();
+
+        let provider = LocalProvider::new(
+            LocalConfig {
+                base_url: server.url(),
+                api_key: Some("local-key".to_string()),
+            },
+            ModelConfig::new(DEFAULT_MODEL),
+        );
+        match provider.complete(&prompt(), DEFAULT_MODEL).await {
+            Err(ExplainError::ApiError { status, body, .. }) => {
+                assert_eq!(404, status.as_u16());
+                assert!(body.contains("not found"));
+            }
+            other => panic!("expected an API error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn local_streams_deltas_and_falls_back_to_a_whole_answer() {
+        let mut server = Server::new_async().await;
+        let _streaming = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(
+                json!({ "model": "qwen3:8b", "stream": true,
+                        "stream_options": { "include_usage": true } }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(
+                [
+                    json!({ "choices": [{ "delta": { "role": "assistant" } }] }).to_string(),
+                    json!({ "choices": [{ "delta": { "content": "Yes, " } }] }).to_string(),
+                    json!({ "choices": [{ "delta": { "content": "soon." } }] }).to_string(),
+                    json!({ "choices": [],
+                            "usage": { "prompt_tokens": 90, "completion_tokens": 4 } })
+                    .to_string(),
+                    "[DONE]".to_string(),
+                ]
+                .iter()
+                .map(|data| format!("data: {}\r\n\r\n", data))
+                .collect::<String>(),
+            )
+            .create();
+        // Servers that do not stream answer with the whole completion
+        let _whole = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(json!({ "model": "phi4" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "choices": [{ "message": { "content": "No" } }] }).to_string())
+            .create();
+
+        let provider = LocalProvider::new(
+            LocalConfig {
+                base_url: server.url(),
+                api_key: None,
+            },
+            ModelConfig::new("qwen3:8b"),
+        );
+        let chunks = Mutex::new(Vec::new());
+        let on_chunk = |chunk: &str| chunks.lock().unwrap().push(chunk.to_string());
+        let completion = provider
+            .stream(&prompt(), "qwen3:8b", &on_chunk)
+            .await
+            .unwrap();
+        assert_eq!("Yes, soon.", completion.text);
+        assert_eq!(
+            Some(Usage {
+                prompt_tokens: 90,
+                completion_tokens: 4
+            }),
+            completion.usage
+        );
+        assert_eq!(vec!["Yes, ", "soon."], *chunks.lock().unwrap());
+
+        chunks.lock().unwrap().clear();
+        assert_eq!(
+            "No",
+            provider
+                .stream(&prompt(), "phi4", &on_chunk)
+                .await
+                .unwrap()
+                .text
+        );
+        assert_eq!(vec!["No"], *chunks.lock().unwrap());
+    }
+}
@@ -0,0 +1,222 @@
+//! OpenAI chat completions.
+
+use super::{
+    Capabilities, ChunkSink, Completion, CompletionResult, GenerationParams,
+    InterpretationProvider, ModelConfig, Prompt, ProviderFuture, ProviderInfo, Usage,
+};
+use crate::explain::{ExplainError, InterpretationBackend};
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+pub const DEFAULT_MODEL: &str = "gpt-5.1";
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Deserialize)]
+struct ChatUsage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+}
+
+impl From<ChatUsage> for Usage {
+    fn from(value: ChatUsage) -> Self {
+        Self {
+            prompt_tokens: value.prompt_tokens,
+            completion_tokens: value.completion_tokens,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: Message,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    content: String,
+}
+
+/// One event of a streamed chat completion. The last one only carries the usage.
+#[derive(Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Deserialize)]
+struct ChunkChoice {
+    delta: Delta,
+}
+
+#[derive(Deserialize)]
+struct Delta {
+    content: Option<String>,
+}
+
+pub struct OpenAiProvider {
+    client: reqwest::Client,
+    api_key: String,
+    models: ModelConfig,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: String, models: ModelConfig) -> Self {
+        Self {
+            client: super::http_client(),
+            api_key,
+            models,
+        }
+    }
+
+    async fn chat(
+        &self,
+        prompt: &Prompt,
+        model: &str,
+        on_chunk: Option<&ChunkSink<'_>>,
+    ) -> CompletionResult {
+        if self.api_key.trim().is_empty() {
+            return Err(ExplainError::MissingApiKey);
+        }
+        // Allow overriding the base URL via env var for testing
+        let base_url =
+            std::env::var("OPENAI_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        chat_completion(
+            &self.client,
+            &base_url,
+            Some(&self.api_key),
+            ChatModel {
+                model,
+                params: &self.models.params,
+                // OpenAI's reasoning models only take the newer name
+                max_tokens_field: "max_completion_tokens",
+            },
+            prompt,
+            on_chunk,
+        )
+        .await
+    }
+}
+
+impl InterpretationProvider for OpenAiProvider {
+    fn info(&self) -> ProviderInfo {
+        ProviderInfo {
+            id: InterpretationBackend::ChatGPT,
+            name: "ChatGPT".to_string(),
+            capabilities: Capabilities {
+                streaming: true,
+                structured_output: true,
+            },
+            models: self.models.models(),
+        }
+    }
+
+    fn models(&self) -> &ModelConfig {
+        &self.models
+    }
+
+    fn complete<'a>(&'a self, prompt: &'a Prompt, model: &'a str) -> ProviderFuture<'a> {
+        Box::pin(self.chat(prompt, model, None))
+    }
+
+    fn stream<'a>(
+        &'a self,
+        prompt: &'a Prompt,
+        model: &'a str,
+        on_chunk: &'a ChunkSink<'a>,
+    ) -> ProviderFuture<'a> {
+        Box::pin(self.chat(prompt, model, Some(on_chunk)))
+    }
+}
+
+/// The model a chat completion asks for, and how the server names the answer length.
+pub(crate) struct ChatModel<'a> {
+    pub model: &'a str,
+    pub params: &'a GenerationParams,
+    pub max_tokens_field: &'a str,
+}
+
+/// Sends `prompt` to the chat completions endpoint under `base_url`, which every
+/// OpenAI-compatible server offers. With `on_chunk`, asks for the answer as a stream and hands
+/// it over piece by piece.
+pub(crate) async fn chat_completion(
+    client: &reqwest::Client,
+    base_url: &str,
+    api_key: Option<&str>,
+    model: ChatModel<'_>,
+    prompt: &Prompt,
+    on_chunk: Option<&ChunkSink<'_>>,
+) -> CompletionResult {
+    let messages = std::iter::once(serde_json::json!({"role": "system", "content": prompt.system}))
+        .chain(
+            prompt
+                .messages()
+                .map(|(role, text)| serde_json::json!({"role": role.id(), "content": text})),
+        )
+        .collect::<Vec<_>>();
+    let mut body = serde_json::json!({
+        "model": model.model,
+        "messages": messages
+    });
+    if let Some(temperature) = model.params.temperature {
+        body["temperature"] = temperature.into();
+    }
+    if let Some(max_tokens) = model.params.max_tokens {
+        body[model.max_tokens_field] = max_tokens.into();
+    }
+    if let Some(effort) = model.params.reasoning_effort {
+        body["reasoning_effort"] = effort.id().into();
+    }
+    if let Some(schema) = &prompt.response_schema {
+        body["response_format"] = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": { "name": "interpretation", "strict": true, "schema": schema }
+        });
+    }
+    let endpoint = format!("{}/v1/chat/completions", base_url.trim_end_matches('/'));
+    let mut request = client.post(endpoint);
+    if let Some(api_key) = api_key {
+        request = request.bearer_auth(api_key);
+    }
+    let first_choice = |parsed: ChatResponse| {
+        let usage = parsed.usage.map(Into::into);
+        let text = parsed
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content);
+        super::non_empty(text).map(|text| Completion { text, usage })
+    };
+
+    let Some(on_chunk) = on_chunk else {
+        let parsed: ChatResponse = super::post_json(request, &body).await?;
+        return first_choice(parsed);
+    };
+    body["stream"] = true.into();
+    body["stream_options"] = serde_json::json!({ "include_usage": true });
+    let mut text = String::new();
+    let mut usage = None;
+    let whole = super::post_events(request, &body, |event: ChatChunk| {
+        for content in event.choices.into_iter().filter_map(|c| c.delta.content) {
+            on_chunk(&content);
+            text.push_str(&content);
+        }
+        usage = event.usage.map(Into::into).or(usage);
+        Ok(())
+    })
+    .await?;
+    match whole {
+        // The server ignored `stream` and answered at once
+        Some(parsed) => {
+            let completion = first_choice(parsed)?;
+            on_chunk(&completion.text);
+            Ok(completion)
+        }
+        None => super::non_empty(Some(text)).map(|text| Completion { text, usage }),
+    }
+}
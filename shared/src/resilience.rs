@@ -0,0 +1,283 @@
+//! Riding out flaky providers: retries with exponential backoff, a circuit breaker per
+//! provider and failover to other providers.
+//!
+//! [`InterpretationService::interpret`](crate::explain::InterpretationService::interpret) applies
+//! the [`ResilienceConfig`] it was built with.
+
+use crate::explain::{ExplainError, InterpretationBackend};
+use rand::Rng;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often and how patiently a provider is asked again after a transient error.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Calls made to a provider for one interpretation, the first one included.
+    pub max_attempts: u32,
+    /// Wait before the first retry; it doubles with every retry after it.
+    pub base_delay: Duration,
+    /// Longest wait between two calls. A provider asking, with `Retry-After`, to wait longer
+    /// than this is not retried.
+    pub max_delay: Duration,
+    /// Longest an interpretation keeps retrying and failing over: no call is started once it
+    /// has gone by. A call already made runs to its own timeout.
+    pub max_elapsed: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_elapsed: Duration::from_secs(150),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait before retry number `retry` (from 1) after `error`; `None` when it
+    /// should not be retried.
+    pub fn delay(&self, retry: u32, error: &ExplainError) -> Option<Duration> {
+        if retry >= self.max_attempts || !error.is_transient() {
+            return None;
+        }
+        if let ExplainError::ApiError {
+            retry_after: Some(retry_after),
+            ..
+        } = error
+        {
+            return (*retry_after <= self.max_delay).then_some(*retry_after);
+        }
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry - 1))
+            .min(self.max_delay);
+        // Jitter keeps readings that failed together from retrying together
+        Some(rand::rng().random_range(backoff / 2..=backoff))
+    }
+}
+
+/// When a provider's circuit breaker opens, and for how long.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BreakerConfig {
+    /// Transient failures in a row that open the breaker.
+    pub failure_threshold: u32,
+    /// How long an open breaker turns requests away before letting one through to test the
+    /// provider.
+    pub cooldown: Duration,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BreakerState {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A single request is testing whether the provider is back.
+    HalfOpen,
+}
+
+/// Stops calling a provider that keeps failing, so readings fail over or fail fast instead of
+/// waiting on it.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: BreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: BreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState::Closed { failures: 0 }),
+        }
+    }
+
+    /// Whether the provider may be called now. Once the cooldown is over, lets one call through
+    /// and turns the others away until it is recorded.
+    pub fn allow(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            BreakerState::Closed { .. } => true,
+            BreakerState::Open { until } if Instant::now() >= until => {
+                *state = BreakerState::HalfOpen;
+                true
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen => false,
+        }
+    }
+
+    pub fn record_success(&self) {
+        *self.state.lock().unwrap() = BreakerState::Closed { failures: 0 };
+    }
+
+    /// Counts a transient failure; other errors say nothing about the provider's health.
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let failures = match *state {
+            BreakerState::Closed { failures } => failures + 1,
+            BreakerState::Open { .. } | BreakerState::HalfOpen => self.config.failure_threshold,
+        };
+        *state = if failures >= self.config.failure_threshold {
+            BreakerState::Open {
+                until: Instant::now() + self.config.cooldown,
+            }
+        } else {
+            BreakerState::Closed { failures }
+        };
+    }
+
+    pub fn is_open(&self) -> bool {
+        !matches!(*self.state.lock().unwrap(), BreakerState::Closed { .. })
+    }
+}
+
+/// Retries, circuit breaking and failover for interpretations.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResilienceConfig {
+    pub retry: RetryPolicy,
+    pub breaker: BreakerConfig,
+    /// Backends to try, in order, when the one a reading asked for keeps failing or its breaker
+    /// is open. Each interprets with its default model.
+    pub failover: Vec<InterpretationBackend>,
+}
+
+impl ResilienceConfig {
+    /// Reads `INTERPRETATION_MAX_ATTEMPTS`, `INTERPRETATION_RETRY_DELAY_MS` (the first wait),
+    /// `INTERPRETATION_RETRY_BUDGET_SECS`, `INTERPRETATION_BREAKER_THRESHOLD`, `INTERPRETATION_BREAKER_COOLDOWN_SECS` and
+    /// `INTERPRETATION_FAILOVER` (comma-separated backend ids, e.g. `gemini,claude`).
+    ///
+    /// # Panics
+    ///
+    /// When a variable is set to something it cannot be parsed as.
+    pub fn from_env() -> Self {
+        fn parse<T: FromStr>(name: &str) -> Option<T> {
+            let value = std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())?;
+            Some(
+                value
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Invalid {}: {}", name, value)),
+            )
+        }
+
+        let defaults = Self::default();
+        Self {
+            retry: RetryPolicy {
+                max_attempts: parse::<u32>("INTERPRETATION_MAX_ATTEMPTS")
+                    .unwrap_or(defaults.retry.max_attempts)
+                    .max(1),
+                base_delay: parse("INTERPRETATION_RETRY_DELAY_MS")
+                    .map(Duration::from_millis)
+                    .unwrap_or(defaults.retry.base_delay),
+                max_elapsed: parse("INTERPRETATION_RETRY_BUDGET_SECS")
+                    .map(Duration::from_secs)
+                    .unwrap_or(defaults.retry.max_elapsed),
+                ..defaults.retry
+            },
+            breaker: BreakerConfig {
+                failure_threshold: parse::<u32>("INTERPRETATION_BREAKER_THRESHOLD")
+                    .unwrap_or(defaults.breaker.failure_threshold)
+                    .max(1),
+                cooldown: parse("INTERPRETATION_BREAKER_COOLDOWN_SECS")
+                    .map(Duration::from_secs)
+                    .unwrap_or(defaults.breaker.cooldown),
+            },
+            failover: std::env::var("INTERPRETATION_FAILOVER")
+                .map(|ids| {
+                    ids.split(',')
+                        .map(str::trim)
+                        .filter(|id| !id.is_empty())
+                        .map(|id| {
+                            id.parse().unwrap_or_else(|_| {
+                                panic!("Unknown interpretation provider: {}", id)
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    fn api_error(status: u16, retry_after: Option<u64>) -> ExplainError {
+        ExplainError::ApiError {
+            status: StatusCode::from_u16(status).unwrap(),
+            body: String::new(),
+            retry_after: retry_after.map(Duration::from_secs),
+        }
+    }
+
+    #[test]
+    fn retry_policy_backs_off_exponentially_with_jitter() {
+        let policy = RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+            ..Default::default()
+        };
+        let error = api_error(503, None);
+        let first = policy.delay(1, &error).unwrap();
+        assert!((50..=100).contains(&first.as_millis()), "{:?}", first);
+        let second = policy.delay(2, &error).unwrap();
+        assert!((100..=200).contains(&second.as_millis()), "{:?}", second);
+        let capped = policy.delay(3, &error).unwrap();
+        assert!((150..=300).contains(&capped.as_millis()), "{:?}", capped);
+        assert_eq!(None, policy.delay(4, &error));
+
+        // Client errors are not retried; rate limits wait as long as the provider asks
+        assert_eq!(None, policy.delay(1, &api_error(400, None)));
+        assert_eq!(None, policy.delay(1, &ExplainError::MissingApiKey));
+        assert_eq!(
+            Some(Duration::from_secs(0)),
+            policy.delay(1, &api_error(429, Some(0)))
+        );
+        assert_eq!(None, policy.delay(1, &api_error(429, Some(60))));
+    }
+
+    #[test]
+    fn circuit_breaker_opens_after_failures_and_tests_after_the_cooldown() {
+        let breaker = CircuitBreaker::new(BreakerConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_millis(50),
+        });
+        breaker.record_failure();
+        assert!(breaker.allow());
+        breaker.record_failure();
+        assert!(breaker.is_open());
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(60));
+        // One request tests the provider while the others keep away
+        assert!(breaker.allow());
+        assert!(!breaker.allow());
+        breaker.record_failure();
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow());
+        breaker.record_success();
+        assert!(!breaker.is_open());
+        assert!(breaker.allow());
+    }
+}
@@ -0,0 +1,150 @@
+//! Interpretations written as separate sections rather than one block of text, for clients that
+//! lay each card's paragraph next to the card.
+
+use crate::t;
+use serde::{Deserialize, Serialize};
+
+/// The sections the system prompt asks interpretations to be organized in.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructuredInterpretation {
+    /// One analysis per card, in reading order.
+    pub cards: Vec<CardAnalysis>,
+    pub synthesis: String,
+    pub emotional_implications: String,
+    pub advice: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardAnalysis {
+    /// The card's number in the reading, from 1, as the prompt lists them.
+    pub number: usize,
+    pub analysis: String,
+}
+
+impl StructuredInterpretation {
+    /// JSON schema providers are held to. Every property is required and no other is allowed,
+    /// as OpenAI's strict mode asks.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "cards": {
+                    "type": "array",
+                    "description": "Card-by-card reading, one entry per card, in order.",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "number": {
+                                "type": "integer",
+                                "description": "The card's number in the list of cards, from 1."
+                            },
+                            "analysis": { "type": "string" }
+                        },
+                        "required": ["number", "analysis"],
+                        "additionalProperties": false
+                    }
+                },
+                "synthesis": {
+                    "type": "string",
+                    "description": "Integrated synthesis of the cards together."
+                },
+                "emotionalImplications": { "type": "string" },
+                "advice": {
+                    "type": "string",
+                    "description": "Orientation or practical advice."
+                }
+            },
+            "required": ["cards", "synthesis", "emotionalImplications", "advice"],
+            "additionalProperties": false
+        })
+    }
+
+    /// Reads a model's answer for a reading of `card_count` cards. Analyses of cards the reading
+    /// does not have, and repeated ones, are dropped and the rest put in reading order; a card
+    /// the model skipped has no analysis. `None` when the answer does not follow the schema.
+    pub fn parse(text: &str, card_count: usize) -> Option<Self> {
+        let mut parsed: Self = serde_json::from_str(text.trim()).ok()?;
+        parsed.cards.sort_by_key(|card| card.number);
+        parsed.cards.dedup_by_key(|card| card.number);
+        parsed
+            .cards
+            .retain(|card| (1..=card_count).contains(&card.number));
+        Some(parsed)
+    }
+
+    /// The interpretation as Markdown, like free-text interpretations are written, with each
+    /// analysis under its card's title (`titles[number - 1]`) and headings in `locale`.
+    pub fn to_markdown(&self, titles: &[String], locale: &str) -> String {
+        let mut sections = Vec::new();
+        if !self.cards.is_empty() {
+            let cards = self
+                .cards
+                .iter()
+                .map(|card| {
+                    let title = titles.get(card.number - 1).cloned().unwrap_or_default();
+                    format!("## {}. {}\n\n{}", card.number, title, card.analysis.trim())
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            sections.push(format!(
+                "# {}\n\n{}",
+                t!("structured.cards", locale = locale),
+                cards
+            ));
+        }
+        for (heading, text) in [
+            (t!("structured.synthesis", locale = locale), &self.synthesis),
+            (
+                t!("structured.emotional_implications", locale = locale),
+                &self.emotional_implications,
+            ),
+            (t!("structured.advice", locale = locale), &self.advice),
+        ] {
+            if !text.trim().is_empty() {
+                sections.push(format!("# {}\n\n{}", heading, text.trim()));
+            }
+        }
+        sections.join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_keeps_the_reading_cards_in_order() {
+        let text = json!({
+            "cards": [
+                { "number": 2, "analysis": "Second." },
+                { "number": 7, "analysis": "Not in the reading." },
+                { "number": 1, "analysis": "First." },
+                { "number": 2, "analysis": "Again." }
+            ],
+            "synthesis": "Together.",
+            "emotionalImplications": "",
+            "advice": "Rest."
+        })
+        .to_string();
+        let parsed = StructuredInterpretation::parse(&text, 3).unwrap();
+        assert_eq!(
+            vec![(1, "First."), (2, "Second.")],
+            parsed
+                .cards
+                .iter()
+                .map(|card| (card.number, card.analysis.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, StructuredInterpretation::parse("# The Fool", 3));
+
+        let titles = ["The Fool".to_string(), "The Magician — Past".to_string()];
+        assert_eq!(
+            "# Card by card\n\n## 1. The Fool\n\nFirst.\n\n## 2. The Magician — Past\n\nSecond.\
+             \n\n# Synthesis\n\nTogether.\n\n# Advice\n\nRest.",
+            parsed.to_markdown(&titles, "en")
+        );
+    }
+}
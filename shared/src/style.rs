@@ -0,0 +1,111 @@
+//! How a reading is interpreted: the interpreter's approach and how much it writes. Both add a
+//! localized fragment to the system prompt, except the defaults the prompt is written for.
+
+use crate::t;
+use serde::{Deserialize, Serialize};
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
+
+/// The interpreter's approach.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Hash,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ReadingStyle {
+    /// Emotional psychology and relational dynamics, as the system prompt describes.
+    #[default]
+    Psychological,
+    /// Classical divinatory meanings, symbolism and correspondences.
+    Traditional,
+    /// Concrete, actionable guidance.
+    Practical,
+    /// A short, direct answer to a yes-or-no question.
+    YesNo,
+}
+
+impl ReadingStyle {
+    pub fn id(&self) -> &'static str {
+        self.into()
+    }
+
+    /// What the system prompt is told about the style; `None` for the style it is written for.
+    pub fn prompt(&self) -> Option<String> {
+        match self {
+            ReadingStyle::Psychological => None,
+            _ => Some(t!(format!("reading_style.{}", self.id())).to_string()),
+        }
+    }
+}
+
+/// How much the interpreter writes.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Hash,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ReadingLength {
+    Brief,
+    #[default]
+    Standard,
+    InDepth,
+}
+
+impl ReadingLength {
+    pub fn id(&self) -> &'static str {
+        self.into()
+    }
+
+    /// What the system prompt is told about the length; `None` for the standard one.
+    pub fn prompt(&self) -> Option<String> {
+        match self {
+            ReadingLength::Standard => None,
+            _ => Some(t!(format!("reading_length.{}", self.id())).to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn every_style_and_length_but_the_defaults_has_a_prompt_in_every_locale() {
+        for locale in ["pt", "en"] {
+            for style in ReadingStyle::iter().filter(|s| *s != ReadingStyle::default()) {
+                let key = format!("reading_style.{}", style.id());
+                assert_ne!(key, t!(&key, locale = locale), "{} missing", key);
+            }
+            for length in ReadingLength::iter().filter(|l| *l != ReadingLength::default()) {
+                let key = format!("reading_length.{}", length.id());
+                assert_ne!(key, t!(&key, locale = locale), "{} missing", key);
+            }
+        }
+        assert_eq!(None, ReadingStyle::Psychological.prompt());
+        assert_eq!(None, ReadingLength::Standard.prompt());
+        assert_eq!(Ok(ReadingStyle::YesNo), "yesNo".parse());
+        assert_eq!("inDepth", ReadingLength::InDepth.id());
+    }
+}
@@ -0,0 +1,635 @@
+//! Versioned prompt templates.
+//!
+//! A template has a name, a version and, for each locale, a system prompt and a user prompt.
+//! `{{variable}}` is replaced with one of the [`VARIABLES`] of the reading;
+//! `{{#variable}}…{{/variable}}` is kept only when the variable is not empty, and
+//! `{{^variable}}…{{/variable}}` only when it is. A section tag alone on its line takes the line
+//! with it.
+//!
+//! Templates are built in (`shared/prompts`) or loaded at startup from a directory laid out the
+//! same way: `<name>/<version>/system.<locale>.md` and `user.<locale>.md`.
+
+use crate::provider::Prompt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error as StdError;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Template readings are interpreted with unless configured otherwise.
+pub const DEFAULT_TEMPLATE: &str = "classic";
+
+/// Variables a template may use.
+pub const VARIABLES: &[&str] = &[
+    "now",
+    "question",
+    "deck",
+    "deck_options",
+    "spread",
+    "cards",
+    "clarification",
+    "jumpers",
+    "context",
+    "user_name",
+    "user_self_description",
+    "style",
+    "length",
+];
+
+const BUILTIN: &[(&str, u32, &str, &str, &str)] = &[
+    (
+        "classic",
+        1,
+        "en",
+        include_str!("../prompts/classic/1/system.en.md"),
+        include_str!("../prompts/classic/1/user.en.md"),
+    ),
+    (
+        "classic",
+        1,
+        "pt",
+        include_str!("../prompts/classic/1/system.pt.md"),
+        include_str!("../prompts/classic/1/user.pt.md"),
+    ),
+    (
+        "classic",
+        2,
+        "en",
+        include_str!("../prompts/classic/2/system.en.md"),
+        include_str!("../prompts/classic/2/user.en.md"),
+    ),
+    (
+        "classic",
+        2,
+        "pt",
+        include_str!("../prompts/classic/2/system.pt.md"),
+        include_str!("../prompts/classic/2/user.pt.md"),
+    ),
+];
+
+/// Name and version of a template, as stored with readings.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TemplateRef {
+    pub name: String,
+    pub version: u32,
+}
+
+impl fmt::Display for TemplateRef {
+    /// `name@version`, which [`PromptTemplates::get`] takes to pin the version.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
+#[derive(Debug)]
+pub enum TemplateError {
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// A version directory whose name is not a number.
+    InvalidVersion(PathBuf),
+    Syntax {
+        path: PathBuf,
+        message: String,
+    },
+    /// A locale with a system prompt and no user prompt, or the other way around.
+    Incomplete {
+        template: TemplateRef,
+        locale: String,
+    },
+    Unknown(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            TemplateError::InvalidVersion(path) => {
+                write!(f, "{}: versions must be numbers", path.display())
+            }
+            TemplateError::Syntax { path, message } => {
+                write!(f, "{}: {}", path.display(), message)
+            }
+            TemplateError::Incomplete { template, locale } => write!(
+                f,
+                "{} needs both system.{locale}.md and user.{locale}.md",
+                template
+            ),
+            TemplateError::Unknown(name) => write!(f, "unknown prompt template: {}", name),
+        }
+    }
+}
+
+impl StdError for TemplateError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            TemplateError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Text(String),
+    Variable(&'static str),
+    Section {
+        name: &'static str,
+        inverted: bool,
+        body: Vec<Node>,
+    },
+}
+
+/// System and user text of each locale of a template.
+type Locales = BTreeMap<String, (Text, Text)>;
+
+/// A section being parsed; the outermost one is the whole text.
+struct Frame {
+    /// Name of the section and whether it is inverted.
+    section: Option<(&'static str, bool)>,
+    nodes: Vec<Node>,
+}
+
+/// Template text, parsed once when the template is loaded.
+#[derive(Clone, Debug)]
+struct Text(Vec<Node>);
+
+impl Text {
+    fn parse(source: &str) -> Result<Self, String> {
+        let variable = |name: &str| {
+            VARIABLES
+                .iter()
+                .find(|known| **known == name)
+                .copied()
+                .ok_or_else(|| format!("unknown variable {{{{{}}}}}", name))
+        };
+        // Open sections, innermost last, each with the nodes it holds so far
+        let mut stack = vec![Frame {
+            section: None,
+            nodes: Vec::new(),
+        }];
+        let mut rest = source;
+        let mut at_line_start = true;
+        while let Some(start) = rest.find("{{") {
+            let end = rest[start..]
+                .find("}}")
+                .map(|end| start + end)
+                .ok_or("unclosed {{")?;
+            let tag = rest[start + 2..end].trim();
+            let mut text = &rest[..start];
+            rest = &rest[end + 2..];
+
+            let is_section = tag.starts_with(['#', '^', '/']);
+            let line = text.rfind('\n').map_or(0, |newline| newline + 1);
+            let standalone = is_section
+                && (line > 0 || at_line_start)
+                && text[line..].trim().is_empty()
+                && (rest.is_empty() || rest.starts_with('\n') || rest.starts_with("\r\n"));
+            if standalone {
+                text = &text[..line];
+                rest = rest.strip_prefix('\r').unwrap_or(rest);
+                rest = rest.strip_prefix('\n').unwrap_or(rest);
+            }
+            at_line_start = standalone;
+            if !text.is_empty() {
+                stack
+                    .last_mut()
+                    .unwrap()
+                    .nodes
+                    .push(Node::Text(text.to_string()));
+            }
+
+            if let Some(name) = tag.strip_prefix('#') {
+                stack.push(Frame {
+                    section: Some((variable(name.trim())?, false)),
+                    nodes: Vec::new(),
+                });
+            } else if let Some(name) = tag.strip_prefix('^') {
+                stack.push(Frame {
+                    section: Some((variable(name.trim())?, true)),
+                    nodes: Vec::new(),
+                });
+            } else if let Some(name) = tag.strip_prefix('/') {
+                let Frame {
+                    section: Some((open, inverted)),
+                    nodes: body,
+                } = stack.pop().unwrap()
+                else {
+                    return Err(format!("{{{{{}}}}} closes no section", tag));
+                };
+                if open != name.trim() {
+                    return Err(format!("{{{{{}}}}} closes {{{{#{}}}}}", tag, open));
+                }
+                stack.last_mut().unwrap().nodes.push(Node::Section {
+                    name: open,
+                    inverted,
+                    body,
+                });
+            } else {
+                stack
+                    .last_mut()
+                    .unwrap()
+                    .nodes
+                    .push(Node::Variable(variable(tag)?));
+            }
+        }
+        if !rest.is_empty() {
+            stack
+                .last_mut()
+                .unwrap()
+                .nodes
+                .push(Node::Text(rest.to_string()));
+        }
+        match stack.pop() {
+            Some(Frame {
+                section: None,
+                nodes,
+            }) if stack.is_empty() => Ok(Self(nodes)),
+            Some(Frame {
+                section: Some((open, _)),
+                ..
+            }) => Err(format!("{{{{#{}}}}} is never closed", open)),
+            _ => unreachable!("the outermost frame is only popped at the end"),
+        }
+    }
+
+    fn uses(&self, variable: &str) -> bool {
+        fn any_uses(nodes: &[Node], variable: &str) -> bool {
+            nodes.iter().any(|node| match node {
+                Node::Text(_) => false,
+                Node::Variable(name) => *name == variable,
+                Node::Section { name, body, .. } => *name == variable || any_uses(body, variable),
+            })
+        }
+
+        any_uses(&self.0, variable)
+    }
+
+    fn render(&self, variables: &HashMap<&str, String>) -> String {
+        fn render_nodes(nodes: &[Node], variables: &HashMap<&str, String>, out: &mut String) {
+            for node in nodes {
+                match node {
+                    Node::Text(text) => out.push_str(text),
+                    Node::Variable(name) => {
+                        out.push_str(variables.get(name).map_or("", String::as_str))
+                    }
+                    Node::Section {
+                        name,
+                        inverted,
+                        body,
+                    } => {
+                        let present = variables.get(name).is_some_and(|value| !value.is_empty());
+                        if present != *inverted {
+                            render_nodes(body, variables, out);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut out = String::new();
+        render_nodes(&self.0, variables, &mut out);
+        out.trim().to_string()
+    }
+}
+
+/// One version of a named template, in every locale it was written for.
+#[derive(Clone, Debug)]
+pub struct PromptTemplate {
+    pub id: TemplateRef,
+    locales: Locales,
+}
+
+impl PromptTemplate {
+    /// Renders the prompt in `locale`, or in English when the template was not written for it.
+    pub fn render(&self, locale: &str, variables: &HashMap<&str, String>) -> Prompt {
+        let (system, user) = self
+            .locales
+            .get(locale)
+            .or_else(|| self.locales.get("en"))
+            .or_else(|| self.locales.values().next())
+            .expect("templates have at least one locale");
+        Prompt {
+            system: system.render(variables),
+            user: user.render(variables),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the prompt changes with `variable`, in any locale.
+    pub fn uses(&self, variable: &str) -> bool {
+        self.locales
+            .values()
+            .any(|(system, user)| system.uses(variable) || user.uses(variable))
+    }
+
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.locales.keys().map(String::as_str)
+    }
+}
+
+/// The templates a service may interpret with, and the one it uses unless asked otherwise.
+#[derive(Clone, Debug)]
+pub struct PromptTemplates {
+    templates: BTreeMap<String, BTreeMap<u32, Arc<PromptTemplate>>>,
+    default: String,
+}
+
+impl Default for PromptTemplates {
+    /// The built-in templates.
+    fn default() -> Self {
+        let mut templates = Self {
+            templates: BTreeMap::new(),
+            default: DEFAULT_TEMPLATE.to_string(),
+        };
+        let mut builtin: BTreeMap<(&str, u32), Locales> = BTreeMap::new();
+        for (name, version, locale, system, user) in BUILTIN {
+            let parse = |source| Text::parse(source).expect("built-in templates are valid");
+            builtin
+                .entry((name, *version))
+                .or_default()
+                .insert(locale.to_string(), (parse(system), parse(user)));
+        }
+        for ((name, version), locales) in builtin {
+            templates.insert(PromptTemplate {
+                id: TemplateRef {
+                    name: name.to_string(),
+                    version,
+                },
+                locales,
+            });
+        }
+        templates
+    }
+}
+
+impl PromptTemplates {
+    /// Adds `template`, replacing one with the same name and version.
+    pub fn insert(&mut self, template: PromptTemplate) {
+        self.templates
+            .entry(template.id.name.clone())
+            .or_default()
+            .insert(template.id.version, Arc::new(template));
+    }
+
+    /// `spec` is a template name, for its latest version, or `name@version`; the default
+    /// template when `None`.
+    pub fn get(&self, spec: Option<&str>) -> Option<&PromptTemplate> {
+        let spec = spec.unwrap_or(&self.default);
+        let (name, version) = match spec.split_once('@') {
+            Some((name, version)) => (name, Some(version.parse().ok()?)),
+            None => (spec, None),
+        };
+        let versions = self.templates.get(name)?;
+        let template = match version {
+            Some(version) => versions.get(&version),
+            None => versions.values().next_back(),
+        };
+        template.map(Arc::as_ref)
+    }
+
+    /// Makes `spec`, as taken by [`get`](Self::get), the template used unless asked otherwise.
+    pub fn set_default(&mut self, spec: &str) -> Result<(), TemplateError> {
+        if self.get(Some(spec)).is_none() {
+            return Err(TemplateError::Unknown(spec.to_string()));
+        }
+        self.default = spec.to_string();
+        Ok(())
+    }
+
+    /// Every template, oldest version first.
+    pub fn list(&self) -> Vec<TemplateRef> {
+        self.templates
+            .values()
+            .flat_map(|versions| versions.values().map(|template| template.id.clone()))
+            .collect()
+    }
+
+    /// Adds the templates under `dir`, laid out as `<name>/<version>/system.<locale>.md` and
+    /// `user.<locale>.md`; other files are ignored. Returns how many were loaded.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize, TemplateError> {
+        let mut loaded = 0;
+        for name_dir in sorted_dirs(dir)? {
+            let name = name_dir.file_name().unwrap().to_string_lossy().to_string();
+            for version_dir in sorted_dirs(&name_dir)? {
+                let id = TemplateRef {
+                    name: name.clone(),
+                    version: version_dir
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .parse()
+                        .map_err(|_| TemplateError::InvalidVersion(version_dir.clone()))?,
+                };
+                self.insert(load_version(id, &version_dir)?);
+                loaded += 1;
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// The built-in templates plus those under `PROMPT_TEMPLATES_DIR`, with `PROMPT_TEMPLATE`
+    /// (a name, or `name@version`) as the default.
+    ///
+    /// # Panics
+    ///
+    /// When a template cannot be loaded or `PROMPT_TEMPLATE` names none.
+    pub fn from_env() -> Self {
+        let mut templates = Self::default();
+        if let Ok(dir) = std::env::var("PROMPT_TEMPLATES_DIR")
+            && !dir.trim().is_empty()
+        {
+            templates
+                .load_dir(Path::new(dir.trim()))
+                .unwrap_or_else(|e| panic!("Invalid prompt templates: {}", e));
+        }
+        if let Ok(spec) = std::env::var("PROMPT_TEMPLATE")
+            && !spec.trim().is_empty()
+        {
+            templates
+                .set_default(spec.trim())
+                .unwrap_or_else(|e| panic!("Invalid PROMPT_TEMPLATE: {}", e));
+        }
+        templates
+    }
+}
+
+fn sorted_dirs(dir: &Path) -> Result<Vec<PathBuf>, TemplateError> {
+    let io = |error| TemplateError::Io {
+        path: dir.to_path_buf(),
+        error,
+    };
+    let mut dirs = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io)? {
+        let path = entry.map_err(io)?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn load_version(id: TemplateRef, dir: &Path) -> Result<PromptTemplate, TemplateError> {
+    let mut sources: BTreeMap<String, (Option<Text>, Option<Text>)> = BTreeMap::new();
+    let entries = std::fs::read_dir(dir).map_err(|error| TemplateError::Io {
+        path: dir.to_path_buf(),
+        error,
+    })?;
+    for entry in entries {
+        let path = entry
+            .map_err(|error| TemplateError::Io {
+                path: dir.to_path_buf(),
+                error,
+            })?
+            .path();
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        let Some((part, locale)) = file_name
+            .strip_suffix(".md")
+            .and_then(|stem| stem.split_once('.'))
+        else {
+            continue;
+        };
+        if part != "system" && part != "user" {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).map_err(|error| TemplateError::Io {
+            path: path.clone(),
+            error,
+        })?;
+        let text = Text::parse(&source).map_err(|message| TemplateError::Syntax {
+            path: path.clone(),
+            message,
+        })?;
+        let slots = sources.entry(locale.to_string()).or_default();
+        if part == "system" {
+            slots.0 = Some(text);
+        } else {
+            slots.1 = Some(text);
+        }
+    }
+    let mut locales = BTreeMap::new();
+    for (locale, slots) in sources {
+        let (Some(system), Some(user)) = slots else {
+            return Err(TemplateError::Incomplete {
+                template: id,
+                locale,
+            });
+        };
+        locales.insert(locale, (system, user));
+    }
+    if locales.is_empty() {
+        return Err(TemplateError::Incomplete {
+            template: id,
+            locale: "en".to_string(),
+        });
+    }
+    Ok(PromptTemplate { id, locales })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(pairs: &[(&'static str, &str)]) -> HashMap<&'static str, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (*name, value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn text_fills_variables_and_drops_empty_sections_with_their_lines() {
+        let text = Text::parse(
+            "Question: {{question}}\n{{#context}}\nContext: {{context}}\n{{/context}}\n{{^context}}\nNo context\n{{/context}}\nCards: {{ cards }}",
+        )
+        .unwrap();
+        assert_eq!(
+            "Question: Why?\nContext: Work\nCards: The Sun",
+            text.render(&variables(&[
+                ("question", "Why?"),
+                ("context", "Work"),
+                ("cards", "The Sun")
+            ]))
+        );
+        assert_eq!(
+            "Question: Why?\nNo context\nCards:",
+            text.render(&variables(&[("question", "Why?"), ("context", "")]))
+        );
+
+        assert!(
+            Text::parse("{{questoin}}")
+                .unwrap_err()
+                .contains("questoin")
+        );
+        assert!(Text::parse("{{#context}}open").is_err());
+        assert!(Text::parse("{{#context}}{{/spread}}").is_err());
+        assert!(Text::parse("{{question").is_err());
+    }
+
+    #[test]
+    fn templates_load_from_a_directory_and_pick_the_latest_version() {
+        let dir = std::env::temp_dir().join(format!("webtarot-templates-{}", std::process::id()));
+        let write = |path: &str, text: &str| {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, text).unwrap();
+        };
+        write("brief/1/system.en.md", "Be brief.");
+        write("brief/1/user.en.md", "{{question}}");
+        write("brief/2/system.en.md", "Be very brief.");
+        write("brief/2/user.en.md", "Q: {{question}}");
+        write("brief/2/notes.txt", "ignored");
+
+        let mut templates = PromptTemplates::default();
+        assert_eq!(2, templates.load_dir(&dir).unwrap());
+        assert_eq!(
+            vec!["brief@1", "brief@2", "classic@1", "classic@2"],
+            templates
+                .list()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+        let question = variables(&[("question", "Why?")]);
+        let latest = templates.get(Some("brief")).unwrap();
+        assert_eq!(2, latest.id.version);
+        // Locales the template was not written for fall back to English
+        assert_eq!(
+            Prompt {
+                system: "Be very brief.".to_string(),
+                user: "Q: Why?".to_string(),
+                ..Default::default()
+            },
+            latest.render("pt", &question)
+        );
+        assert_eq!(
+            "Why?",
+            templates
+                .get(Some("brief@1"))
+                .unwrap()
+                .render("en", &question)
+                .user
+        );
+        assert!(templates.get(Some("brief@3")).is_none());
+        assert!(latest.uses("question"));
+        assert!(!latest.uses("style"));
+        assert!(!templates.get(Some("classic@1")).unwrap().uses("style"));
+        let classic = templates.get(Some("classic@2")).unwrap();
+        assert!(classic.uses("style") && classic.uses("length"));
+
+        assert_eq!("classic", templates.get(None).unwrap().id.name);
+        templates.set_default("brief@1").unwrap();
+        assert_eq!(1, templates.get(None).unwrap().id.version);
+        assert!(templates.set_default("missing").is_err());
+
+        write("broken/1/system.en.md", "Be brief.");
+        assert!(matches!(
+            templates.load_dir(&dir),
+            Err(TemplateError::Incomplete { .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}